@@ -4,9 +4,10 @@ use std::cmp::Ordering;
 use std::f64::consts::PI;
 use std::time::{Duration, SystemTime};
 
+use crate::clock::{Clock, SystemClock};
 use crate::{
-    constants, ConvertToScale, FromWithSettings, GlickoSettings, Internal, InternalRating,
-    IntoWithSettings, Public, Rating, RatingScale,
+    constants, ConvertToScale, FromWithSettings, GlickoError, GlickoSettings, Internal,
+    InternalRating, IntoWithSettings, Public, Rating, RatingScale,
 };
 
 #[cfg(feature = "serde")]
@@ -69,7 +70,22 @@ impl<Scale: RatingScale> TimedRating<Scale> {
         Scale: ConvertToScale<Internal>,
         Internal: ConvertToScale<Scale>,
     {
-        self.rating_at(SystemTime::now(), settings)
+        self.rating_with_clock(&SystemClock, settings)
+    }
+
+    /// The rating with the deviation updated to the current time of `clock` after no games were played since the last update.
+    /// Convenience for `self.rating_at(clock.now(), settings)`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `last_updated` is after the current time of `clock`.
+    #[must_use]
+    pub fn rating_with_clock<C: Clock>(&self, clock: &C, settings: GlickoSettings) -> Rating<Scale>
+    where
+        Scale: ConvertToScale<Internal>,
+        Internal: ConvertToScale<Scale>,
+    {
+        self.rating_at(clock.now(), settings)
     }
 
     /// The rating with the deviation updated to the given time after no games were played since the last update.
@@ -89,6 +105,7 @@ impl<Scale: RatingScale> TimedRating<Scale> {
             internal_rating.volatility(),
             internal_rating,
             self.elapsed_rating_periods(time, settings.rating_period_duration),
+            settings,
         );
 
         InternalRating {
@@ -134,6 +151,8 @@ where
 pub struct Game<Scale: RatingScale> {
     opponent: Rating<Scale>,
     score: f64,
+    #[cfg_attr(feature = "serde", serde(default = "default_weight"))]
+    weight: f64,
 }
 
 /// A [`Game`] of [`Public`] scale.
@@ -148,11 +167,58 @@ impl<Scale: RatingScale> Game<Scale> {
     /// # Panics
     ///
     /// This function panics if `score` is less than `0.0` or greater than `1.0`.
+    /// See [`try_new`][Self::try_new] for a non-panicking version.
     #[must_use]
     pub fn new(opponent: Rating<Scale>, score: f64) -> Self {
-        assert!((0.0..=1.0).contains(&score));
+        Self::try_new(opponent, score).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates a new [`PublicGame`] with the given `opponent` and `score`.
+    /// `score` is a number between 0.0 (decisive opponent win) and `1.0` (decisive player win).
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidScore`] if `score` is less than `0.0` or greater than `1.0`.
+    pub fn try_new(opponent: Rating<Scale>, score: f64) -> Result<Self, GlickoError> {
+        Self::try_new_weighted(opponent, score, 1.0)
+    }
+
+    /// Creates a new [`PublicGame`] with the given `opponent`, `score` and `weight`.
+    /// `score` is a number between 0.0 (decisive opponent win) and `1.0` (decisive player win).
+    /// `weight` is the number of full games this game counts as, e.g. `0.5` for a game that should only count half.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `score` is less than `0.0` or greater than `1.0`, or if `weight` is not positive.
+    /// See [`try_new_weighted`][Self::try_new_weighted] for a non-panicking version.
+    #[must_use]
+    pub fn new_weighted(opponent: Rating<Scale>, score: f64, weight: f64) -> Self {
+        Self::try_new_weighted(opponent, score, weight).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates a new [`PublicGame`] with the given `opponent`, `score` and `weight`.
+    /// `score` is a number between 0.0 (decisive opponent win) and `1.0` (decisive player win).
+    /// `weight` is the number of full games this game counts as, e.g. `0.5` for a game that should only count half.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidScore`] if `score` is less than `0.0` or greater than `1.0`,
+    /// and [`GlickoError::InvalidWeight`] if `weight` is not positive.
+    pub fn try_new_weighted(
+        opponent: Rating<Scale>,
+        score: f64,
+        weight: f64,
+    ) -> Result<Self, GlickoError> {
+        validate_score(score)?;
+        if !weight.is_finite() || weight <= 0.0 {
+            return Err(GlickoError::InvalidWeight(weight));
+        }
 
-        Game { opponent, score }
+        Ok(Game {
+            opponent,
+            score,
+            weight,
+        })
     }
 
     /// The opponent's rating.
@@ -166,6 +232,18 @@ impl<Scale: RatingScale> Game<Scale> {
     pub fn score(&self) -> f64 {
         self.score
     }
+
+    /// The number of full games this game counts as.
+    /// This is `1.0` unless the game was created with [`new_weighted`][Self::new_weighted].
+    #[must_use]
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+}
+
+#[cfg(feature = "serde")]
+fn default_weight() -> f64 {
+    1.0
 }
 
 impl<Scale1: RatingScale, Scale2: RatingScale> FromWithSettings<Game<Scale1>> for Game<Scale2>
@@ -173,7 +251,11 @@ where
     Scale1: ConvertToScale<Scale2>,
 {
     fn from_with_settings(game: Game<Scale1>, settings: GlickoSettings) -> Self {
-        Game::new(game.opponent.into_with_settings(settings), game.score)
+        Game {
+            opponent: game.opponent.into_with_settings(settings),
+            score: game.score,
+            weight: game.weight,
+        }
     }
 }
 
@@ -206,15 +288,30 @@ impl<Scale: RatingScale> TimedGame<Scale> {
     /// # Panics
     ///
     /// This function panics if `score` is less than `0.0` or greater than `1.0`.
+    /// See [`try_new`][Self::try_new] for a non-panicking version.
     #[must_use]
     pub fn new(time: SystemTime, opponent: TimedRating<Scale>, score: f64) -> Self {
-        assert!((0.0..=1.0).contains(&score));
+        Self::try_new(time, opponent, score).unwrap_or_else(|err| panic!("{err}"))
+    }
 
-        TimedGame {
+    /// Creates a new [`TimedGame`] at the given `time` with the given `opponent` and `score`.
+    /// `score` is a number between 0.0 (decisive opponent win) and `1.0` (decisive player win).
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidScore`] if `score` is less than `0.0` or greater than `1.0`.
+    pub fn try_new(
+        time: SystemTime,
+        opponent: TimedRating<Scale>,
+        score: f64,
+    ) -> Result<Self, GlickoError> {
+        validate_score(score)?;
+
+        Ok(TimedGame {
             time,
             opponent,
             score,
-        }
+        })
     }
 
     /// The time this game was recorded.
@@ -313,11 +410,22 @@ impl<Scale: RatingScale> TimedOpponentGame<Scale> {
     /// # Panics
     ///
     /// This function panics if `score` is less than `0.0` or greater than `1.0`.
+    /// See [`try_new`][Self::try_new] for a non-panicking version.
     #[must_use]
     pub fn new(opponent: TimedRating<Scale>, score: f64) -> Self {
-        assert!((0.0..=1.0).contains(&score));
+        Self::try_new(opponent, score).unwrap_or_else(|err| panic!("{err}"))
+    }
 
-        TimedOpponentGame { opponent, score }
+    /// Creates a new [`TimedOpponentGame`] with the given `opponent` and the player's `score`.
+    /// `score` is a number between 0.0 (decisive opponent win) and `1.0` (decisive player win).
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidScore`] if `score` is less than `0.0` or greater than `1.0`.
+    pub fn try_new(opponent: TimedRating<Scale>, score: f64) -> Result<Self, GlickoError> {
+        validate_score(score)?;
+
+        Ok(TimedOpponentGame { opponent, score })
     }
 
     /// The opponent's rating.
@@ -522,11 +630,15 @@ pub fn rate_games(
 /// Calculates the new internal player rating after the given [`InternalGame`]s were played
 /// and the given amount of rating periods `elapsed_periods` were elapsed using the Glicko-2 algorithm.
 ///
+/// The new rating value is limited to the [rating floor][GlickoSettings::rating_floor]
+/// and [rating cap][GlickoSettings::rating_cap] of `settings`.
+///
 /// # Panics
 ///
 /// This function panics if `elapsed_periods` is less than `0`.
 ///
 /// It can also panic if `settings.convergence_tolerance()` is unreasonably low.
+/// See [`try_rate_games_untimed`] for a non-panicking version.
 #[must_use]
 pub fn rate_games_untimed(
     player_rating: InternalRating,
@@ -534,7 +646,29 @@ pub fn rate_games_untimed(
     elapsed_periods: f64,
     settings: GlickoSettings,
 ) -> InternalRating {
-    assert!(elapsed_periods >= 0.0);
+    try_rate_games_untimed(player_rating, results, elapsed_periods, settings)
+        .unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Calculates the new internal player rating after the given [`InternalGame`]s were played
+/// and the given amount of rating periods `elapsed_periods` were elapsed using the Glicko-2 algorithm.
+///
+/// The new rating value is limited to the [rating floor][GlickoSettings::rating_floor]
+/// and [rating cap][GlickoSettings::rating_cap] of `settings`.
+///
+/// # Errors
+///
+/// This function returns [`GlickoError::InvalidElapsedPeriods`] if `elapsed_periods` is less than `0`,
+/// and [`GlickoError::NoConvergence`] if `settings.convergence_tolerance()` is unreasonably low.
+pub fn try_rate_games_untimed(
+    player_rating: InternalRating,
+    results: &[InternalGame],
+    elapsed_periods: f64,
+    settings: GlickoSettings,
+) -> Result<InternalRating, GlickoError> {
+    if elapsed_periods.is_nan() || elapsed_periods < 0.0 {
+        return Err(GlickoError::InvalidElapsedPeriods(elapsed_periods));
+    }
 
     // Step 1. (initialising) doesn't apply, we have already set the starting ratings.
     // Step 2. (converting to internal scale) doesn't apply either, we get typed checked internal rating here
@@ -545,21 +679,32 @@ pub fn rate_games_untimed(
             player_rating.volatility(),
             player_rating,
             elapsed_periods,
+            settings,
         );
 
-        return InternalRating::new(
-            player_rating.rating(),
-            new_deviation,
-            player_rating.volatility(),
-        )
-        .into_with_settings(settings);
+        return Ok(settings.limit_rating(
+            InternalRating::new(
+                player_rating.rating(),
+                new_deviation,
+                player_rating.volatility(),
+            ),
+            None,
+        ));
     }
 
     // Step 3.
-    let estimated_variance = calculate_estimated_variance(player_rating, results.iter().copied());
+    let estimated_variance = calculate_estimated_variance(
+        player_rating,
+        results.iter().copied(),
+        settings.draw_parameter(),
+    );
 
     // Step 4.
-    let performance_sum = calculate_performance_sum(player_rating, results.iter().copied());
+    let performance_sum = calculate_performance_sum(
+        player_rating,
+        results.iter().copied(),
+        settings.draw_parameter(),
+    );
     let estimated_improvement =
         calculate_estimated_improvement(estimated_variance, performance_sum);
 
@@ -570,18 +715,125 @@ pub fn rate_games_untimed(
         player_rating,
         settings.volatility_change(),
         settings.convergence_tolerance(),
-    );
+    )?;
 
     // Step 6.
     let pre_rating_period_value =
-        calculate_pre_rating_period_value(new_volatility, player_rating, elapsed_periods);
+        calculate_pre_rating_period_value(new_volatility, player_rating, elapsed_periods, settings);
 
     // Step 7.
     let new_deviation = calculate_new_rating_deviation(pre_rating_period_value, estimated_variance);
+    let new_deviation = match settings.min_deviation() {
+        Some(min_deviation) => new_deviation.max(min_deviation / constants::RATING_SCALING_RATIO),
+        None => new_deviation,
+    };
     let new_rating = calculate_new_rating(new_deviation, player_rating, performance_sum);
 
     // Step 8. (converting back to public) doesn't apply
-    InternalRating::new(new_rating, new_deviation, new_volatility)
+    Ok(settings.limit_rating(
+        InternalRating::new(new_rating, new_deviation, new_volatility),
+        None,
+    ))
+}
+
+/// The probabilities of the outcomes of a game, from the perspective of one player.
+///
+/// See [`outcome_probabilities`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OutcomeProbabilities {
+    win: f64,
+    draw: f64,
+    loss: f64,
+}
+
+impl OutcomeProbabilities {
+    /// The probability that the player wins.
+    #[must_use]
+    pub fn win(&self) -> f64 {
+        self.win
+    }
+
+    /// The probability that the game ends in a draw.
+    ///
+    /// This is always `0.0` if the [draw parameter][GlickoSettings::draw_parameter] is `0.0`.
+    #[must_use]
+    pub fn draw(&self) -> f64 {
+        self.draw
+    }
+
+    /// The probability that the player loses.
+    #[must_use]
+    pub fn loss(&self) -> f64 {
+        self.loss
+    }
+
+    /// The score the player is expected to achieve on average, counting a draw as `0.5`.
+    #[must_use]
+    pub fn expected_score(&self) -> f64 {
+        self.win + 0.5 * self.draw
+    }
+
+    /// The variance of the score, which describes how much information a game carries.
+    #[must_use]
+    fn score_variance(&self) -> f64 {
+        // Equal to win + draw / 4 - expected_score², but without cancellation,
+        // and exactly e * (1 - e) from "Step 3." without draws.
+        self.win * self.loss + 0.25 * self.draw * (self.win + self.loss)
+    }
+}
+
+/// Calculates the probabilities that a player wins, draws or loses against an opponent.
+///
+/// Unlike the expected score in "Step 3." of [Glickman's paper](http://www.glicko.net/glicko/glicko2.pdf),
+/// this takes the deviations of both ratings into account, as the paper suggests for predicting outcomes.
+/// The less certain the ratings are, the closer the win and loss probabilities are to each other.
+///
+/// If the [draw parameter][GlickoSettings::draw_parameter] of `settings` is `0.0`, draws are not modelled,
+/// so the draw probability is `0.0`.
+#[must_use]
+pub fn outcome_probabilities(
+    player_rating: InternalRating,
+    opponent_rating: InternalRating,
+    settings: GlickoSettings,
+) -> OutcomeProbabilities {
+    let combined_deviation = f64::sqrt(
+        player_rating.deviation() * player_rating.deviation()
+            + opponent_rating.deviation() * opponent_rating.deviation(),
+    );
+
+    calculate_probabilities(
+        calculate_g(combined_deviation),
+        player_rating.rating(),
+        opponent_rating.rating(),
+        settings.draw_parameter(),
+    )
+}
+
+/// Calculates the score a player is expected to achieve against an opponent on average,
+/// between `0.0` (certain loss) and `1.0` (certain win).
+///
+/// This takes the deviations of both ratings and the [draw parameter][GlickoSettings::draw_parameter] into account,
+/// see [`outcome_probabilities`].
+/// The less certain the ratings are, the closer the expected score is to `0.5`.
+///
+/// If draws are impossible, this is the probability that the player wins.
+#[must_use]
+pub fn expected_score(
+    player_rating: InternalRating,
+    opponent_rating: InternalRating,
+    settings: GlickoSettings,
+) -> f64 {
+    outcome_probabilities(player_rating, opponent_rating, settings).expected_score()
+}
+
+/// Checks that `score` is between `0.0` and `1.0`.
+pub(crate) fn validate_score(score: f64) -> Result<(), GlickoError> {
+    if (0.0..=1.0).contains(&score) {
+        Ok(())
+    } else {
+        Err(GlickoError::InvalidScore(score))
+    }
 }
 
 /// Step 3.
@@ -596,6 +848,7 @@ pub fn rate_games_untimed(
 fn calculate_estimated_variance(
     player_rating: InternalRating,
     games: impl IntoIterator<Item = InternalGame>,
+    draw_parameter: f64,
 ) -> f64 {
     1.0 / games
         .into_iter()
@@ -603,9 +856,14 @@ fn calculate_estimated_variance(
             let opponent_rating = game.opponent();
 
             let g = calculate_g(opponent_rating.deviation());
-            let e = calculate_e(g, player_rating.rating(), opponent_rating.rating());
-
-            g * g * e * (1.0 - e)
+            let probabilities = calculate_probabilities(
+                g,
+                player_rating.rating(),
+                opponent_rating.rating(),
+                draw_parameter,
+            );
+
+            game.weight() * g * g * probabilities.score_variance()
         })
         .sum::<f64>()
 }
@@ -614,6 +872,7 @@ fn calculate_estimated_variance(
 fn calculate_performance_sum(
     player_rating: InternalRating,
     games: impl IntoIterator<Item = InternalGame>,
+    draw_parameter: f64,
 ) -> f64 {
     games
         .into_iter()
@@ -621,9 +880,15 @@ fn calculate_performance_sum(
             let opponent_rating = game.opponent();
 
             let g = calculate_g(opponent_rating.deviation());
-            let e = calculate_e(g, player_rating.rating(), opponent_rating.rating());
+            let e = calculate_probabilities(
+                g,
+                player_rating.rating(),
+                opponent_rating.rating(),
+                draw_parameter,
+            )
+            .expected_score();
 
-            g * (game.score() - e)
+            game.weight() * g * (game.score() - e)
         })
         .sum::<f64>()
 }
@@ -638,7 +903,7 @@ fn calculate_estimated_improvement(estimated_variance: f64, performance_sum: f64
 // Optimizer is prolly smart enough to notice we call it with the same value twice
 // Even if not, like, ... this is likely not a bottleneck
 #[must_use]
-fn calculate_g(deviation: f64) -> f64 {
+pub(crate) fn calculate_g(deviation: f64) -> f64 {
     1.0 / f64::sqrt(1.0 + 3.0 * deviation * deviation / (PI * PI))
 }
 
@@ -647,19 +912,62 @@ fn calculate_e(g: f64, player_rating: f64, opponent_rating: f64) -> f64 {
     1.0 / (1.0 + f64::exp(-g * (player_rating - opponent_rating)))
 }
 
+/// Calculates the outcome probabilities for "Step 3." and "Step 4.".
+///
+/// Without a draw parameter, this is just `E` from the paper as the win probability.
+/// Otherwise, Davidson's model is used, which reduces to the same win probability for a draw parameter of `0.0`:
+/// With `d = g * (player_rating - opponent_rating)`, the win, draw and loss probabilities are proportional to
+/// `exp(d / 2)`, `draw_parameter` and `exp(-d / 2)`.
+#[must_use]
+fn calculate_probabilities(
+    g: f64,
+    player_rating: f64,
+    opponent_rating: f64,
+    draw_parameter: f64,
+) -> OutcomeProbabilities {
+    if draw_parameter > 0.0 {
+        let difference = g * (player_rating - opponent_rating);
+
+        // Everything is divided by exp(|d| / 2) so nothing overflows
+        let half_underdog = f64::exp(-0.5 * difference.abs());
+        let underdog = half_underdog * half_underdog;
+        let draw = draw_parameter * half_underdog;
+        let total = 1.0 + underdog + draw;
+
+        let (win, loss) = if difference >= 0.0 {
+            (1.0, underdog)
+        } else {
+            (underdog, 1.0)
+        };
+
+        OutcomeProbabilities {
+            win: win / total,
+            draw: draw / total,
+            loss: loss / total,
+        }
+    } else {
+        let win = calculate_e(g, player_rating, opponent_rating);
+
+        OutcomeProbabilities {
+            win,
+            draw: 0.0,
+            loss: 1.0 - win,
+        }
+    }
+}
+
 /// Step 5.
 ///
-/// # Panics
+/// # Errors
 ///
-/// This function might panic if `convergence_tolerance` is unreasonably low.
-#[must_use]
+/// This function returns [`GlickoError::NoConvergence`] if `convergence_tolerance` is unreasonably low.
 fn calculate_new_volatility(
     estimated_improvement: f64,
     estimated_variance: f64,
     player_rating: InternalRating,
     volatility_change: f64,
     convergence_tolerance: f64,
-) -> f64 {
+) -> Result<f64, GlickoError> {
     let deviation = player_rating.deviation();
     let deviation_sq = deviation * deviation;
     let current_volatility = player_rating.volatility();
@@ -715,11 +1023,12 @@ fn calculate_new_volatility(
     // 4.
     let mut iteration = 0;
     while f64::abs(b - a) > convergence_tolerance {
-        assert!(
-            iteration <= constants::MAX_ITERATIONS,
-            "Maximum number of iterations ({}) in converging loop algorithm exceeded. Is the convergence tolerance ({}) unreasonably low?",
-            constants::MAX_ITERATIONS, convergence_tolerance
-        );
+        if iteration > constants::MAX_ITERATIONS {
+            return Err(GlickoError::NoConvergence {
+                max_iterations: constants::MAX_ITERATIONS,
+                convergence_tolerance,
+            });
+        }
 
         // (a)
         let c = a + (a - b) * f_a / (f_b - f_a);
@@ -742,22 +1051,32 @@ fn calculate_new_volatility(
     }
 
     // 5.
-    f64::exp(a / 2.0)
+    Ok(f64::exp(a / 2.0))
 }
 
 /// Step 6.
+///
+/// The result is limited to the maximum deviation of `settings`, like Lichess does.
 #[must_use]
 fn calculate_pre_rating_period_value(
     new_volatility: f64,
     player_rating: InternalRating,
     elapsed_periods: f64,
+    settings: GlickoSettings,
 ) -> f64 {
     let current_deviation = player_rating.deviation();
 
     // See Lichess' implementation: https://github.com/lichess-org/lila/blob/d6a175d25228b0f3d9053a30301fce90850ceb2d/modules/rating/src/main/java/glicko2/RatingCalculator.java#L316
-    f64::sqrt(
+    let pre_rating_period_value = f64::sqrt(
         current_deviation * current_deviation + elapsed_periods * new_volatility * new_volatility,
-    )
+    );
+
+    match settings.max_deviation() {
+        Some(max_deviation) => {
+            pre_rating_period_value.min(max_deviation / constants::RATING_SCALING_RATIO)
+        }
+        None => pre_rating_period_value,
+    }
 }
 
 /// Step 7.1.
@@ -783,10 +1102,10 @@ mod test {
     use std::time::{Duration, SystemTime};
 
     use crate::algorithm::{
-        rate_games, rate_games_untimed, PublicGame, PublicTimedGames, PublicTimedOpponentGame,
-        PublicTimedRating,
+        expected_score, outcome_probabilities, rate_games, rate_games_untimed, InternalGame,
+        PublicGame, PublicTimedGames, PublicTimedOpponentGame, PublicTimedRating,
     };
-    use crate::{FromWithSettings, GlickoSettings, IntoWithSettings, PublicRating};
+    use crate::{FromWithSettings, GlickoError, GlickoSettings, IntoWithSettings, PublicRating};
 
     macro_rules! assert_approx_eq {
         ($a:expr, $b:expr, $tolerance:expr) => {{
@@ -899,4 +1218,233 @@ mod test {
         assert_approx_eq!(new_public_rating.deviation(), 151.52, 0.01);
         assert_approx_eq!(new_public_rating.volatility(), 0.05999, 0.0001);
     }
+
+    #[test]
+    fn test_weighted_games() {
+        let settings = GlickoSettings::default();
+
+        let player = PublicRating::new(1500.0, 200.0, 0.06).into_with_settings(settings);
+        let opponent = PublicRating::new(1600.0, 100.0, 0.06).into_with_settings(settings);
+
+        // A game with weight 2 counts as two identical games
+        let weighted = rate_games_untimed(
+            player,
+            &[InternalGame::new_weighted(opponent, 1.0, 2.0)],
+            1.0,
+            settings,
+        );
+        let repeated = rate_games_untimed(
+            player,
+            &[
+                InternalGame::new(opponent, 1.0),
+                InternalGame::new(opponent, 1.0),
+            ],
+            1.0,
+            settings,
+        );
+
+        assert_approx_eq!(weighted.rating(), repeated.rating(), 0.000_000_001);
+        assert_approx_eq!(weighted.deviation(), repeated.deviation(), 0.000_000_001);
+        assert_approx_eq!(weighted.volatility(), repeated.volatility(), 0.000_000_001);
+
+        assert_eq!(
+            InternalGame::try_new_weighted(opponent, 1.0, 0.0),
+            Err(GlickoError::InvalidWeight(0.0)),
+        );
+    }
+
+    #[test]
+    fn test_expected_score() {
+        let settings = GlickoSettings::default();
+
+        let player = PublicRating::new(1700.0, 50.0, 0.06);
+        let opponent = PublicRating::new(1500.0, 50.0, 0.06);
+
+        assert_approx_eq!(player.expected_score(player, settings), 0.5, 0.000_000_001);
+
+        let score = player.expected_score(opponent, settings);
+        assert!(score > 0.5);
+        assert_approx_eq!(
+            score + opponent.expected_score(player, settings),
+            1.0,
+            0.000_000_001
+        );
+
+        // Uncertainty pulls the expected score towards 0.5
+        let uncertain_opponent = PublicRating::new(1500.0, 300.0, 0.06);
+        let uncertain_score = player.expected_score(uncertain_opponent, settings);
+        assert!(0.5 < uncertain_score && uncertain_score < score);
+
+        assert_approx_eq!(
+            expected_score(
+                player.into_with_settings(settings),
+                opponent.into_with_settings(settings),
+                settings
+            ),
+            score,
+            0.000_000_001
+        );
+    }
+
+    #[test]
+    fn test_draw_model() {
+        let settings = GlickoSettings::default().with_volatility_change(0.5);
+        // A draw rate of 30% between equal players
+        let draw_settings = settings.with_draw_parameter(2.0 * 0.3 / 0.7);
+
+        let player = PublicRating::new(1500.0, 50.0, 0.06);
+        let strong_opponent = PublicRating::new(1900.0, 50.0, 0.06);
+
+        let probabilities = player.outcome_probabilities(player, draw_settings);
+        assert_approx_eq!(probabilities.draw(), 0.3, 0.000_000_001);
+        assert_approx_eq!(probabilities.win(), probabilities.loss(), 0.000_000_001);
+
+        let probabilities = player.outcome_probabilities(strong_opponent, draw_settings);
+        assert_approx_eq!(
+            probabilities.win() + probabilities.draw() + probabilities.loss(),
+            1.0,
+            0.000_000_001
+        );
+        assert!(probabilities.draw() < 0.3);
+        assert!(probabilities.win() < probabilities.loss());
+        assert_approx_eq!(
+            probabilities.expected_score() + strong_opponent.expected_score(player, draw_settings),
+            1.0,
+            0.000_000_001
+        );
+
+        // Without a draw parameter, draws aren't modelled
+        let probabilities = player.outcome_probabilities(strong_opponent, settings);
+        assert!(probabilities.draw().abs() < f64::EPSILON);
+        assert_approx_eq!(
+            probabilities.win(),
+            player.expected_score(strong_opponent, settings),
+            0.000_000_001
+        );
+
+        // A draw against a much stronger opponent is less surprising if draws are modelled
+        let internal_player = player.into_with_settings(settings);
+        let draw = [InternalGame::new(
+            strong_opponent.into_with_settings(settings),
+            0.5,
+        )];
+        let gain = PublicRating::from_with_settings(
+            rate_games_untimed(internal_player, &draw, 1.0, settings),
+            settings,
+        )
+        .rating()
+            - player.rating();
+        let draw_gain = PublicRating::from_with_settings(
+            rate_games_untimed(internal_player, &draw, 1.0, draw_settings),
+            settings,
+        )
+        .rating()
+            - player.rating();
+        assert!(0.0 < draw_gain && draw_gain < gain);
+
+        // Draws between equal players don't change the rating
+        let even_draw = [InternalGame::new(internal_player, 0.5)];
+        let new_rating = rate_games_untimed(internal_player, &even_draw, 1.0, draw_settings);
+        assert_approx_eq!(new_rating.rating(), internal_player.rating(), 0.000_000_001);
+
+        assert_approx_eq!(
+            outcome_probabilities(
+                internal_player,
+                strong_opponent.into_with_settings(settings),
+                draw_settings
+            )
+            .win(),
+            player
+                .outcome_probabilities(strong_opponent, draw_settings)
+                .win(),
+            0.000_000_001
+        );
+
+        assert_eq!(
+            settings.try_with_draw_parameter(-1.0),
+            Err(GlickoError::InvalidDrawParameter(-1.0)),
+        );
+    }
+
+    #[test]
+    fn test_max_deviation() {
+        let settings = GlickoSettings::default();
+        let capped_settings = settings.with_max_deviation(350.0);
+
+        let start_time = SystemTime::UNIX_EPOCH;
+        let time = start_time + Duration::from_secs(60 * 60 * 24 * 365 * 2);
+
+        let rating = PublicRating::new(1500.0, 300.0, 0.06);
+        let player = PublicTimedRating::new(start_time, rating);
+
+        // Without a limit, the deviation of an inactive player exceeds the start deviation
+        assert!(player.rating_at(time, settings).deviation() > 350.0);
+        assert_approx_eq!(
+            player.rating_at(time, capped_settings).deviation(),
+            350.0,
+            0.000_001
+        );
+
+        let internal_player = rating.into_with_settings(capped_settings);
+        let new_rating: PublicRating =
+            rate_games_untimed(internal_player, &[], 1000.0, capped_settings)
+                .into_with_settings(capped_settings);
+        assert_approx_eq!(new_rating.deviation(), 350.0, 0.000_001);
+
+        assert_eq!(
+            settings.try_with_max_deviation(f64::INFINITY),
+            Err(GlickoError::InvalidDeviation(f64::INFINITY)),
+        );
+
+        let opponent = PublicRating::new(1500.0, 50.0, 0.06).into_with_settings(capped_settings);
+        let new_rating: PublicRating = rate_games_untimed(
+            internal_player,
+            &[InternalGame::new(opponent, 1.0)],
+            1000.0,
+            capped_settings,
+        )
+        .into_with_settings(capped_settings);
+        assert!(new_rating.deviation() < 350.0);
+
+        // Deviations that only shrink are not affected
+        let active_player = PublicRating::new(1500.0, 50.0, 0.06).into_with_settings(settings);
+        let games = [InternalGame::new(opponent, 0.5)];
+        assert_eq!(
+            rate_games_untimed(active_player, &games, 1.0, capped_settings),
+            rate_games_untimed(active_player, &games, 1.0, settings),
+        );
+    }
+
+    #[test]
+    fn test_min_deviation() {
+        let settings = GlickoSettings::default();
+        let limited_settings = settings.with_min_deviation(60.0);
+
+        let player = PublicRating::new(1500.0, 50.0, 0.06).into_with_settings(settings);
+        let opponent = PublicRating::new(1500.0, 50.0, 0.06).into_with_settings(settings);
+        let games = [InternalGame::new(opponent, 1.0); 20];
+
+        let new_rating: PublicRating =
+            rate_games_untimed(player, &games, 1.0, settings).into_with_settings(settings);
+        let limited_rating: PublicRating =
+            rate_games_untimed(player, &games, 1.0, limited_settings).into_with_settings(settings);
+
+        assert!(new_rating.deviation() < 60.0);
+        assert_approx_eq!(limited_rating.deviation(), 60.0, 0.000_001);
+        // The rating moves more with the higher deviation
+        assert!(limited_rating.rating() > new_rating.rating());
+
+        // Deviations are not raised without games
+        let new_rating = rate_games_untimed(player, &[], 0.0, limited_settings);
+        assert_approx_eq!(new_rating.deviation(), player.deviation(), 0.000_000_001);
+
+        assert_eq!(
+            limited_settings.try_with_max_deviation(50.0),
+            Err(GlickoError::InvalidDeviation(50.0)),
+        );
+        assert_eq!(
+            settings.try_with_min_deviation(f64::INFINITY),
+            Err(GlickoError::InvalidDeviation(f64::INFINITY)),
+        );
+    }
 }
@@ -0,0 +1,198 @@
+//! This mod defines the [`Clock`] trait, which [`RatingEngine`][crate::engine::RatingEngine] uses to get the current time.
+//!
+//! By default, the [`SystemClock`] is used.
+//! With a [`ManualClock`], the methods without the `_at` suffix can be used in tests and simulations,
+//! and an [`OffsetClock`] can shift time, e.g. to continue a replayed event log from a different point in time.
+
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, SystemTime};
+
+/// A source of the current time.
+pub trait Clock {
+    /// The current time.
+    #[must_use]
+    fn now(&self) -> SystemTime;
+}
+
+/// A [`Clock`] that returns the current system time, see [`SystemTime::now`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A [`Clock`] that only changes its time when told to.
+///
+/// Clones of a [`ManualClock`] share their time,
+/// so a clone can be kept to control the clock of an engine.
+///
+/// # Example
+///
+/// ```
+/// use std::time::{Duration, SystemTime};
+///
+/// use instant_glicko_2::GlickoSettings;
+/// use instant_glicko_2::clock::{Clock, ManualClock};
+/// use instant_glicko_2::engine::RatingEngine;
+///
+/// let clock = ManualClock::new(SystemTime::UNIX_EPOCH);
+///
+/// let mut engine = RatingEngine::start_new_with_clock(clock.clone(), GlickoSettings::default());
+/// assert_eq!(engine.last_rating_period_start(), SystemTime::UNIX_EPOCH);
+///
+/// clock.advance(Duration::from_secs(60 * 60 * 24 * 3));
+/// assert_eq!(engine.clock().now(), clock.now());
+///
+/// let (_, closed_periods) = engine.maybe_close_rating_periods();
+/// assert!(closed_periods > 0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ManualClock {
+    now: Arc<Mutex<SystemTime>>,
+}
+
+impl ManualClock {
+    /// Creates a new [`ManualClock`] that is set to `now`.
+    #[must_use]
+    pub fn new(now: SystemTime) -> Self {
+        ManualClock {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    /// Sets the time of this clock and all its clones to `now`.
+    pub fn set(&self, now: SystemTime) {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner) = now;
+    }
+
+    /// Advances the time of this clock and all its clones by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner) += duration;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl PartialEq for ManualClock {
+    fn eq(&self, other: &Self) -> bool {
+        self.now() == other.now()
+    }
+}
+
+/// A [`Clock`] that is a fixed duration ahead of or behind another clock.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OffsetClock<C = SystemClock> {
+    clock: C,
+    offset: Duration,
+    ahead: bool,
+}
+
+impl<C: Clock> OffsetClock<C> {
+    /// Creates a new [`OffsetClock`] that is `offset` ahead of `clock`.
+    #[must_use]
+    pub fn ahead(clock: C, offset: Duration) -> Self {
+        OffsetClock {
+            clock,
+            offset,
+            ahead: true,
+        }
+    }
+
+    /// Creates a new [`OffsetClock`] that is `offset` behind `clock`.
+    #[must_use]
+    pub fn behind(clock: C, offset: Duration) -> Self {
+        OffsetClock {
+            clock,
+            offset,
+            ahead: false,
+        }
+    }
+
+    /// Creates a new [`OffsetClock`] that is currently set to `now`, and advances along with `clock` from then on.
+    #[must_use]
+    pub fn starting_at(clock: C, now: SystemTime) -> Self {
+        let clock_now = clock.now();
+
+        match clock_now.duration_since(now) {
+            Ok(offset) => Self::behind(clock, offset),
+            Err(err) => Self::ahead(clock, err.duration()),
+        }
+    }
+
+    /// The clock this clock is offset from.
+    #[must_use]
+    pub fn inner(&self) -> &C {
+        &self.clock
+    }
+}
+
+impl<C: Clock> Clock for OffsetClock<C> {
+    fn now(&self) -> SystemTime {
+        if self.ahead {
+            self.clock.now() + self.offset
+        } else {
+            self.clock.now() - self.offset
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, SystemTime};
+
+    use super::{Clock, ManualClock, OffsetClock};
+    use crate::engine::{MatchResult, RatingEngine};
+    use crate::{GlickoSettings, Public};
+
+    #[test]
+    fn test_clocks() {
+        let settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(60));
+
+        let start_time = SystemTime::UNIX_EPOCH + Duration::from_secs(600);
+
+        let clock = ManualClock::new(start_time);
+        let offset_clock = OffsetClock::starting_at(clock.clone(), SystemTime::UNIX_EPOCH);
+        assert_eq!(offset_clock.now(), SystemTime::UNIX_EPOCH);
+        assert_eq!(
+            OffsetClock::ahead(clock.clone(), Duration::from_secs(5)).now(),
+            start_time + Duration::from_secs(5),
+        );
+
+        let mut engine = RatingEngine::start_new_with_clock(clock.clone(), settings);
+        let mut at_engine = RatingEngine::start_new_at(start_time, settings);
+
+        let player_1 = engine.register_player(settings.start_rating()).0;
+        let player_2 = engine.register_player(settings.start_rating()).0;
+        at_engine.register_player_at(settings.start_rating(), start_time);
+        at_engine.register_player_at(settings.start_rating(), start_time);
+
+        clock.advance(Duration::from_secs(30));
+        engine.register_result(player_1, player_2, &MatchResult::Win);
+        at_engine.register_result_at(player_1, player_2, &MatchResult::Win, clock.now());
+
+        clock.advance(Duration::from_secs(100));
+        assert_eq!(
+            offset_clock.now(),
+            SystemTime::UNIX_EPOCH + Duration::from_secs(130)
+        );
+
+        // Using the clock is the same as passing its time explicitly
+        let (rating, closed_periods) = engine.player_rating::<Public>(player_1);
+        assert_eq!(closed_periods, 2);
+        assert_eq!(
+            (rating, closed_periods),
+            at_engine.player_rating_at::<Public>(player_1, clock.now()),
+        );
+
+        let at_engine = at_engine.with_clock(clock);
+        assert_eq!(engine, at_engine);
+    }
+}
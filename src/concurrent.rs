@@ -0,0 +1,812 @@
+//! This mod defines the [`ConcurrentRatingEngine`] struct, a variant of [`RatingEngine`][crate::engine::RatingEngine] that can be shared between threads.
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard};
+use std::time::SystemTime;
+
+use crate::algorithm::{self, InternalGame};
+use crate::clock::{Clock, SystemClock};
+use crate::engine::{PlayerHandle, Score};
+use crate::util::GenerationalArena;
+use crate::{
+    ConvertToScale, GlickoError, GlickoSettings, Internal, InternalRating, IntoWithSettings,
+    Rating, RatingScale,
+};
+
+/// A player as managed by [`ConcurrentRatingEngine`].
+#[derive(Debug)]
+struct ConcurrentPlayer {
+    // The rating at the start of the rating period with the index `rating_period`.
+    rating: InternalRating,
+    rating_period: u64,
+    current_rating_period_results: Vec<InternalGame>,
+}
+
+impl ConcurrentPlayer {
+    /// Closes the rating periods of this player until the rating period with the index `rating_period` is the current one.
+    ///
+    /// All new ratings are calculated before the player is modified,
+    /// so if an error is returned, nothing is changed.
+    fn close_rating_periods(
+        &mut self,
+        rating_period: u64,
+        settings: GlickoSettings,
+    ) -> Result<(), GlickoError> {
+        if rating_period <= self.rating_period {
+            return Ok(());
+        }
+
+        // The results are all in the first rating period that is closed
+        let mut rating = algorithm::try_rate_games_untimed(
+            self.rating,
+            &self.current_rating_period_results,
+            1.0,
+            settings,
+        )?;
+        for _ in self.rating_period + 1..rating_period {
+            rating = algorithm::try_rate_games_untimed(rating, &[], 1.0, settings)?;
+        }
+
+        self.rating = rating;
+        self.rating_period = rating_period;
+        self.current_rating_period_results.clear();
+
+        Ok(())
+    }
+}
+
+/// A variant of [`RatingEngine`][crate::engine::RatingEngine] that is [`Send`] and [`Sync`],
+/// so results can be registered from many threads at the same time.
+///
+/// Every player is locked separately.
+/// Registering a result only locks the two participants,
+/// and only briefly locks the collection of players for reading.
+///
+/// Rating periods aren't closed for all players at once.
+/// Instead, every player's elapsed rating periods are closed the next time the player is accessed,
+/// which leads to the same ratings as closing them all at once like [`RatingEngine`][crate::engine::RatingEngine] does.
+/// [`maybe_close_rating_periods`][Self::maybe_close_rating_periods] closes them for all players,
+/// but still locks only one player at a time.
+///
+/// This engine only supports the basic operations of [`RatingEngine`][crate::engine::RatingEngine].
+/// Results can't be retracted or amended, and the settings can't be changed.
+///
+/// The current time is taken from a [`Clock`], which is the [`SystemClock`] unless the engine was created with
+/// [`start_new_with_clock`][ConcurrentRatingEngine::start_new_with_clock].
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// use instant_glicko_2::{GlickoSettings, PublicRating};
+/// use instant_glicko_2::concurrent::ConcurrentRatingEngine;
+/// use instant_glicko_2::engine::MatchResult;
+///
+/// let settings = GlickoSettings::default();
+///
+/// let engine = Arc::new(ConcurrentRatingEngine::start_new(settings));
+///
+/// let alice = engine.register_player(settings.start_rating());
+/// let bob = engine.register_player(settings.start_rating());
+///
+/// let worker = {
+///     let engine = Arc::clone(&engine);
+///     thread::spawn(move || engine.register_result(alice, bob, &MatchResult::Win))
+/// };
+/// worker.join().unwrap();
+///
+/// let alice_rating: PublicRating = engine.player_rating(alice);
+/// assert!(alice_rating.rating() > settings.start_rating().rating());
+/// ```
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug)]
+pub struct ConcurrentRatingEngine<C = SystemClock> {
+    start_time: SystemTime,
+    settings: GlickoSettings,
+    managed_players: RwLock<GenerationalArena<Arc<Mutex<ConcurrentPlayer>>>>,
+    clock: C,
+}
+
+impl ConcurrentRatingEngine {
+    /// Creates an empty [`ConcurrentRatingEngine`] with the first rating period starting now.
+    #[must_use]
+    pub fn start_new(settings: GlickoSettings) -> Self {
+        Self::start_new_with_clock(SystemClock, settings)
+    }
+
+    /// Creates an empty [`ConcurrentRatingEngine`] with the first rating period starting at the given point in time.
+    ///
+    /// This function is meant mostly for testability.
+    #[must_use]
+    pub fn start_new_at(start_time: SystemTime, settings: GlickoSettings) -> Self {
+        ConcurrentRatingEngine {
+            start_time,
+            settings,
+            managed_players: RwLock::new(GenerationalArena::new()),
+            clock: SystemClock,
+        }
+    }
+}
+
+impl<C: Clock> ConcurrentRatingEngine<C> {
+    /// Creates an empty [`ConcurrentRatingEngine`] that gets the current time from `clock`,
+    /// with the first rating period starting now.
+    #[must_use]
+    pub fn start_new_with_clock(clock: C, settings: GlickoSettings) -> Self {
+        ConcurrentRatingEngine {
+            start_time: clock.now(),
+            settings,
+            managed_players: RwLock::new(GenerationalArena::new()),
+            clock,
+        }
+    }
+
+    /// The clock this engine gets the current time from.
+    #[must_use]
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// The start time of the first rating period of this engine.
+    #[must_use]
+    pub fn start_time(&self) -> SystemTime {
+        self.start_time
+    }
+
+    /// The settings of this engine.
+    #[must_use]
+    pub fn settings(&self) -> GlickoSettings {
+        self.settings
+    }
+
+    /// Returns the handles of all registered players.
+    #[must_use]
+    pub fn player_handles(&self) -> Vec<PlayerHandle> {
+        self.read_players().indices().map(PlayerHandle).collect()
+    }
+
+    /// The number of registered players.
+    #[must_use]
+    pub fn player_count(&self) -> usize {
+        self.read_players().len()
+    }
+
+    /// Returns `true` if `player` is currently registered with this engine.
+    #[must_use]
+    pub fn contains_player(&self, player: PlayerHandle) -> bool {
+        self.read_players().contains(player.0)
+    }
+
+    /// Registers a new player with a given rating to this engine at the start of the current rating period.
+    ///
+    /// # Returns
+    ///
+    /// A value that can be later used to identify this player with this engine.
+    pub fn register_player<Scale>(&self, rating: Rating<Scale>) -> PlayerHandle
+    where
+        Scale: RatingScale + ConvertToScale<Internal>,
+    {
+        self.register_player_at(rating, self.clock.now())
+    }
+
+    /// Registers a new player with a given rating to this engine at the start of what is the current rating period at the given time.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A value that can be later used to identify this player with this engine.
+    pub fn register_player_at<Scale>(&self, rating: Rating<Scale>, time: SystemTime) -> PlayerHandle
+    where
+        Scale: RatingScale + ConvertToScale<Internal>,
+    {
+        let player = ConcurrentPlayer {
+            rating: rating.into_with_settings(self.settings),
+            rating_period: self.rating_period_at(time).0,
+            current_rating_period_results: Vec::new(),
+        };
+
+        PlayerHandle(
+            self.managed_players
+                .write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(Arc::new(Mutex::new(player))),
+        )
+    }
+
+    /// Removes a player from this engine.
+    ///
+    /// Results that were already registered against this player in the current rating period
+    /// stay part of their opponents' current rating period results.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the player was removed, or `false` if `player` was already removed or doesn't belong to this engine.
+    pub fn remove_player(&self, player: PlayerHandle) -> bool {
+        self.managed_players
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(player.0)
+            .is_some()
+    }
+
+    /// Registers a result in the current rating period.
+    /// Only the two players are locked for this.
+    ///
+    /// See [`RatingEngine::register_result`][crate::engine::RatingEngine::register_result].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if one of the players doesn't belong to this engine,
+    /// or if `score` is not between `0.0` and `1.0`.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn register_result<S: Score>(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        score: &S,
+    ) {
+        self.register_result_at(player_1, player_2, score, self.clock.now());
+    }
+
+    /// Registers a result at the given time in the current rating period.
+    /// Only the two players are locked for this.
+    ///
+    /// If a player's rating periods were already closed until a later time,
+    /// the result is registered in that player's current rating period.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if one of the players doesn't belong to this engine,
+    /// or if `score` is not between `0.0` and `1.0`.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn register_result_at<S: Score>(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        score: &S,
+        time: SystemTime,
+    ) {
+        self.try_register_result_at(player_1, player_2, score, time)
+            .unwrap_or_else(|err| panic!("{err}"));
+    }
+
+    /// Registers a result in the current rating period.
+    /// Only the two players are locked for this.
+    ///
+    /// See [`RatingEngine::register_result`][crate::engine::RatingEngine::register_result].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this engine,
+    /// [`GlickoError::InvalidScore`] if `score` is not between `0.0` and `1.0`,
+    /// and [`GlickoError::NoConvergence`] if closing a rating period failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    /// If an error is returned, the result has not been registered.
+    pub fn try_register_result<S: Score>(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        score: &S,
+    ) -> Result<(), GlickoError> {
+        self.try_register_result_at(player_1, player_2, score, self.clock.now())
+    }
+
+    /// Registers a result at the given time in the current rating period.
+    /// Only the two players are locked for this.
+    ///
+    /// If a player's rating periods were already closed until a later time,
+    /// the result is registered in that player's current rating period.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this engine,
+    /// [`GlickoError::InvalidScore`] if `score` is not between `0.0` and `1.0`,
+    /// and [`GlickoError::NoConvergence`] if closing a rating period failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    /// If an error is returned, the result has not been registered.
+    pub fn try_register_result_at<S: Score>(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        score: &S,
+        time: SystemTime,
+    ) -> Result<(), GlickoError> {
+        let player_1_score = score.player_score();
+        let player_2_score = score.opponent_score();
+
+        algorithm::validate_score(player_1_score)?;
+        algorithm::validate_score(player_2_score)?;
+
+        let player_1_data = self.player(player_1)?;
+        let player_2_data = self.player(player_2)?;
+
+        let rating_period = self.rating_period_at(time).0;
+
+        if player_1 == player_2 {
+            let mut player = lock_player(&player_1_data);
+            player.close_rating_periods(rating_period, self.settings)?;
+
+            let rating = player.rating;
+            player
+                .current_rating_period_results
+                .push(InternalGame::new(rating, player_1_score));
+            player
+                .current_rating_period_results
+                .push(InternalGame::new(rating, player_2_score));
+
+            return Ok(());
+        }
+
+        // Players are always locked in the same order so that concurrent results can't deadlock.
+        let (mut player_1_data, mut player_2_data) = if player_1 < player_2 {
+            let player_1_data = lock_player(&player_1_data);
+            (player_1_data, lock_player(&player_2_data))
+        } else {
+            let player_2_data = lock_player(&player_2_data);
+            (lock_player(&player_1_data), player_2_data)
+        };
+
+        // Both players need to be in the same rating period for the result
+        let rating_period = rating_period
+            .max(player_1_data.rating_period)
+            .max(player_2_data.rating_period);
+        player_1_data.close_rating_periods(rating_period, self.settings)?;
+        player_2_data.close_rating_periods(rating_period, self.settings)?;
+
+        let player_1_game = InternalGame::new(player_2_data.rating, player_1_score);
+        let player_2_game = InternalGame::new(player_1_data.rating, player_2_score);
+
+        player_1_data
+            .current_rating_period_results
+            .push(player_1_game);
+        player_2_data
+            .current_rating_period_results
+            .push(player_2_game);
+
+        Ok(())
+    }
+
+    /// The current rating of a player, including the results of the current rating period.
+    /// This closes the player's elapsed rating periods.
+    ///
+    /// See [`RatingEngine::player_rating`][crate::engine::RatingEngine::player_rating].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `player` doesn't belong to this engine.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    #[must_use]
+    pub fn player_rating<Scale: RatingScale>(&self, player: PlayerHandle) -> Rating<Scale>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.player_rating_at(player, self.clock.now())
+    }
+
+    /// The rating of a player at the given time, including the results of the current rating period.
+    /// This closes the player's rating periods that have elapsed by `time`.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `player` doesn't belong to this engine.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    #[must_use]
+    pub fn player_rating_at<Scale: RatingScale>(
+        &self,
+        player: PlayerHandle,
+        time: SystemTime,
+    ) -> Rating<Scale>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.try_player_rating_at(player, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// The current rating of a player, including the results of the current rating period.
+    /// This closes the player's elapsed rating periods.
+    ///
+    /// See [`RatingEngine::player_rating`][crate::engine::RatingEngine::player_rating].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if `player` doesn't belong to this engine,
+    /// and [`GlickoError::NoConvergence`] if the rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_player_rating<Scale: RatingScale>(
+        &self,
+        player: PlayerHandle,
+    ) -> Result<Rating<Scale>, GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.try_player_rating_at(player, self.clock.now())
+    }
+
+    /// The rating of a player at the given time, including the results of the current rating period.
+    /// This closes the player's rating periods that have elapsed by `time`.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if `player` doesn't belong to this engine,
+    /// and [`GlickoError::NoConvergence`] if the rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_player_rating_at<Scale: RatingScale>(
+        &self,
+        player: PlayerHandle,
+        time: SystemTime,
+    ) -> Result<Rating<Scale>, GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        let player_data = self.player(player)?;
+        let mut player_data = lock_player(&player_data);
+
+        let (rating_period, elapsed_periods) = self.rating_period_at(time);
+        player_data.close_rating_periods(rating_period, self.settings)?;
+
+        // If the player's rating periods were already closed until a later time, no time has elapsed in their current one
+        let elapsed_periods = if player_data.rating_period == rating_period {
+            elapsed_periods
+        } else {
+            0.0
+        };
+
+        let rating = algorithm::try_rate_games_untimed(
+            player_data.rating,
+            &player_data.current_rating_period_results,
+            elapsed_periods,
+            self.settings,
+        )?;
+
+        Ok(rating.into_with_settings(self.settings))
+    }
+
+    /// Closes all rating periods that have elapsed by now for all players.
+    /// This doesn't need to be called manually.
+    ///
+    /// Only one player is locked at a time, so results can be registered while this runs.
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn maybe_close_rating_periods(&self) {
+        self.maybe_close_rating_periods_at(self.clock.now());
+    }
+
+    /// Closes all rating periods that have elapsed by a given point in time for all players.
+    /// This doesn't need to be called manually.
+    ///
+    /// Only one player is locked at a time, so results can be registered while this runs.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn maybe_close_rating_periods_at(&self, time: SystemTime) {
+        self.try_maybe_close_rating_periods_at(time)
+            .unwrap_or_else(|err| panic!("{err}"));
+    }
+
+    /// Closes all rating periods that have elapsed by now for all players.
+    /// This doesn't need to be called manually.
+    ///
+    /// Only one player is locked at a time, so results can be registered while this runs.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::NoConvergence`] if the rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    /// In that case, the rating periods of the players that weren't processed yet stay open.
+    pub fn try_maybe_close_rating_periods(&self) -> Result<(), GlickoError> {
+        self.try_maybe_close_rating_periods_at(self.clock.now())
+    }
+
+    /// Closes all rating periods that have elapsed by a given point in time for all players.
+    /// This doesn't need to be called manually.
+    ///
+    /// Only one player is locked at a time, so results can be registered while this runs.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::NoConvergence`] if the rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    /// In that case, the rating periods of the players that weren't processed yet stay open.
+    pub fn try_maybe_close_rating_periods_at(&self, time: SystemTime) -> Result<(), GlickoError> {
+        let rating_period = self.rating_period_at(time).0;
+
+        // Don't hold the lock on all players while closing the rating periods
+        let players: Vec<_> = self
+            .read_players()
+            .iter()
+            .map(|(_, player)| Arc::clone(player))
+            .collect();
+
+        for player in players {
+            lock_player(&player).close_rating_periods(rating_period, self.settings)?;
+        }
+
+        Ok(())
+    }
+
+    /// The index of the rating period that is the current one at the given time,
+    /// and the amount of the rating period that has elapsed by then as a fraction.
+    fn rating_period_at(&self, time: SystemTime) -> (u64, f64) {
+        if let Ok(elapsed_duration) = time.duration_since(self.start_time) {
+            let elapsed_periods = elapsed_duration.as_secs_f64()
+                / self.settings.rating_period_duration().as_secs_f64();
+
+            // We won't have negative elapsed_periods. Truncation this is the wanted result.
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let rating_period = elapsed_periods as u64;
+
+            (rating_period, elapsed_periods.fract())
+        } else {
+            (0, 0.0)
+        }
+    }
+
+    fn read_players(&self) -> RwLockReadGuard<'_, GenerationalArena<Arc<Mutex<ConcurrentPlayer>>>> {
+        self.managed_players
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn player(&self, player: PlayerHandle) -> Result<Arc<Mutex<ConcurrentPlayer>>, GlickoError> {
+        self.read_players()
+            .get(player.0)
+            .map(Arc::clone)
+            .ok_or(GlickoError::UnknownPlayer(player))
+    }
+}
+
+/// Locks a player.
+///
+/// Players are only modified after all calculations succeeded,
+/// so a player whose lock was poisoned is still consistent.
+fn lock_player(player: &Mutex<ConcurrentPlayer>) -> MutexGuard<'_, ConcurrentPlayer> {
+    player.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, SystemTime};
+
+    use super::ConcurrentRatingEngine;
+    use crate::clock::{Clock, ManualClock};
+    use crate::engine::{MatchResult, RatingEngine};
+    use crate::{GlickoError, GlickoSettings, Public, PublicRating};
+
+    #[test]
+    fn test_concurrent_engine() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ConcurrentRatingEngine>();
+
+        let settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(60));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let concurrent_engine =
+            Arc::new(ConcurrentRatingEngine::start_new_at(start_time, settings));
+        let mut engine = RatingEngine::start_new_at(start_time, settings);
+
+        let players: Vec<_> = (0..8)
+            .map(|_| {
+                (
+                    concurrent_engine.register_player_at(settings.start_rating(), start_time),
+                    engine
+                        .register_player_at(settings.start_rating(), start_time)
+                        .0,
+                )
+            })
+            .collect();
+
+        // Every worker registers results between its own pair of players in the first two rating periods
+        let workers: Vec<_> = players
+            .chunks(2)
+            .map(|pair| {
+                let concurrent_engine = Arc::clone(&concurrent_engine);
+                let (player_1, player_2) = (pair[0].0, pair[1].0);
+
+                thread::spawn(move || {
+                    for secs in [10, 20, 70] {
+                        concurrent_engine.register_result_at(
+                            player_1,
+                            player_2,
+                            &MatchResult::Win,
+                            start_time + Duration::from_secs(secs),
+                        );
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        for secs in [10, 20, 70] {
+            for pair in players.chunks(2) {
+                engine.register_result_at(
+                    pair[0].1,
+                    pair[1].1,
+                    &MatchResult::Win,
+                    start_time + Duration::from_secs(secs),
+                );
+            }
+        }
+
+        // Closing rating periods lazily per player leads to the same ratings
+        let time = start_time + Duration::from_secs(150);
+        concurrent_engine.maybe_close_rating_periods_at(start_time + Duration::from_secs(100));
+        for &(concurrent_player, player) in &players {
+            assert_eq!(
+                concurrent_engine.player_rating_at::<Public>(concurrent_player, time),
+                engine.player_rating_at::<Public>(player, time).0,
+            );
+        }
+
+        let winner_rating: PublicRating = concurrent_engine.player_rating_at(players[0].0, time);
+        assert!(winner_rating.rating() > settings.start_rating().rating());
+
+        let removed_player = players[1].0;
+        assert!(concurrent_engine.remove_player(removed_player));
+        assert_eq!(concurrent_engine.player_count(), 7);
+        assert_eq!(
+            concurrent_engine.try_register_result_at(
+                players[0].0,
+                removed_player,
+                &MatchResult::Win,
+                time,
+            ),
+            Err(GlickoError::UnknownPlayer(removed_player)),
+        );
+
+        // The methods without the `_at` suffix get the time from the clock
+        let clock = ManualClock::new(start_time);
+        let clocked_engine = ConcurrentRatingEngine::start_new_with_clock(clock.clone(), settings);
+        let expected_engine = ConcurrentRatingEngine::start_new_at(start_time, settings);
+
+        let player_1 = clocked_engine.register_player(settings.start_rating());
+        let player_2 = clocked_engine.register_player(settings.start_rating());
+        clocked_engine.register_result(player_1, player_2, &MatchResult::Win);
+        clock.advance(Duration::from_secs(90));
+        clocked_engine.maybe_close_rating_periods();
+
+        let expected_player_1 =
+            expected_engine.register_player_at(settings.start_rating(), start_time);
+        let expected_player_2 =
+            expected_engine.register_player_at(settings.start_rating(), start_time);
+        expected_engine.register_result_at(
+            expected_player_1,
+            expected_player_2,
+            &MatchResult::Win,
+            start_time,
+        );
+        assert_eq!(
+            clocked_engine.player_rating::<Public>(player_1),
+            expected_engine.player_rating_at::<Public>(expected_player_1, clock.now()),
+        );
+    }
+
+    #[test]
+    fn test_concurrent_engine_overlapping_pairs() {
+        let settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(60));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+        let result_time = start_time + Duration::from_secs(70);
+
+        let concurrent_engine =
+            Arc::new(ConcurrentRatingEngine::start_new_at(start_time, settings));
+        let mut engine = RatingEngine::start_new_at(start_time, settings);
+
+        let players: Vec<_> = (0..4)
+            .map(|_| {
+                (
+                    concurrent_engine.register_player_at(settings.start_rating(), start_time),
+                    engine
+                        .register_player_at(settings.start_rating(), start_time)
+                        .0,
+                )
+            })
+            .collect();
+
+        let mut pairs = Vec::new();
+        for (index, &(player_1, _)) in players.iter().enumerate() {
+            for &(player_2, _) in &players[index + 1..] {
+                pairs.push((player_1, player_2));
+            }
+        }
+
+        // Every worker registers results between all pairs of players,
+        // half of them with the players and the pairs in opposite order.
+        // Meanwhile, another worker keeps closing the first rating period.
+        let workers: Vec<_> = (0..4)
+            .map(|worker| {
+                let concurrent_engine = Arc::clone(&concurrent_engine);
+                let mut pairs = pairs.clone();
+                if worker % 2 == 1 {
+                    pairs.reverse();
+                }
+
+                thread::spawn(move || {
+                    for _ in 0..3 {
+                        for &(player_1, player_2) in &pairs {
+                            if worker % 2 == 0 {
+                                concurrent_engine.register_result_at(
+                                    player_1,
+                                    player_2,
+                                    &MatchResult::Win,
+                                    result_time,
+                                );
+                            } else {
+                                concurrent_engine.register_result_at(
+                                    player_2,
+                                    player_1,
+                                    &MatchResult::Loss,
+                                    result_time,
+                                );
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+        let closer = {
+            let concurrent_engine = Arc::clone(&concurrent_engine);
+
+            thread::spawn(move || {
+                for _ in 0..20 {
+                    concurrent_engine
+                        .maybe_close_rating_periods_at(start_time + Duration::from_secs(65));
+                }
+            })
+        };
+        for worker in workers {
+            worker.join().unwrap();
+        }
+        closer.join().unwrap();
+
+        for (index, &(_, player_1)) in players.iter().enumerate() {
+            for &(_, player_2) in &players[index + 1..] {
+                for _ in 0..12 {
+                    engine.register_result_at(player_1, player_2, &MatchResult::Win, result_time);
+                }
+            }
+        }
+
+        // The order the results were registered in doesn't change the ratings
+        let time = start_time + Duration::from_secs(150);
+        for &(concurrent_player, player) in &players {
+            let concurrent_rating: PublicRating =
+                concurrent_engine.player_rating_at(concurrent_player, time);
+            let rating: PublicRating = engine.player_rating_at(player, time).0;
+            assert!((concurrent_rating.rating() - rating.rating()).abs() < 1e-9);
+            assert!((concurrent_rating.deviation() - rating.deviation()).abs() < 1e-9);
+        }
+
+        let first_rating: PublicRating = concurrent_engine.player_rating_at(players[0].0, time);
+        let last_rating: PublicRating = concurrent_engine.player_rating_at(players[3].0, time);
+        assert!(first_rating.rating() > last_rating.rating());
+    }
+}
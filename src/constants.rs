@@ -32,5 +32,5 @@ pub const DEFAULT_RATING_PERIOD_DURATION: Duration = Duration::from_secs(60 * 60
 
 /// The maximum number of iterations for the converging loop algorithm for "Step 5.4." in [Glickman's paper](http://www.glicko.net/glicko/glicko2.pdf).
 /// This is a fail-safe so we don't enter an infinite loop (even tho that shouldn't happen if the convergence tolerance is reasonable).
-/// If the maximum number of iterations is exceeded, the function panics.
+/// If the maximum number of iterations is exceeded, the calculation fails with [`GlickoError::NoConvergence`][crate::GlickoError::NoConvergence].
 pub const MAX_ITERATIONS: u32 = 10_000;
@@ -1,21 +1,173 @@
 //! This mod defines the [`RatingEngine`] struct which abstracts away the rating period from rating calculations.
 
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::sync::Arc;
 use std::time::SystemTime;
 
-use crate::algorithm::{self, Game, InternalGame};
-use crate::util::PushOnlyVec;
+use crate::algorithm::{self, Game, InternalGame, OutcomeProbabilities};
+use crate::clock::{Clock, SystemClock};
+use crate::events::EngineEvent;
+use crate::history::{
+    RatingHistory, RatingHistoryEntry, RatingHistoryEntryKind, RatingHistorySettings,
+};
+use crate::leaderboard::{Leaderboard, LeaderboardSettings};
+use crate::matchmaking::{self, ProposedMatch, QueuePairing};
+use crate::observer::{Observers, RatingObserver};
+use crate::report::{PeriodCloseReport, PlayerPeriodChange};
+use crate::team::TeamRatingAggregation;
+use crate::util::{ArenaIndex, GenerationalArena};
 use crate::{
-    ConvertToScale, FromWithSettings, GlickoSettings, Internal, IntoWithSettings, Public, Rating,
-    RatingScale,
+    ConvertToScale, FromWithSettings, GlickoError, GlickoSettings, Internal, InternalRating,
+    IntoWithSettings, Public, PublicRating, Rating, RatingScale,
 };
 
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use retroactive::ArchivedPeriod;
+
+mod retroactive;
+
 /// An opaque index pointing to a player.
 /// This is handed out by [`RatingEngine`].
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-pub struct PlayerHandle(usize);
+///
+/// Handles of removed players stay invalid,
+/// even if the [`RatingEngine`] reuses their memory for newly registered players.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlayerHandle(pub(crate) ArenaIndex);
+
+/// An opaque identifier of a result registered with a [`RatingEngine`].
+///
+/// While the rating period the result was registered in is still open,
+/// it can be used to retract or amend the result.
+///
+/// Result ids are local to the engine that handed them out.
+/// Ids are numbered consecutively, so an id of another engine is not detected as such,
+/// but refers to the result of this engine with the same number, if any.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ResultId(u64);
+
+/// A result between two teams as registered in a rating period.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct RecordedResult {
+    teams: RecordedTeams,
+    team_1_score: f64,
+    team_2_score: f64,
+    // How many full games this result counts as, see `Game::weight`.
+    weight: f64,
+}
+
+/// A team member with their rating.
+type RatedMember = (PlayerHandle, InternalRating);
+
+/// The members of the teams of a [`RecordedResult`]
+/// with their ratings at the start of the rating period the result was registered in.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+enum RecordedTeams {
+    /// Two teams with one member each, which don't need their ratings aggregated.
+    Pair([RatedMember; 2]),
+    /// Two teams of any size, whose member ratings are combined with `aggregation`.
+    Team {
+        team_1: Vec<RatedMember>,
+        team_2: Vec<RatedMember>,
+        aggregation: TeamRatingAggregation,
+    },
+}
+
+impl RecordedResult {
+    fn pair(
+        player_1: RatedMember,
+        player_2: RatedMember,
+        player_1_score: f64,
+        player_2_score: f64,
+        weight: f64,
+    ) -> Self {
+        RecordedResult {
+            teams: RecordedTeams::Pair([player_1, player_2]),
+            team_1_score: player_1_score,
+            team_2_score: player_2_score,
+            weight,
+        }
+    }
+
+    /// The members of both teams with their ratings.
+    fn teams(&self) -> (&[RatedMember], &[RatedMember]) {
+        match &self.teams {
+            RecordedTeams::Pair(pair) => pair.split_at(1),
+            RecordedTeams::Team { team_1, team_2, .. } => (team_1, team_2),
+        }
+    }
+
+    /// The members of both teams, starting with the first team.
+    fn players(&self) -> impl Iterator<Item = PlayerHandle> + '_ {
+        let (team_1, team_2) = self.teams();
+
+        team_1.iter().chain(team_2).map(|&(player, _)| player)
+    }
+
+    /// The members of both teams with their ratings, starting with the first team.
+    fn ratings_mut(&mut self) -> impl Iterator<Item = &mut RatedMember> {
+        let (team_1, team_2) = match &mut self.teams {
+            RecordedTeams::Pair(pair) => pair.split_at_mut(1),
+            RecordedTeams::Team { team_1, team_2, .. } => {
+                (team_1.as_mut_slice(), team_2.as_mut_slice())
+            }
+        };
+
+        team_1.iter_mut().chain(team_2)
+    }
+
+    /// The games of all members in this result, starting with the first team.
+    /// Every member plays one game against the aggregated rating of the other team.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidTeams`] if a team is empty.
+    fn games(
+        &self,
+    ) -> Result<impl Iterator<Item = (PlayerHandle, InternalGame)> + '_, GlickoError> {
+        let (team_1, team_2) = self.teams();
+
+        let (team_1_rating, team_2_rating) = match &self.teams {
+            RecordedTeams::Pair([(_, player_1_rating), (_, player_2_rating)]) => {
+                (*player_1_rating, *player_2_rating)
+            }
+            RecordedTeams::Team { aggregation, .. } => {
+                let team_rating = |team: &[RatedMember]| {
+                    let ratings = team.iter().map(|&(_, rating)| rating).collect::<Vec<_>>();
+
+                    aggregation
+                        .team_rating(&ratings)
+                        .ok_or(GlickoError::InvalidTeams)
+                };
+
+                (team_rating(team_1)?, team_rating(team_2)?)
+            }
+        };
+
+        let team_1_games = team_1.iter().map(move |&(player, _)| {
+            (
+                player,
+                InternalGame::new_weighted(team_2_rating, self.team_1_score, self.weight),
+            )
+        });
+        let team_2_games = team_2.iter().map(move |&(player, _)| {
+            (
+                player,
+                InternalGame::new_weighted(team_1_rating, self.team_2_score, self.weight),
+            )
+        });
+
+        Ok(team_1_games.chain(team_2_games))
+    }
+}
 
 /// A player as managed by [`RatingEngine`].
 #[derive(Clone, PartialEq, Debug)]
@@ -24,6 +176,17 @@ pub struct PlayerHandle(usize);
 pub struct EnginePlayer<Scale: RatingScale> {
     rating: Rating<Scale>,
     current_rating_period_results: Vec<Game<Scale>>,
+    // The ids of the results in current_rating_period_results, in the same order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    current_rating_period_result_ids: Vec<ResultId>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    rating_history: RatingHistory,
+    #[cfg_attr(feature = "serde", serde(default))]
+    games_played: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    last_played: Option<SystemTime>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    rating_floor: Option<f64>,
 }
 
 /// A player in the [`RatingEngine`] with a rating of [`Public`] scale.
@@ -44,6 +207,85 @@ impl<Scale: RatingScale> EnginePlayer<Scale> {
     pub fn current_rating_period_results(&self) -> &[Game<Scale>] {
         &self.current_rating_period_results
     }
+
+    /// The recorded rating history of this player, sorted by time.
+    ///
+    /// This is empty unless the rating history of the [`RatingEngine`] is enabled,
+    /// see [`RatingEngine::with_rating_history`].
+    pub fn rating_history(&self) -> impl Iterator<Item = RatingHistoryEntry> + '_ {
+        self.rating_history.entries()
+    }
+
+    /// The number of results this player was part of since they were registered.
+    ///
+    /// Retracted results are not counted.
+    #[must_use]
+    pub fn games_played(&self) -> u64 {
+        self.games_played
+    }
+
+    /// The latest point in time this player played a game at, or `None` if they haven't played yet.
+    ///
+    /// This is not reset when the result of that game is retracted.
+    #[must_use]
+    pub fn last_played(&self) -> Option<SystemTime> {
+        self.last_played
+    }
+
+    /// The individual [`Public`] rating value this player can't fall below, or `None` if they don't have one.
+    ///
+    /// This is raised when the player reaches a milestone by playing (see [`GlickoSettings::milestone_floors`])
+    /// and can be set with [`RatingEngine::set_player_rating_floor`].
+    /// The rating floor of the settings (see [`GlickoSettings::rating_floor`]) applies in addition.
+    #[must_use]
+    pub fn rating_floor(&self) -> Option<f64> {
+        self.rating_floor
+    }
+}
+
+impl InternalEnginePlayer {
+    fn result_index(&self, result: ResultId) -> Option<usize> {
+        self.current_rating_period_result_ids
+            .iter()
+            .position(|&id| id == result)
+    }
+
+    fn last_result_index(&self, result: ResultId) -> Option<usize> {
+        self.current_rating_period_result_ids
+            .iter()
+            .rposition(|&id| id == result)
+    }
+
+    /// The number of results in the current rating period.
+    fn current_rating_period_result_count(&self) -> u64 {
+        let result_ids = &self.current_rating_period_result_ids;
+        // The games of a result are always next to each other, so every result but the first starts where the id changes
+        let later_results = result_ids.windows(2).filter(|ids| ids[0] != ids[1]).count();
+
+        (later_results + usize::from(!result_ids.is_empty())) as u64
+    }
+
+    fn record_game_played(&mut self, time: SystemTime) {
+        self.games_played += 1;
+        self.last_played = Some(
+            self.last_played
+                .map_or(time, |last_played| last_played.max(time)),
+        );
+    }
+
+    fn record_rating(
+        &mut self,
+        time: SystemTime,
+        rating: InternalRating,
+        kind: RatingHistoryEntryKind,
+        settings: GlickoSettings,
+        history_settings: RatingHistorySettings,
+    ) {
+        self.rating_history.insert(
+            RatingHistoryEntry::new(time, rating.into_with_settings(settings), kind),
+            history_settings,
+        );
+    }
 }
 
 impl<Scale1: RatingScale, Scale2: RatingScale> FromWithSettings<EnginePlayer<Scale1>>
@@ -59,6 +301,11 @@ where
                 .into_iter()
                 .map(|game| game.into_with_settings(settings))
                 .collect(),
+            current_rating_period_result_ids: player.current_rating_period_result_ids,
+            rating_history: player.rating_history,
+            games_played: player.games_played,
+            last_played: player.last_played,
+            rating_floor: player.rating_floor,
         }
     }
 }
@@ -117,11 +364,70 @@ impl MatchResult {
     }
 }
 
+/// The ratings two players would have after a result, for every [`MatchResult`].
+///
+/// See [`RatingEngine::preview_result`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(serialize = "", deserialize = "")))]
+pub struct ResultPreview<Scale: RatingScale> {
+    current: (Rating<Scale>, Rating<Scale>),
+    win: (Rating<Scale>, Rating<Scale>),
+    draw: (Rating<Scale>, Rating<Scale>),
+    loss: (Rating<Scale>, Rating<Scale>),
+}
+
+impl<Scale: RatingScale> ResultPreview<Scale> {
+    /// The current ratings of the first and the second player, if they don't play.
+    #[must_use]
+    pub fn current(&self) -> (Rating<Scale>, Rating<Scale>) {
+        self.current
+    }
+
+    /// The ratings of the first and the second player if the first player wins.
+    #[must_use]
+    pub fn win(&self) -> (Rating<Scale>, Rating<Scale>) {
+        self.win
+    }
+
+    /// The ratings of the first and the second player if they draw.
+    #[must_use]
+    pub fn draw(&self) -> (Rating<Scale>, Rating<Scale>) {
+        self.draw
+    }
+
+    /// The ratings of the first and the second player if the first player loses.
+    #[must_use]
+    pub fn loss(&self) -> (Rating<Scale>, Rating<Scale>) {
+        self.loss
+    }
+
+    /// The ratings of the first and the second player for the given `result` from the first player's perspective.
+    #[must_use]
+    pub fn outcome(&self, result: MatchResult) -> (Rating<Scale>, Rating<Scale>) {
+        match result {
+            MatchResult::Win => self.win,
+            MatchResult::Draw => self.draw,
+            MatchResult::Loss => self.loss,
+        }
+    }
+
+    /// How much the first player's rating value would change for the given `result` from the first player's perspective.
+    #[must_use]
+    pub fn rating_change(&self, result: MatchResult) -> f64 {
+        self.outcome(result).0.rating() - self.current.0.rating()
+    }
+}
+
 /// Struct for managing player ratings and calculating them based on match results.
 ///
 /// It uses the Glicko-2 algorithm with the given settings.
 /// Matches can be added at any time, and participant ratings will update instantly.
 ///
+/// The current time is taken from a [`Clock`], which is the [`SystemClock`] unless the engine was created with
+/// [`start_new_with_clock`][RatingEngine::start_new_with_clock].
+/// The methods with the `_at` suffix take the time explicitly instead.
+///
 /// # Example
 ///
 /// ```
@@ -168,18 +474,37 @@ impl MatchResult {
 #[allow(clippy::module_name_repetitions)]
 #[derive(Clone, PartialEq, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct RatingEngine {
+pub struct RatingEngine<C = SystemClock> {
     last_rating_period_start: SystemTime,
-    // This should be a PushOnlyVec because we hand out index references.
-    managed_players: PushOnlyVec<InternalEnginePlayer>,
+    // This is a GenerationalArena because we hand out index references that need to detect removed players.
+    managed_players: GenerationalArena<InternalEnginePlayer>,
     settings: GlickoSettings,
+    #[cfg_attr(feature = "serde", serde(default))]
+    event_log: Option<Vec<EngineEvent>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    next_result_id: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    // A result consists of multiple pairwise results if it was registered as a ranked result.
+    open_results: BTreeMap<ResultId, Vec<RecordedResult>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    max_archived_periods: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    archived_periods: VecDeque<ArchivedPeriod>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    rating_history_settings: Option<RatingHistorySettings>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    team_rating_aggregation: TeamRatingAggregation,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    observers: Observers,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    clock: C,
 }
 
 impl RatingEngine {
     /// Creates a new [`RatingEngine`], starting the first rating period immediately.
     #[must_use]
     pub fn start_new(settings: GlickoSettings) -> Self {
-        Self::start_new_at(SystemTime::now(), settings)
+        Self::start_new_with_clock(SystemClock, settings)
     }
 
     /// Creates a new [`RatingEngine`], starting the first rating period at the specified point in time.
@@ -189,114 +514,303 @@ impl RatingEngine {
     pub fn start_new_at(start_time: SystemTime, settings: GlickoSettings) -> Self {
         RatingEngine {
             last_rating_period_start: start_time,
-            managed_players: PushOnlyVec::new(),
+            managed_players: GenerationalArena::new(),
             settings,
+            event_log: None,
+            next_result_id: 0,
+            open_results: BTreeMap::new(),
+            max_archived_periods: 0,
+            archived_periods: VecDeque::new(),
+            rating_history_settings: None,
+            team_rating_aggregation: TeamRatingAggregation::default(),
+            observers: Observers::default(),
+            clock: SystemClock,
         }
     }
 
-    /// The start of the last opened rating period.
-    #[must_use]
-    pub fn last_rating_period_start(&self) -> SystemTime {
-        self.last_rating_period_start
+    /// Reconstructs a [`RatingEngine`] from the events recorded by another engine (see [`with_event_log`][Self::with_event_log]).
+    ///
+    /// The reconstructed engine uses `settings` until the first [`EngineEvent::SettingsChanged`] event.
+    /// If `settings` are the settings the original engine was created with,
+    /// the reconstructed engine is identical to the original one, including the event log.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidEventLog`] if `events` doesn't start with exactly one [`EngineEvent::Started`],
+    /// or if the events are inconsistent with the reconstructed engine, e.g. if a registered player was assigned a different handle.
+    /// It returns any other error that occurs while applying an event to the reconstructed engine.
+    pub fn replay<'a>(
+        settings: GlickoSettings,
+        events: impl IntoIterator<Item = &'a EngineEvent>,
+    ) -> Result<Self, GlickoError> {
+        let mut events = events.into_iter();
+
+        let mut engine = match events.next() {
+            Some(&EngineEvent::Started { time }) => {
+                RatingEngine::start_new_at(time, settings).with_event_log()
+            }
+            _ => return Err(GlickoError::InvalidEventLog),
+        };
+
+        for event in events {
+            engine.apply_event(event)?;
+        }
+
+        Ok(engine)
     }
+}
 
-    /// The settings.
+impl<C: Clock> RatingEngine<C> {
+    /// Creates a new [`RatingEngine`] that gets the current time from `clock`, starting the first rating period immediately.
     #[must_use]
-    pub fn settings(&self) -> GlickoSettings {
-        self.settings
+    pub fn start_new_with_clock(clock: C, settings: GlickoSettings) -> Self {
+        RatingEngine::start_new_at(clock.now(), settings).with_clock(clock)
     }
 
-    /// The rating of a player at the **start of** the last opened rating period.
-    ///
-    /// # Panics
+    /// Replaces the clock this engine gets the current time from.
     ///
-    /// This function might panic or behave undesirably if `player` doesn't belong to this [`RatingEngine`].
+    /// This can be used to continue an engine that was reconstructed with [`replay`][RatingEngine::replay] with another clock.
     #[must_use]
-    pub fn last_rating_period_rating<Scale: RatingScale>(
-        &self,
-        player: PlayerHandle,
-    ) -> Rating<Scale>
-    where
-        Internal: ConvertToScale<Scale>,
-    {
-        self.managed_players
-            .vec()
-            .get(player.0)
-            .expect("Player didn't belong to this RatingEngine")
-            .rating()
-            .into_with_settings(self.settings)
+    pub fn with_clock<C2: Clock>(self, clock: C2) -> RatingEngine<C2> {
+        RatingEngine {
+            last_rating_period_start: self.last_rating_period_start,
+            managed_players: self.managed_players,
+            settings: self.settings,
+            event_log: self.event_log,
+            next_result_id: self.next_result_id,
+            open_results: self.open_results,
+            max_archived_periods: self.max_archived_periods,
+            archived_periods: self.archived_periods,
+            rating_history_settings: self.rating_history_settings,
+            team_rating_aggregation: self.team_rating_aggregation,
+            observers: self.observers,
+            clock,
+        }
     }
 
-    /// Returns an [`Iterator`] over all registered players.
-    pub fn player_handles(&self) -> impl Iterator<Item = PlayerHandle> {
-        (0..self.managed_players.vec().len()).map(PlayerHandle)
+    /// The clock this engine gets the current time from.
+    #[must_use]
+    pub fn clock(&self) -> &C {
+        &self.clock
     }
 
-    /// Registers a new player with a given rating to this engine at the start of the current rating period.
-    ///
-    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
-    ///
-    /// # Returns
+    /// Enables the event log of this engine.
+    /// From now on, every change to this engine is recorded as an [`EngineEvent`].
     ///
-    /// A tuple containing a value that can be later used to identify this player with this engine
-    /// and the number of rating periods that were closed for this operation.
+    /// The recorded events can be used to reconstruct this engine with [`replay`][Self::replay].
+    /// For that to work, the event log needs to be enabled before any players are registered.
+    /// If the event log is already enabled, this function does nothing.
+    #[must_use]
+    pub fn with_event_log(mut self) -> Self {
+        if self.event_log.is_none() {
+            self.event_log = Some(vec![EngineEvent::Started {
+                time: self.last_rating_period_start,
+            }]);
+
+            if self.max_archived_periods > 0 {
+                self.record_event(EngineEvent::MaxArchivedPeriodsChanged {
+                    max_archived_periods: self.max_archived_periods,
+                });
+            }
+
+            if self.rating_history_settings.is_some() {
+                self.record_event(EngineEvent::RatingHistorySettingsChanged {
+                    settings: self.rating_history_settings,
+                });
+            }
+
+            if self.team_rating_aggregation != TeamRatingAggregation::default() {
+                self.record_event(EngineEvent::TeamRatingAggregationChanged {
+                    aggregation: self.team_rating_aggregation,
+                });
+            }
+        }
+
+        self
+    }
+
+    /// The events recorded by this engine, or `None` if the event log isn't enabled.
     ///
-    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
-    // TODO: a way to register Right Now (so that the deviation is exactly the same at the now timestamp)
-    pub fn register_player<Scale: RatingScale>(
-        &mut self,
-        rating: Rating<Scale>,
-    ) -> (PlayerHandle, u32)
-    where
-        Scale: ConvertToScale<Internal>,
-    {
-        self.register_player_at(rating, SystemTime::now())
+    /// See [`with_event_log`][Self::with_event_log].
+    #[must_use]
+    pub fn event_log(&self) -> Option<&[EngineEvent]> {
+        self.event_log.as_deref()
     }
 
-    /// Registers a new player with a given rating to this engine at the start of what is the current rating period at the given time.
-    /// If `time` is earlier than the start of the last rating period, the player will be registered at the start of the last rating period.
+    /// Removes all events recorded by this engine so far and returns them.
+    /// This is useful for persisting the events incrementally.
+    /// Appending all drained events in order results in the complete event log.
     ///
-    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
-    /// If `time` is earlier than the start of the last rating period, no rating periods will be closed.
+    /// If the event log isn't enabled, this returns an empty [`Vec`].
+    pub fn drain_event_log(&mut self) -> Vec<EngineEvent> {
+        self.event_log
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    // This is just one simple match arm per event.
+    #[allow(clippy::too_many_lines)]
+    fn apply_event(&mut self, event: &EngineEvent) -> Result<(), GlickoError> {
+        match *event {
+            EngineEvent::Started { .. } => return Err(GlickoError::InvalidEventLog),
+            EngineEvent::PlayerRegistered {
+                player,
+                rating,
+                time,
+            } => {
+                let (registered_player, _) = self.try_register_player_at(rating, time)?;
+
+                if registered_player != player {
+                    return Err(GlickoError::InvalidEventLog);
+                }
+            }
+            EngineEvent::PlayerRemoved { player } => {
+                self.remove_player::<Internal>(player)
+                    .ok_or(GlickoError::InvalidEventLog)?;
+            }
+            EngineEvent::PlayerRatingFloorChanged {
+                player,
+                rating_floor,
+            } => {
+                self.try_set_player_rating_floor(player, rating_floor)?;
+            }
+            EngineEvent::ResultRegistered {
+                result,
+                player_1,
+                player_2,
+                player_1_score,
+                player_2_score,
+                time,
+            } => {
+                let (registered_result, _) = self.register_scores_at(
+                    player_1,
+                    player_2,
+                    player_1_score,
+                    player_2_score,
+                    time,
+                )?;
+
+                if registered_result != result {
+                    return Err(GlickoError::InvalidEventLog);
+                }
+            }
+            EngineEvent::RankedResultRegistered {
+                result,
+                ref players,
+                ref placements,
+                time,
+            } => {
+                let (registered_result, _) =
+                    self.try_register_ranked_result_at(players, placements, time)?;
+
+                if registered_result != result {
+                    return Err(GlickoError::InvalidEventLog);
+                }
+            }
+            EngineEvent::TeamResultRegistered {
+                result,
+                ref team_1,
+                ref team_2,
+                team_1_score,
+                team_2_score,
+                time,
+            } => {
+                let (registered_result, _) =
+                    self.register_team_scores_at(team_1, team_2, team_1_score, team_2_score, time)?;
+
+                if registered_result != result {
+                    return Err(GlickoError::InvalidEventLog);
+                }
+            }
+            EngineEvent::ResultRetracted { result, time } => {
+                self.try_retract_result_at(result, time)?;
+            }
+            EngineEvent::ResultAmended {
+                result,
+                player_1_score,
+                player_2_score,
+                time,
+            } => {
+                self.amend_scores_at(result, player_1_score, player_2_score, time)?;
+            }
+            EngineEvent::SettingsChanged { settings, time } => {
+                self.try_change_settings_at(settings, time)?;
+            }
+            EngineEvent::RetroactiveResultRegistered {
+                player_1,
+                player_2,
+                player_1_score,
+                player_2_score,
+                game_time,
+                time,
+            } => {
+                self.register_scores_retroactive_at(
+                    player_1,
+                    player_2,
+                    player_1_score,
+                    player_2_score,
+                    game_time,
+                    time,
+                )?;
+            }
+            EngineEvent::MaxArchivedPeriodsChanged {
+                max_archived_periods,
+            } => self.set_max_archived_periods(max_archived_periods),
+            EngineEvent::RatingHistorySettingsChanged { settings } => {
+                self.set_rating_history_settings(settings);
+            }
+            EngineEvent::TeamRatingAggregationChanged { aggregation } => {
+                self.set_team_rating_aggregation(aggregation);
+            }
+            EngineEvent::RatingPeriodClosed => {
+                self.close_rating_period(false)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn record_event(&mut self, event: EngineEvent) {
+        if let Some(event_log) = &mut self.event_log {
+            event_log.push(event);
+        }
+    }
+
+    /// The start of the last opened rating period.
+    #[must_use]
+    pub fn last_rating_period_start(&self) -> SystemTime {
+        self.last_rating_period_start
+    }
+
+    /// The settings.
+    #[must_use]
+    pub fn settings(&self) -> GlickoSettings {
+        self.settings
+    }
+
+    /// Changes the settings of this engine.
     ///
-    /// This function is meant mostly for testability.
+    /// Rating periods that have elapsed by now are closed with the old settings before the new settings are applied.
+    /// Stored ratings are converted so that their [`Public`] values stay the same, even if the start rating changes.
     ///
     /// # Returns
     ///
-    /// A tuple containing a value that can be later used to identify this player with this engine
-    /// and the number of rating periods that were closed for this operation.
+    /// The number of rating periods that were closed for this operation.
     ///
     /// # Panics
     ///
-    /// This function panics if `time` is earlier than the start of the last rating period.
-    ///
-    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
-    pub fn register_player_at<Scale: RatingScale>(
-        &mut self,
-        rating: Rating<Scale>,
-        time: SystemTime,
-    ) -> (PlayerHandle, u32)
-    where
-        Scale: ConvertToScale<Internal>,
-    {
-        let (_, closed_periods) = self.maybe_close_rating_periods_at(time);
-
-        let rating = rating.into_with_settings(self.settings);
-
-        let index = self.managed_players.vec().len();
-
-        self.managed_players.push(InternalEnginePlayer {
-            rating,
-            current_rating_period_results: Vec::new(),
-        });
-
-        (PlayerHandle(index), closed_periods)
+    /// This function might panic if the old settings' convergence tolerance is unreasonably low.
+    pub fn change_settings(&mut self, settings: GlickoSettings) -> u32 {
+        self.change_settings_at(settings, self.clock.now())
     }
 
-    /// Registers a result in the current rating period.
-    /// Calculating the resulting ratings happens only when the Rating is inspected.
+    /// Changes the settings of this engine at the given point in time.
     ///
-    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    /// Rating periods that have elapsed by `time` are closed with the old settings before the new settings are applied.
+    /// Stored ratings are converted so that their [`Public`] values stay the same, even if the start rating changes.
+    ///
+    /// This function is meant mostly for testability.
     ///
     /// # Returns
     ///
@@ -304,23 +818,17 @@ impl RatingEngine {
     ///
     /// # Panics
     ///
-    /// This function might panic or behave undesirable if the `result`'s players do not come from this `RatingEngine`.
-    ///
-    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
-    pub fn register_result<S: Score>(
-        &mut self,
-        player_1: PlayerHandle,
-        player_2: PlayerHandle,
-        score: &S,
-    ) -> u32 {
-        self.register_result_at(player_1, player_2, score, SystemTime::now())
+    /// This function might panic if the old settings' convergence tolerance is unreasonably low.
+    /// See [`try_change_settings_at`][Self::try_change_settings_at] for a non-panicking version.
+    pub fn change_settings_at(&mut self, settings: GlickoSettings, time: SystemTime) -> u32 {
+        self.try_change_settings_at(settings, time)
+            .unwrap_or_else(|err| panic!("{err}"))
     }
 
-    /// Registers a result at the given time in the current rating period.
-    /// Calculating the resulting ratings happens only when the Rating is inspected.
+    /// Changes the settings of this engine at the given point in time.
     ///
-    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
-    /// If `time` is earlier than the start of the last rating period, no rating periods will be closed.
+    /// Rating periods that have elapsed by `time` are closed with the old settings before the new settings are applied.
+    /// Stored ratings are converted so that their [`Public`] values stay the same, even if the start rating changes.
     ///
     /// This function is meant mostly for testability.
     ///
@@ -328,375 +836,4251 @@ impl RatingEngine {
     ///
     /// The number of rating periods that were closed for this operation.
     ///
-    /// # Panics
-    ///
-    /// This function might panic or behave undesirable if the `result`'s players do not come from this `RatingEngine`.
+    /// # Errors
     ///
-    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
-    pub fn register_result_at<S: Score>(
+    /// This function returns [`GlickoError::NoConvergence`] if closing a rating period failed
+    /// because the old settings' convergence tolerance is unreasonably low.
+    /// If an error is returned, the settings have not been changed.
+    pub fn try_change_settings_at(
         &mut self,
-        player_1: PlayerHandle,
-        player_2: PlayerHandle,
-        score: &S,
+        settings: GlickoSettings,
         time: SystemTime,
-    ) -> u32 {
-        // We have to maybe close so the results will be added in the right rating period.
-        let (_, closed_periods) = self.maybe_close_rating_periods_at(time);
+    ) -> Result<u32, GlickoError> {
+        let (_, closed_periods) = self.try_maybe_close_rating_periods_at(time)?;
 
-        // Split the result into two ScaledPlayerResults and save that on the players
-        let player_1_rating = self
-            .managed_players
-            .vec()
-            .get(player_1.0)
-            .expect("Result didn't belong to this RatingEngine")
-            .rating;
+        let old_settings = self.settings;
+        let convert = |rating| convert_rating_settings(rating, old_settings, settings);
 
-        let player_2_rating = self
-            .managed_players
-            .vec()
-            .get(player_2.0)
-            .expect("Result didn't belong to this RatingEngine")
-            .rating;
+        if old_settings.start_rating() != settings.start_rating() {
+            for (_, player) in self.managed_players.iter_mut() {
+                player.rating = convert(player.rating);
 
-        self.managed_players
-            .get_mut(player_1.0)
-            .unwrap()
-            .current_rating_period_results
-            .push(InternalGame::new(player_2_rating, score.player_score()));
+                for game in &mut player.current_rating_period_results {
+                    *game = InternalGame::new_weighted(
+                        convert(game.opponent()),
+                        game.score(),
+                        game.weight(),
+                    );
+                }
+            }
 
-        self.managed_players
-            .get_mut(player_2.0)
-            .unwrap()
-            .current_rating_period_results
-            .push(InternalGame::new(player_1_rating, score.opponent_score()));
+            for (_, rating) in self
+                .open_results
+                .values_mut()
+                .flatten()
+                .flat_map(RecordedResult::ratings_mut)
+            {
+                *rating = convert(*rating);
+            }
+        }
+
+        self.settings = settings;
 
-        closed_periods
+        self.record_event(EngineEvent::SettingsChanged { settings, time });
+
+        Ok(closed_periods)
     }
 
-    /// Calculates a player's rating at this point in time.
-    /// The calculation is based on the registered results for this player (see [`register_result`][Self::register_result]).
-    /// Note that this function does the actual rating computation.
-    /// The rating deviation of this result also depends on the current time, because rating deviation increases with time.
+    /// Enables the rating history of this engine with the given settings.
+    /// From now on, the ratings of all players are recorded at every rating period boundary,
+    /// and optionally after each of their games.
     ///
-    /// This function takes `self` mutably because it can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    /// See [`rating_history`][Self::rating_history] and [`rating_at_past_time`][Self::rating_at_past_time].
+    #[must_use]
+    pub fn with_rating_history(mut self, history_settings: RatingHistorySettings) -> Self {
+        self.set_rating_history_settings(Some(history_settings));
+
+        self
+    }
+
+    /// The rating history settings of this engine, or `None` if the rating history is disabled.
+    #[must_use]
+    pub fn rating_history_settings(&self) -> Option<RatingHistorySettings> {
+        self.rating_history_settings
+    }
+
+    /// Changes the rating history settings of this engine.
     ///
-    /// # Returns
+    /// Entries that are no longer allowed by the new retention settings are forgotten immediately.
+    /// Passing `None` disables the rating history and forgets all recorded entries.
+    pub fn set_rating_history_settings(&mut self, history_settings: Option<RatingHistorySettings>) {
+        self.rating_history_settings = history_settings;
+
+        for (_, player) in self.managed_players.iter_mut() {
+            if let Some(history_settings) = history_settings {
+                player.rating_history.prune(history_settings);
+            } else {
+                player.rating_history.clear();
+            }
+        }
+
+        self.record_event(EngineEvent::RatingHistorySettingsChanged {
+            settings: history_settings,
+        });
+    }
+
+    /// Sets the strategy for combining the ratings of a team's members in team results
+    /// (see [`register_team_result`][Self::register_team_result]).
+    #[must_use]
+    pub fn with_team_rating_aggregation(mut self, aggregation: TeamRatingAggregation) -> Self {
+        self.set_team_rating_aggregation(aggregation);
+
+        self
+    }
+
+    /// The strategy for combining the ratings of a team's members in team results.
     ///
-    /// A tuple containing the player's current rating and the number of rating periods that were closed for this operation.
+    /// See [`register_team_result`][Self::register_team_result].
+    #[must_use]
+    pub fn team_rating_aggregation(&self) -> TeamRatingAggregation {
+        self.team_rating_aggregation
+    }
+
+    /// Changes the strategy for combining the ratings of a team's members in team results
+    /// (see [`register_team_result`][Self::register_team_result]).
     ///
-    /// # Panics
+    /// Team results that were already registered keep the strategy they were registered with.
+    pub fn set_team_rating_aggregation(&mut self, aggregation: TeamRatingAggregation) {
+        self.team_rating_aggregation = aggregation;
+
+        self.record_event(EngineEvent::TeamRatingAggregationChanged { aggregation });
+    }
+
+    /// Adds an observer that is notified whenever a player is registered, a rating period is closed,
+    /// or ratings change otherwise. See [`RatingObserver`].
+    ///
+    /// Observers are shared between clones of this engine.
+    /// They aren't part of the event log, and they aren't (de)serialized.
+    #[must_use]
+    pub fn with_observer(mut self, observer: Arc<dyn RatingObserver>) -> Self {
+        self.add_observer(observer);
+
+        self
+    }
+
+    /// Adds an observer that is notified whenever a player is registered, a rating period is closed,
+    /// or ratings change otherwise.
     ///
-    /// This function might panic or return a meaningless result if `player` wasn't sourced from this [`RatingEngine`].
+    /// See [`with_observer`][Self::with_observer].
+    pub fn add_observer(&mut self, observer: Arc<dyn RatingObserver>) {
+        self.observers.push(observer);
+    }
+
+    /// The rating of a player at the **start of** the last opened rating period.
     ///
-    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// # Panics
+    ///
+    /// This function panics if `player` doesn't belong to this [`RatingEngine`].
+    /// See [`try_last_rating_period_rating`][Self::try_last_rating_period_rating] for a non-panicking version.
     #[must_use]
-    pub fn player_rating<Scale: RatingScale>(
-        &mut self,
+    pub fn last_rating_period_rating<Scale: RatingScale>(
+        &self,
         player: PlayerHandle,
-    ) -> (Rating<Scale>, u32)
+    ) -> Rating<Scale>
     where
         Internal: ConvertToScale<Scale>,
     {
-        self.player_rating_at(player, SystemTime::now())
+        self.try_last_rating_period_rating(player)
+            .unwrap_or_else(|err| panic!("{err}"))
     }
 
-    /// Calculates a player's rating at a given point in time.
-    /// The calculation is based on the registered results for this player (see [`register_result`][Self::register_result]).
-    /// Note that this function does the actual rating computation.
-    /// The rating deviation of this result also depends on the current time, because rating deviation increases with time.
+    /// The rating of a player at the **start of** the last opened rating period.
     ///
-    /// This function is meant mostly for testability.
+    /// # Errors
     ///
-    /// This function takes `self` mutably because it can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    /// This function returns [`GlickoError::UnknownPlayer`] if `player` doesn't belong to this [`RatingEngine`].
+    pub fn try_last_rating_period_rating<Scale: RatingScale>(
+        &self,
+        player: PlayerHandle,
+    ) -> Result<Rating<Scale>, GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        Ok(self
+            .player(player)?
+            .rating()
+            .into_with_settings(self.settings))
+    }
+
+    /// Returns an [`Iterator`] over all registered players.
+    pub fn player_handles(&self) -> impl Iterator<Item = PlayerHandle> + '_ {
+        self.managed_players.indices().map(PlayerHandle)
+    }
+
+    /// The number of registered players.
+    #[must_use]
+    pub fn player_count(&self) -> usize {
+        self.managed_players.len()
+    }
+
+    /// Returns `true` if `player` is currently registered with this engine.
     ///
-    /// # Returns
+    /// This is `false` for handles of removed players.
+    #[must_use]
+    pub fn contains_player(&self, player: PlayerHandle) -> bool {
+        self.managed_players.contains(player.0)
+    }
+
+    /// Registers a new player with a given rating to this engine at the start of the current rating period.
     ///
-    /// A tuple containing the player's current rating and the number of rating periods that were closed for this operation.
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
     ///
-    /// If `time` is earlier than the start of the last rating period,
-    /// no rating periods will be closed and the function will return the rating at the start of the last rating period
-    /// after applying the rating change from the registered results.
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be later used to identify this player with this engine
+    /// and the number of rating periods that were closed for this operation.
     ///
     /// # Panics
     ///
-    /// This function might panic or return a meaningless result if `player` wasn't sourced from this [`RatingEngine`].
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    // TODO: a way to register Right Now (so that the deviation is exactly the same at the now timestamp)
+    pub fn register_player<Scale>(&mut self, rating: Rating<Scale>) -> (PlayerHandle, u32)
+    where
+        Scale: RatingScale + ConvertToScale<Internal>,
+    {
+        self.register_player_at(rating, self.clock.now())
+    }
+
+    /// Registers a new player with a given rating to this engine at the start of what is the current rating period at the given time.
+    /// If `time` is earlier than the start of the last rating period, the player will be registered at the start of the last rating period.
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    /// If `time` is earlier than the start of the last rating period, no rating periods will be closed.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be later used to identify this player with this engine
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
     ///
     /// This function might panic if the set settings' convergence tolerance is unreasonably low.
-    #[must_use]
-    pub fn player_rating_at<Scale: RatingScale>(
+    /// See [`try_register_player_at`][Self::try_register_player_at] for a non-panicking version.
+    pub fn register_player_at<Scale>(
         &mut self,
-        player: PlayerHandle,
+        rating: Rating<Scale>,
         time: SystemTime,
-    ) -> (Rating<Scale>, u32)
+    ) -> (PlayerHandle, u32)
     where
-        Internal: ConvertToScale<Scale>,
+        Scale: RatingScale + ConvertToScale<Internal>,
     {
-        let (elapsed_periods, closed_periods) = self.maybe_close_rating_periods_at(time);
-
-        let player = self
-            .managed_players
-            .vec()
-            .get(player.0)
-            .expect("Player didn't belong to this RatingEngine");
-
-        let rating = algorithm::rate_games_untimed(
-            player.rating,
-            &player.current_rating_period_results,
-            elapsed_periods,
-            self.settings,
-        )
-        .into_with_settings(self.settings);
-
-        (rating, closed_periods)
+        self.try_register_player_at(rating, time)
+            .unwrap_or_else(|err| panic!("{err}"))
     }
 
-    /// Closes all open rating periods that have elapsed by now.
-    /// This doesn't need to be called manually.
+    /// Registers a new player with a given rating to this engine at the start of the current rating period.
     ///
-    /// When a rating period is closed, the stored results are cleared and the players' ratings
-    /// at the end of the period are stored as their ratings at the beginning of the next one.
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
     ///
     /// # Returns
     ///
-    /// A tuple containing the elapsed periods in the current rating period *after* all previous periods have been closed as a fraction
-    /// as well as the amount of rating periods that have been closed.
-    /// The elapsed periods will always be smaller than 1.
+    /// A tuple containing a value that can be later used to identify this player with this engine
+    /// and the number of rating periods that were closed for this operation.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
-    pub fn maybe_close_rating_periods(&mut self) -> (f64, u32) {
-        self.maybe_close_rating_periods_at(SystemTime::now())
+    /// This function returns [`GlickoError::NoConvergence`] if closing a rating period failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_register_player<Scale>(
+        &mut self,
+        rating: Rating<Scale>,
+    ) -> Result<(PlayerHandle, u32), GlickoError>
+    where
+        Scale: RatingScale + ConvertToScale<Internal>,
+    {
+        self.try_register_player_at(rating, self.clock.now())
     }
 
-    /// Closes all open rating periods that have elapsed by a given point in time.
-    /// This doesn't need to be called manually.
+    /// Registers a new player with a given rating to this engine at the start of what is the current rating period at the given time.
+    /// If `time` is earlier than the start of the last rating period, the player will be registered at the start of the last rating period.
     ///
-    /// When a rating period is closed, the stored results are cleared and the players' ratings
-    /// at the end of the period are stored as their ratings at the beginning of the next one.
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    /// If `time` is earlier than the start of the last rating period, no rating periods will be closed.
     ///
     /// This function is meant mostly for testability.
     ///
     /// # Returns
     ///
-    /// A tuple containing the elapsed periods in the current rating period *after* all previous periods have been closed as a fraction
-    /// as well as the amount of rating periods that have been closed.
-    /// The elapsed periods will always be smaller than `1.0`.
-    ///
-    /// If `time` is earlier than the start of the last rating period,
-    /// no rating periods will be closed and the returned value will be `(0.0, 0)`.
+    /// A tuple containing a value that can be later used to identify this player with this engine
+    /// and the number of rating periods that were closed for this operation.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
-    pub fn maybe_close_rating_periods_at(&mut self, time: SystemTime) -> (f64, u32) {
-        let elapsed_periods = self.elapsed_periods_at(time);
+    /// This function returns [`GlickoError::NoConvergence`] if closing a rating period failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_register_player_at<Scale>(
+        &mut self,
+        rating: Rating<Scale>,
+        time: SystemTime,
+    ) -> Result<(PlayerHandle, u32), GlickoError>
+    where
+        Scale: RatingScale + ConvertToScale<Internal>,
+    {
+        let (_, closed_periods) = self.try_maybe_close_rating_periods_at(time)?;
 
-        // We won't have negative elapsed_periods. Truncation this is the wanted result.
-        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-        let periods_to_close = elapsed_periods as u32;
+        let rating = rating.into_with_settings(self.settings);
 
-        // Every result is in the first rating period that needs to be closed.
-        // This is guaranteed because we call this method before every time a new result gets added.
-        for _ in 0..periods_to_close {
-            for player in self.managed_players.iter_mut() {
-                player.rating = algorithm::rate_games_untimed(
-                    player.rating,
-                    &player.current_rating_period_results,
-                    1.0,
-                    self.settings,
-                );
+        let mut player_data = InternalEnginePlayer {
+            rating,
+            current_rating_period_results: Vec::new(),
+            current_rating_period_result_ids: Vec::new(),
+            rating_history: RatingHistory::default(),
+            games_played: 0,
+            last_played: None,
+            rating_floor: None,
+        };
 
-                // We have now submitted the results to the players rating
-                player.current_rating_period_results.clear();
-            }
+        if let Some(history_settings) = self.rating_history_settings {
+            player_data.record_rating(
+                time.max(self.last_rating_period_start),
+                rating,
+                RatingHistoryEntryKind::Registered,
+                self.settings,
+                history_settings,
+            );
+        }
+
+        let player = PlayerHandle(self.managed_players.insert(player_data));
+
+        let rating: PublicRating = rating.into_with_settings(self.settings);
+
+        for observer in self.observers.iter() {
+            observer.player_registered(player, rating, time);
         }
 
-        self.last_rating_period_start += periods_to_close * self.settings.rating_period_duration;
+        self.record_event(EngineEvent::PlayerRegistered {
+            player,
+            rating,
+            time,
+        });
+
+        Ok((player, closed_periods))
+    }
+
+    /// Removes a player from this engine.
+    ///
+    /// The player's rating will no longer be updated when rating periods close,
+    /// and `player` as well as any copies of it will no longer be valid for this engine.
+    /// Handles for players registered later will never be equal to `player`.
+    ///
+    /// Results that were already registered against this player in the current rating period
+    /// stay part of their opponents' current rating period results.
+    ///
+    /// # Returns
+    ///
+    /// The removed player, or `None` if `player` was already removed or doesn't belong to this [`RatingEngine`].
+    pub fn remove_player<Scale: RatingScale>(
+        &mut self,
+        player: PlayerHandle,
+    ) -> Option<EnginePlayer<Scale>>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        let removed_player = self.managed_players.remove(player.0)?;
+
+        self.record_event(EngineEvent::PlayerRemoved { player });
 
-        (elapsed_periods.fract(), periods_to_close)
+        Some(removed_player.into_with_settings(self.settings))
     }
 
-    /// The amount of rating periods that have elapsed since the last one was closed as a fraction.
+    /// The individual [`Public`] rating value a player can't fall below, or `None` if they don't have one.
+    ///
+    /// See [`EnginePlayer::rating_floor`].
+    /// Rating periods that have elapsed but weren't closed yet (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods])
+    /// haven't raised the floor yet.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `player` doesn't belong to this [`RatingEngine`].
+    /// See [`try_player_rating_floor`][Self::try_player_rating_floor] for a non-panicking version.
     #[must_use]
-    pub fn elapsed_periods(&self) -> f64 {
-        self.elapsed_periods_at(SystemTime::now())
+    pub fn player_rating_floor(&self, player: PlayerHandle) -> Option<f64> {
+        self.try_player_rating_floor(player)
+            .unwrap_or_else(|err| panic!("{err}"))
     }
 
-    /// The amount of rating periods that have elapsed at the given point in time since the last one was closed as a fraction.
+    /// The individual [`Public`] rating value a player can't fall below, or `None` if they don't have one.
     ///
-    /// If `time` is earlier than the start of the last rating period, this function returns `0.0`.
+    /// See [`player_rating_floor`][Self::player_rating_floor].
     ///
-    /// This function is meant mostly for testability.
-    #[must_use]
-    pub fn elapsed_periods_at(&self, time: SystemTime) -> f64 {
-        if let Ok(elapsed_duration) = time.duration_since(self.last_rating_period_start) {
-            elapsed_duration.as_secs_f64() / self.settings.rating_period_duration.as_secs_f64()
-        } else {
-            0.0
-        }
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if `player` doesn't belong to this [`RatingEngine`].
+    pub fn try_player_rating_floor(
+        &self,
+        player: PlayerHandle,
+    ) -> Result<Option<f64>, GlickoError> {
+        Ok(self.player(player)?.rating_floor)
     }
-}
 
-#[cfg(test)]
-mod test {
-    use std::time::{Duration, SystemTime};
+    /// Sets the individual [`Public`] rating value a player can't fall below, or removes it if `rating_floor` is `None`.
+    ///
+    /// The floor applies to all ratings of the player calculated from now on, including the ones of the current rating period.
+    /// It can still be raised when the player reaches a milestone, see [`GlickoSettings::milestone_floors`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `player` doesn't belong to this [`RatingEngine`] or if `rating_floor` is not finite.
+    /// See [`try_set_player_rating_floor`][Self::try_set_player_rating_floor] for a non-panicking version.
+    pub fn set_player_rating_floor(&mut self, player: PlayerHandle, rating_floor: Option<f64>) {
+        self.try_set_player_rating_floor(player, rating_floor)
+            .unwrap_or_else(|err| panic!("{err}"));
+    }
+
+    /// Sets the individual [`Public`] rating value a player can't fall below, or removes it if `rating_floor` is `None`.
+    ///
+    /// See [`set_player_rating_floor`][Self::set_player_rating_floor].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if `player` doesn't belong to this [`RatingEngine`],
+    /// and [`GlickoError::InvalidRatingLimit`] if `rating_floor` is not finite.
+    pub fn try_set_player_rating_floor(
+        &mut self,
+        player: PlayerHandle,
+        rating_floor: Option<f64>,
+    ) -> Result<(), GlickoError> {
+        if let Some(rating_floor) = rating_floor.filter(|rating_floor| !rating_floor.is_finite()) {
+            return Err(GlickoError::InvalidRatingLimit(rating_floor));
+        }
 
-    use super::{MatchResult, RatingEngine};
-    use crate::{GlickoSettings, Public, PublicRating};
+        let time = self.clock.now();
+        let old_ratings = self.observed_ratings_at([player], time)?;
 
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr, $tolerance:expr $(,)?) => {{
-            let a_val = $a;
-            let b_val = $b;
+        self.player_mut(player)?.rating_floor = rating_floor;
 
-            assert!(
-                (a_val - b_val).abs() <= $tolerance,
-                "{} = {a_val} is not approximately equal to {} = {b_val}",
-                stringify!($a),
-                stringify!($b)
-            )
-        }};
+        self.notify_ratings_recalculated(old_ratings, time)?;
+
+        self.record_event(EngineEvent::PlayerRatingFloorChanged {
+            player,
+            rating_floor,
+        });
+
+        Ok(())
+    }
+
+    /// Registers a result in the current rating period.
+    /// Calculating the resulting ratings happens only when the Rating is inspected.
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract or amend the result while the current rating period is open
+    /// (see [`retract_result`][Self::retract_result] and [`amend_result`][Self::amend_result])
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the `result`'s players do not come from this `RatingEngine`,
+    /// or if `score` is not between `0.0` and `1.0`.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn register_result<S: Score>(
+        &mut self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        score: &S,
+    ) -> (ResultId, u32) {
+        self.register_result_at(player_1, player_2, score, self.clock.now())
+    }
+
+    /// Registers a result at the given time in the current rating period.
+    /// Calculating the resulting ratings happens only when the Rating is inspected.
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    /// If `time` is earlier than the start of the last rating period, no rating periods will be closed.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract or amend the result while the current rating period is open
+    /// (see [`retract_result`][Self::retract_result] and [`amend_result`][Self::amend_result])
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the `result`'s players do not come from this `RatingEngine`,
+    /// or if `score` is not between `0.0` and `1.0`.
+    /// See [`try_register_result_at`][Self::try_register_result_at] for a non-panicking version.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn register_result_at<S: Score>(
+        &mut self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        score: &S,
+        time: SystemTime,
+    ) -> (ResultId, u32) {
+        self.try_register_result_at(player_1, player_2, score, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Registers a result in the current rating period.
+    /// Calculating the resulting ratings happens only when the Rating is inspected.
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract or amend the result while the current rating period is open
+    /// (see [`retract_result`][Self::retract_result] and [`amend_result`][Self::amend_result])
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this [`RatingEngine`],
+    /// [`GlickoError::InvalidScore`] if `score` is not between `0.0` and `1.0`,
+    /// and [`GlickoError::NoConvergence`] if closing a rating period failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    /// If an error is returned, the result has not been registered.
+    pub fn try_register_result<S: Score>(
+        &mut self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        score: &S,
+    ) -> Result<(ResultId, u32), GlickoError> {
+        self.try_register_result_at(player_1, player_2, score, self.clock.now())
+    }
+
+    /// Registers a result at the given time in the current rating period.
+    /// Calculating the resulting ratings happens only when the Rating is inspected.
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    /// If `time` is earlier than the start of the last rating period, no rating periods will be closed.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract or amend the result while the current rating period is open
+    /// (see [`retract_result`][Self::retract_result] and [`amend_result`][Self::amend_result])
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this [`RatingEngine`],
+    /// [`GlickoError::InvalidScore`] if `score` is not between `0.0` and `1.0`,
+    /// and [`GlickoError::NoConvergence`] if closing a rating period failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    /// If an error is returned, the result has not been registered.
+    pub fn try_register_result_at<S: Score>(
+        &mut self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        score: &S,
+        time: SystemTime,
+    ) -> Result<(ResultId, u32), GlickoError> {
+        self.register_scores_at(
+            player_1,
+            player_2,
+            score.player_score(),
+            score.opponent_score(),
+            time,
+        )
+    }
+
+    /// Registers a result between two teams in the current rating period.
+    /// `score` is the score of `team_1` against `team_2`.
+    /// Calculating the resulting ratings happens only when the Rating is inspected.
+    ///
+    /// Every member of a team plays a game against the rating of the other team,
+    /// which is combined from the ratings of its members (see [`with_team_rating_aggregation`][Self::with_team_rating_aggregation]).
+    /// The members' ratings are updated individually, so members with a less certain rating change more.
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract or amend the result while the current rating period is open
+    /// (see [`retract_result`][Self::retract_result] and [`amend_result`][Self::amend_result])
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if a team is empty, if a player appears more than once,
+    /// if one of the players does not come from this `RatingEngine`,
+    /// or if `score` is not between `0.0` and `1.0`.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_register_team_result`][Self::try_register_team_result] for a non-panicking version.
+    pub fn register_team_result<S: Score>(
+        &mut self,
+        team_1: &[PlayerHandle],
+        team_2: &[PlayerHandle],
+        score: &S,
+    ) -> (ResultId, u32) {
+        self.register_team_result_at(team_1, team_2, score, self.clock.now())
+    }
+
+    /// Registers a result between two teams at the given time in the current rating period.
+    ///
+    /// See [`register_team_result`][Self::register_team_result].
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    /// If `time` is earlier than the start of the last rating period, no rating periods will be closed.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract or amend the result while the current rating period is open
+    /// (see [`retract_result`][Self::retract_result] and [`amend_result`][Self::amend_result])
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if a team is empty, if a player appears more than once,
+    /// if one of the players does not come from this `RatingEngine`,
+    /// or if `score` is not between `0.0` and `1.0`.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_register_team_result_at`][Self::try_register_team_result_at] for a non-panicking version.
+    pub fn register_team_result_at<S: Score>(
+        &mut self,
+        team_1: &[PlayerHandle],
+        team_2: &[PlayerHandle],
+        score: &S,
+        time: SystemTime,
+    ) -> (ResultId, u32) {
+        self.try_register_team_result_at(team_1, team_2, score, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Registers a result between two teams in the current rating period.
+    ///
+    /// See [`register_team_result`][Self::register_team_result].
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract or amend the result while the current rating period is open
+    /// (see [`retract_result`][Self::retract_result] and [`amend_result`][Self::amend_result])
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidTeams`] if a team is empty or if a player appears more than once,
+    /// [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this [`RatingEngine`],
+    /// [`GlickoError::InvalidScore`] if `score` is not between `0.0` and `1.0`,
+    /// and [`GlickoError::NoConvergence`] if closing a rating period failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    /// If an error is returned, the result has not been registered.
+    pub fn try_register_team_result<S: Score>(
+        &mut self,
+        team_1: &[PlayerHandle],
+        team_2: &[PlayerHandle],
+        score: &S,
+    ) -> Result<(ResultId, u32), GlickoError> {
+        self.try_register_team_result_at(team_1, team_2, score, self.clock.now())
+    }
+
+    /// Registers a result between two teams at the given time in the current rating period.
+    ///
+    /// See [`register_team_result`][Self::register_team_result].
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    /// If `time` is earlier than the start of the last rating period, no rating periods will be closed.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract or amend the result while the current rating period is open
+    /// (see [`retract_result`][Self::retract_result] and [`amend_result`][Self::amend_result])
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidTeams`] if a team is empty or if a player appears more than once,
+    /// [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this [`RatingEngine`],
+    /// [`GlickoError::InvalidScore`] if `score` is not between `0.0` and `1.0`,
+    /// and [`GlickoError::NoConvergence`] if closing a rating period failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    /// If an error is returned, the result has not been registered.
+    pub fn try_register_team_result_at<S: Score>(
+        &mut self,
+        team_1: &[PlayerHandle],
+        team_2: &[PlayerHandle],
+        score: &S,
+        time: SystemTime,
+    ) -> Result<(ResultId, u32), GlickoError> {
+        self.register_team_scores_at(
+            team_1,
+            team_2,
+            score.player_score(),
+            score.opponent_score(),
+            time,
+        )
+    }
+
+    fn register_team_scores_at(
+        &mut self,
+        team_1: &[PlayerHandle],
+        team_2: &[PlayerHandle],
+        team_1_score: f64,
+        team_2_score: f64,
+        time: SystemTime,
+    ) -> Result<(ResultId, u32), GlickoError> {
+        // Validate everything before closing rating periods so that errors don't have side effects.
+        if team_1.is_empty() || team_2.is_empty() {
+            return Err(GlickoError::InvalidTeams);
+        }
+
+        let mut sorted_players = [team_1, team_2].concat();
+        sorted_players.sort_unstable();
+        if sorted_players.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(GlickoError::InvalidTeams);
+        }
+
+        for &player in &sorted_players {
+            self.player(player)?;
+        }
+
+        algorithm::validate_score(team_1_score)?;
+        algorithm::validate_score(team_2_score)?;
+
+        // We have to maybe close so the results will be added in the right rating period.
+        let (_, closed_periods) = self.try_maybe_close_rating_periods_at(time)?;
+
+        let team_with_ratings = |team: &[PlayerHandle]| {
+            team.iter()
+                .map(|&player| Ok((player, self.player(player)?.rating)))
+                .collect::<Result<Vec<_>, GlickoError>>()
+        };
+
+        let recorded_result = RecordedResult {
+            teams: RecordedTeams::Team {
+                team_1: team_with_ratings(team_1)?,
+                team_2: team_with_ratings(team_2)?,
+                aggregation: self.team_rating_aggregation,
+            },
+            team_1_score,
+            team_2_score,
+            weight: 1.0,
+        };
+
+        let result = self.insert_open_result(vec![recorded_result], time)?;
+
+        self.record_event(EngineEvent::TeamResultRegistered {
+            result,
+            team_1: team_1.to_vec(),
+            team_2: team_2.to_vec(),
+            team_1_score,
+            team_2_score,
+            time,
+        });
+
+        Ok((result, closed_periods))
+    }
+
+    /// Registers the result of a match between any number of players, e.g. a free-for-all or a race, in the current rating period.
+    /// Calculating the resulting ratings happens only when the Rating is inspected.
+    ///
+    /// `placements` contains the placement of each player in `players`, where lower placements are better.
+    /// Players with the same placement tied.
+    /// The placements don't need to be consecutive, so `[1, 2, 2, 4]` and `[0, 1, 1, 2]` are equivalent.
+    ///
+    /// The ranking is split into a virtual game between every pair of players.
+    /// Each of these games only counts as `1 / (players.len() - 1)` of a full game,
+    /// so every player's games from this result add up to one full game, regardless of the number of players.
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract the result while the current rating period is open
+    /// (see [`retract_result`][Self::retract_result])
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there are fewer than two players, if a player appears more than once,
+    /// if the number of placements doesn't match the number of players,
+    /// or if one of the players does not come from this `RatingEngine`.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_register_ranked_result`][Self::try_register_ranked_result] for a non-panicking version.
+    pub fn register_ranked_result(
+        &mut self,
+        players: &[PlayerHandle],
+        placements: &[u32],
+    ) -> (ResultId, u32) {
+        self.register_ranked_result_at(players, placements, self.clock.now())
+    }
+
+    /// Registers the result of a match between any number of players at the given time in the current rating period.
+    ///
+    /// See [`register_ranked_result`][Self::register_ranked_result].
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    /// If `time` is earlier than the start of the last rating period, no rating periods will be closed.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract the result while the current rating period is open
+    /// (see [`retract_result`][Self::retract_result])
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there are fewer than two players, if a player appears more than once,
+    /// if the number of placements doesn't match the number of players,
+    /// or if one of the players does not come from this `RatingEngine`.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_register_ranked_result_at`][Self::try_register_ranked_result_at] for a non-panicking version.
+    pub fn register_ranked_result_at(
+        &mut self,
+        players: &[PlayerHandle],
+        placements: &[u32],
+        time: SystemTime,
+    ) -> (ResultId, u32) {
+        self.try_register_ranked_result_at(players, placements, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Registers the result of a match between any number of players in the current rating period.
+    ///
+    /// See [`register_ranked_result`][Self::register_ranked_result].
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract the result while the current rating period is open
+    /// (see [`retract_result`][Self::retract_result])
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidRanking`] if there are fewer than two players,
+    /// if a player appears more than once, or if the number of placements doesn't match the number of players,
+    /// [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if closing a rating period failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    /// If an error is returned, the result has not been registered.
+    pub fn try_register_ranked_result(
+        &mut self,
+        players: &[PlayerHandle],
+        placements: &[u32],
+    ) -> Result<(ResultId, u32), GlickoError> {
+        self.try_register_ranked_result_at(players, placements, self.clock.now())
+    }
+
+    /// Registers the result of a match between any number of players at the given time in the current rating period.
+    ///
+    /// See [`register_ranked_result`][Self::register_ranked_result].
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    /// If `time` is earlier than the start of the last rating period, no rating periods will be closed.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract the result while the current rating period is open
+    /// (see [`retract_result`][Self::retract_result])
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidRanking`] if there are fewer than two players,
+    /// if a player appears more than once, or if the number of placements doesn't match the number of players,
+    /// [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if closing a rating period failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    /// If an error is returned, the result has not been registered.
+    pub fn try_register_ranked_result_at(
+        &mut self,
+        players: &[PlayerHandle],
+        placements: &[u32],
+        time: SystemTime,
+    ) -> Result<(ResultId, u32), GlickoError> {
+        // Validate everything before closing rating periods so that errors don't have side effects.
+        if players.len() < 2 || players.len() != placements.len() {
+            return Err(GlickoError::InvalidRanking);
+        }
+
+        let mut sorted_players = players.to_vec();
+        sorted_players.sort_unstable();
+        if sorted_players.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(GlickoError::InvalidRanking);
+        }
+
+        for &player in players {
+            self.player(player)?;
+        }
+
+        // We have to maybe close so the results will be added in the right rating period.
+        let (_, closed_periods) = self.try_maybe_close_rating_periods_at(time)?;
+
+        // The number of players won't be anywhere near big enough to lose precision
+        #[allow(clippy::cast_precision_loss)]
+        let weight = 1.0 / (players.len() - 1) as f64;
+
+        let mut recorded_results = Vec::new();
+        for (index, (&player_1, &player_1_placement)) in players.iter().zip(placements).enumerate()
+        {
+            for (&player_2, &player_2_placement) in players.iter().zip(placements).skip(index + 1) {
+                let player_1_score = match player_1_placement.cmp(&player_2_placement) {
+                    Ordering::Less => 1.0,
+                    Ordering::Equal => 0.5,
+                    Ordering::Greater => 0.0,
+                };
+
+                recorded_results.push(RecordedResult::pair(
+                    (player_1, self.player(player_1)?.rating),
+                    (player_2, self.player(player_2)?.rating),
+                    player_1_score,
+                    1.0 - player_1_score,
+                    weight,
+                ));
+            }
+        }
+
+        let result = self.insert_open_result(recorded_results, time)?;
+
+        self.record_event(EngineEvent::RankedResultRegistered {
+            result,
+            players: players.to_vec(),
+            placements: placements.to_vec(),
+            time,
+        });
+
+        Ok((result, closed_periods))
+    }
+
+    fn register_scores_at(
+        &mut self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        player_1_score: f64,
+        player_2_score: f64,
+        time: SystemTime,
+    ) -> Result<(ResultId, u32), GlickoError> {
+        // Validate everything before closing rating periods so that errors don't have side effects.
+        self.player(player_1)?;
+        self.player(player_2)?;
+
+        algorithm::validate_score(player_1_score)?;
+        algorithm::validate_score(player_2_score)?;
+
+        // We have to maybe close so the results will be added in the right rating period.
+        let (_, closed_periods) = self.try_maybe_close_rating_periods_at(time)?;
+
+        let recorded_result = RecordedResult::pair(
+            (player_1, self.player(player_1)?.rating),
+            (player_2, self.player(player_2)?.rating),
+            player_1_score,
+            player_2_score,
+            1.0,
+        );
+
+        let result = self.insert_open_result(vec![recorded_result], time)?;
+
+        self.record_event(EngineEvent::ResultRegistered {
+            result,
+            player_1,
+            player_2,
+            player_1_score,
+            player_2_score,
+            time,
+        });
+
+        Ok((result, closed_periods))
+    }
+
+    /// Saves the games of `recorded_results` on the players and stores them as one open result.
+    ///
+    /// The players need to be validated and the elapsed rating periods need to be closed before calling this.
+    fn insert_open_result(
+        &mut self,
+        recorded_results: Vec<RecordedResult>,
+        time: SystemTime,
+    ) -> Result<ResultId, GlickoError> {
+        // The games of every player, sorted by handle.
+        // A player playing against themselves gets both games.
+        let mut player_games: BTreeMap<PlayerHandle, Vec<InternalGame>> = BTreeMap::new();
+        for recorded_result in &recorded_results {
+            for (player, game) in recorded_result.games()? {
+                player_games.entry(player).or_default().push(game);
+            }
+        }
+
+        // Calculate the history entries before changing anything so that errors don't have side effects
+        let game_history_entries = match self.rating_history_settings {
+            Some(history_settings) if history_settings.record_games() => Some((
+                player_games
+                    .iter()
+                    .map(|(player, games)| self.rating_with_games_at(*player, games, time))
+                    .collect::<Result<Vec<_>, _>>()?,
+                history_settings,
+            )),
+            _ => None,
+        };
+
+        // The rating changes are only calculated if someone is interested in them
+        let rating_changes = if self.observers.is_empty() {
+            None
+        } else {
+            Some(
+                player_games
+                    .iter()
+                    .map(|(player, games)| {
+                        Ok((
+                            self.rating_with_games_at(*player, &[], time)?,
+                            self.rating_with_games_at(*player, games, time)?,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, GlickoError>>()?,
+            )
+        };
+
+        let result = ResultId(self.next_result_id);
+        self.next_result_id += 1;
+
+        for (player, games) in &player_games {
+            let player_data = self.player_mut(*player)?;
+            player_data
+                .current_rating_period_results
+                .extend_from_slice(games);
+            player_data
+                .current_rating_period_result_ids
+                .extend(games.iter().map(|_| result));
+            // A player playing against themselves played only one game
+            player_data.record_game_played(time);
+        }
+
+        if let Some((new_ratings, history_settings)) = game_history_entries {
+            let time = time.max(self.last_rating_period_start);
+            let settings = self.settings;
+
+            // A player playing against themselves gets only one entry
+            for (player, new_rating) in player_games.keys().zip(new_ratings) {
+                self.player_mut(*player)?.record_rating(
+                    time,
+                    new_rating,
+                    RatingHistoryEntryKind::Game,
+                    settings,
+                    history_settings,
+                );
+            }
+        }
+
+        if let Some(rating_changes) = rating_changes {
+            for (player, (old_rating, new_rating)) in player_games.keys().zip(rating_changes) {
+                let old_rating = old_rating.into_with_settings(self.settings);
+                let new_rating = new_rating.into_with_settings(self.settings);
+
+                for observer in self.observers.iter() {
+                    observer.result_registered(result, *player, old_rating, new_rating, time);
+                }
+            }
+        }
+
+        self.open_results.insert(result, recorded_results);
+
+        Ok(result)
+    }
+
+    /// Retracts a result that was registered in the current rating period, as if it was never registered.
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the rating period of the result has already been closed,
+    /// if the result was already retracted, or if `result` wasn't sourced from this [`RatingEngine`].
+    /// See [`try_retract_result`][Self::try_retract_result] for a non-panicking version.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn retract_result(&mut self, result: ResultId) -> u32 {
+        self.try_retract_result(result)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Retracts a result that was registered in what is the current rating period at the given time, as if it was never registered.
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the rating period of the result has been closed by `time`,
+    /// if the result was already retracted, or if `result` wasn't sourced from this [`RatingEngine`].
+    /// See [`try_retract_result_at`][Self::try_retract_result_at] for a non-panicking version.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn retract_result_at(&mut self, result: ResultId, time: SystemTime) -> u32 {
+        self.try_retract_result_at(result, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Retracts a result that was registered in the current rating period, as if it was never registered.
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::ClosedResult`] if the rating period of the result has already been closed
+    /// or if the result was already retracted,
+    /// [`GlickoError::UnknownResult`] if `result` was never handed out by this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if closing a rating period failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_retract_result(&mut self, result: ResultId) -> Result<u32, GlickoError> {
+        self.try_retract_result_at(result, self.clock.now())
+    }
+
+    /// Retracts a result that was registered in what is the current rating period at the given time, as if it was never registered.
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::ClosedResult`] if the rating period of the result has been closed by `time`
+    /// or if the result was already retracted,
+    /// [`GlickoError::UnknownResult`] if `result` was never handed out by this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if closing a rating period failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_retract_result_at(
+        &mut self,
+        result: ResultId,
+        time: SystemTime,
+    ) -> Result<u32, GlickoError> {
+        self.validate_result_id(result)?;
+
+        let (_, closed_periods) = self.try_maybe_close_rating_periods_at(time)?;
+
+        let open_results = self
+            .open_results
+            .get(&result)
+            .ok_or(GlickoError::ClosedResult(result))?;
+        let old_ratings =
+            self.observed_ratings_at(open_results.iter().flat_map(RecordedResult::players), time)?;
+
+        let open_results = self
+            .open_results
+            .remove(&result)
+            .ok_or(GlickoError::ClosedResult(result))?;
+
+        for player in open_results.iter().flat_map(RecordedResult::players) {
+            // The player might have been removed in the meantime
+            if let Some(player) = self.managed_players.get_mut(player.0) {
+                // A player playing against themselves or appearing in multiple pairs played only one game
+                if player.result_index(result).is_some() {
+                    player.games_played -= 1;
+                }
+
+                while let Some(index) = player.result_index(result) {
+                    player.current_rating_period_results.remove(index);
+                    player.current_rating_period_result_ids.remove(index);
+                }
+            }
+        }
+
+        self.notify_rating_changes(
+            old_ratings,
+            time,
+            |observer, player, old_rating, new_rating| {
+                observer.result_retracted(result, player, old_rating, new_rating, time);
+            },
+        )?;
+
+        self.record_event(EngineEvent::ResultRetracted { result, time });
+
+        Ok(closed_periods)
+    }
+
+    /// Replaces the score of a result that was registered in the current rating period.
+    ///
+    /// The opponent ratings of the result stay the same.
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the rating period of the result has already been closed,
+    /// if the result was retracted, if `result` wasn't sourced from this [`RatingEngine`],
+    /// if it is a ranked result, or if `new_score` is not between `0.0` and `1.0`.
+    /// See [`try_amend_result`][Self::try_amend_result] for a non-panicking version.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn amend_result<S: Score>(&mut self, result: ResultId, new_score: &S) -> u32 {
+        self.try_amend_result(result, new_score)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Replaces the score of a result that was registered in what is the current rating period at the given time.
+    ///
+    /// The opponent ratings of the result stay the same.
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the rating period of the result has been closed by `time`,
+    /// if the result was retracted, if `result` wasn't sourced from this [`RatingEngine`],
+    /// if it is a ranked result, or if `new_score` is not between `0.0` and `1.0`.
+    /// See [`try_amend_result_at`][Self::try_amend_result_at] for a non-panicking version.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn amend_result_at<S: Score>(
+        &mut self,
+        result: ResultId,
+        new_score: &S,
+        time: SystemTime,
+    ) -> u32 {
+        self.try_amend_result_at(result, new_score, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Replaces the score of a result that was registered in the current rating period.
+    ///
+    /// The opponent ratings of the result stay the same.
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::ClosedResult`] if the rating period of the result has already been closed
+    /// or if the result was retracted,
+    /// [`GlickoError::UnknownResult`] if `result` was never handed out by this [`RatingEngine`],
+    /// [`GlickoError::RankedResult`] if `result` is a ranked result,
+    /// [`GlickoError::InvalidScore`] if `new_score` is not between `0.0` and `1.0`,
+    /// and [`GlickoError::NoConvergence`] if closing a rating period failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_amend_result<S: Score>(
+        &mut self,
+        result: ResultId,
+        new_score: &S,
+    ) -> Result<u32, GlickoError> {
+        self.try_amend_result_at(result, new_score, self.clock.now())
+    }
+
+    /// Replaces the score of a result that was registered in what is the current rating period at the given time.
+    ///
+    /// The opponent ratings of the result stay the same.
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::ClosedResult`] if the rating period of the result has been closed by `time`
+    /// or if the result was retracted,
+    /// [`GlickoError::UnknownResult`] if `result` was never handed out by this [`RatingEngine`],
+    /// [`GlickoError::RankedResult`] if `result` is a ranked result,
+    /// [`GlickoError::InvalidScore`] if `new_score` is not between `0.0` and `1.0`,
+    /// and [`GlickoError::NoConvergence`] if closing a rating period failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_amend_result_at<S: Score>(
+        &mut self,
+        result: ResultId,
+        new_score: &S,
+        time: SystemTime,
+    ) -> Result<u32, GlickoError> {
+        self.amend_scores_at(
+            result,
+            new_score.player_score(),
+            new_score.opponent_score(),
+            time,
+        )
+    }
+
+    fn amend_scores_at(
+        &mut self,
+        result: ResultId,
+        player_1_score: f64,
+        player_2_score: f64,
+        time: SystemTime,
+    ) -> Result<u32, GlickoError> {
+        self.validate_result_id(result)?;
+        // Only results of one pair of players can be amended, no matter when
+        if matches!(self.open_results.get(&result), Some(open_results) if open_results.len() != 1) {
+            return Err(GlickoError::RankedResult(result));
+        }
+        algorithm::validate_score(player_1_score)?;
+        algorithm::validate_score(player_2_score)?;
+
+        let (_, closed_periods) = self.try_maybe_close_rating_periods_at(time)?;
+
+        let old_ratings = match self.open_results.get(&result) {
+            Some(open_results) => self
+                .observed_ratings_at(open_results.iter().flat_map(RecordedResult::players), time)?,
+            None => None,
+        };
+
+        let open_result = match self
+            .open_results
+            .get_mut(&result)
+            .ok_or(GlickoError::ClosedResult(result))?
+            .as_mut_slice()
+        {
+            [open_result] => open_result,
+            _ => return Err(GlickoError::RankedResult(result)),
+        };
+        open_result.team_1_score = player_1_score;
+        open_result.team_2_score = player_2_score;
+        let open_result = open_result.clone();
+        let (team_1, team_2) = open_result.teams();
+
+        for &(player, _) in team_1 {
+            if let Some(player) = self.managed_players.get_mut(player.0) {
+                // If a player played against themselves, the first game is the one of player_1
+                if let Some(index) = player.result_index(result) {
+                    let game = &mut player.current_rating_period_results[index];
+                    *game =
+                        InternalGame::new_weighted(game.opponent(), player_1_score, game.weight());
+                }
+            }
+        }
+
+        for &(player, _) in team_2 {
+            if let Some(player) = self.managed_players.get_mut(player.0) {
+                if let Some(index) = player.last_result_index(result) {
+                    let game = &mut player.current_rating_period_results[index];
+                    *game =
+                        InternalGame::new_weighted(game.opponent(), player_2_score, game.weight());
+                }
+            }
+        }
+
+        self.notify_rating_changes(
+            old_ratings,
+            time,
+            |observer, player, old_rating, new_rating| {
+                observer.result_amended(result, player, old_rating, new_rating, time);
+            },
+        )?;
+
+        self.record_event(EngineEvent::ResultAmended {
+            result,
+            player_1_score,
+            player_2_score,
+            time,
+        });
+
+        Ok(closed_periods)
+    }
+
+    /// Checks that `result` was handed out by this engine.
+    /// Ids of other engines can't be told apart, see [`ResultId`].
+    fn validate_result_id(&self, result: ResultId) -> Result<(), GlickoError> {
+        if result.0 < self.next_result_id {
+            Ok(())
+        } else {
+            Err(GlickoError::UnknownResult(result))
+        }
+    }
+
+    /// Calculates a player's rating at this point in time.
+    /// The calculation is based on the registered results for this player (see [`register_result`][Self::register_result]).
+    /// Note that this function does the actual rating computation.
+    /// The rating deviation of this result also depends on the current time, because rating deviation increases with time.
+    ///
+    /// This function takes `self` mutably because it can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    /// See [`peek_player_rating`][Self::peek_player_rating] for a version that takes `self` immutably.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the player's current rating and the number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `player` wasn't sourced from this [`RatingEngine`].
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    #[must_use]
+    pub fn player_rating<Scale: RatingScale>(
+        &mut self,
+        player: PlayerHandle,
+    ) -> (Rating<Scale>, u32)
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.player_rating_at(player, self.clock.now())
+    }
+
+    /// Calculates a player's rating at a given point in time.
+    /// The calculation is based on the registered results for this player (see [`register_result`][Self::register_result]).
+    /// Note that this function does the actual rating computation.
+    /// The rating deviation of this result also depends on the current time, because rating deviation increases with time.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// This function takes `self` mutably because it can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    /// See [`peek_player_rating_at`][Self::peek_player_rating_at] for a version that takes `self` immutably.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the player's current rating and the number of rating periods that were closed for this operation.
+    ///
+    /// If `time` is earlier than the start of the last rating period,
+    /// no rating periods will be closed and the function will return the rating at the start of the last rating period
+    /// after applying the rating change from the registered results.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `player` wasn't sourced from this [`RatingEngine`].
+    /// See [`try_player_rating_at`][Self::try_player_rating_at] for a non-panicking version.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    #[must_use]
+    pub fn player_rating_at<Scale: RatingScale>(
+        &mut self,
+        player: PlayerHandle,
+        time: SystemTime,
+    ) -> (Rating<Scale>, u32)
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.try_player_rating_at(player, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Calculates a player's rating at this point in time.
+    /// The calculation is based on the registered results for this player (see [`register_result`][Self::register_result]).
+    /// Note that this function does the actual rating computation.
+    /// The rating deviation of this result also depends on the current time, because rating deviation increases with time.
+    ///
+    /// This function takes `self` mutably because it can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    /// See [`try_peek_player_rating`][Self::try_peek_player_rating] for a version that takes `self` immutably.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the player's current rating and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if `player` doesn't belong to this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if the rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_player_rating<Scale: RatingScale>(
+        &mut self,
+        player: PlayerHandle,
+    ) -> Result<(Rating<Scale>, u32), GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.try_player_rating_at(player, self.clock.now())
+    }
+
+    /// Calculates a player's rating at a given point in time.
+    /// The calculation is based on the registered results for this player (see [`register_result`][Self::register_result]).
+    /// Note that this function does the actual rating computation.
+    /// The rating deviation of this result also depends on the current time, because rating deviation increases with time.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// This function takes `self` mutably because it can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    /// See [`try_peek_player_rating_at`][Self::try_peek_player_rating_at] for a version that takes `self` immutably.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the player's current rating and the number of rating periods that were closed for this operation.
+    ///
+    /// If `time` is earlier than the start of the last rating period,
+    /// no rating periods will be closed and the function will return the rating at the start of the last rating period
+    /// after applying the rating change from the registered results.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if `player` doesn't belong to this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if the rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_player_rating_at<Scale: RatingScale>(
+        &mut self,
+        player: PlayerHandle,
+        time: SystemTime,
+    ) -> Result<(Rating<Scale>, u32), GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        // Validate the player before closing rating periods so that errors don't have side effects.
+        self.player(player)?;
+
+        let (elapsed_periods, closed_periods) = self.try_maybe_close_rating_periods_at(time)?;
+
+        let player = self.player(player)?;
+
+        let rating = rate_player_games(
+            self.settings,
+            player.rating,
+            &player.current_rating_period_results,
+            elapsed_periods,
+            player.rating_floor,
+        )?
+        .into_with_settings(self.settings);
+
+        Ok((rating, closed_periods))
+    }
+
+    /// The recorded rating history of a player, sorted by time.
+    ///
+    /// This is empty unless the rating history is enabled, see [`with_rating_history`][Self::with_rating_history].
+    /// Rating periods are only recorded once they are closed,
+    /// so rating periods that have elapsed but weren't closed yet (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods])
+    /// are missing from the history.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `player` doesn't belong to this [`RatingEngine`].
+    /// See [`try_rating_history`][Self::try_rating_history] for a non-panicking version.
+    pub fn rating_history(
+        &self,
+        player: PlayerHandle,
+    ) -> impl Iterator<Item = RatingHistoryEntry> + '_ {
+        self.try_rating_history(player)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// The recorded rating history of a player, sorted by time.
+    ///
+    /// See [`rating_history`][Self::rating_history].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if `player` doesn't belong to this [`RatingEngine`].
+    pub fn try_rating_history(
+        &self,
+        player: PlayerHandle,
+    ) -> Result<impl Iterator<Item = RatingHistoryEntry> + '_, GlickoError> {
+        Ok(self.player(player)?.rating_history.entries())
+    }
+
+    /// The latest rating recorded for a player at or before `time`.
+    ///
+    /// This is the rating that was shown for the player at that time.
+    /// The rating deviation increase between the recorded entry and `time` is not accounted for.
+    ///
+    /// # Returns
+    ///
+    /// The rating, or `None` if no rating was recorded at or before `time`,
+    /// e.g. because the rating history is disabled (see [`with_rating_history`][Self::with_rating_history])
+    /// or the entries have been forgotten already.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `player` doesn't belong to this [`RatingEngine`].
+    /// See [`try_rating_at_past_time`][Self::try_rating_at_past_time] for a non-panicking version.
+    #[must_use]
+    pub fn rating_at_past_time<Scale: RatingScale>(
+        &self,
+        player: PlayerHandle,
+        time: SystemTime,
+    ) -> Option<Rating<Scale>>
+    where
+        Public: ConvertToScale<Scale>,
+    {
+        self.try_rating_at_past_time(player, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// The latest rating recorded for a player at or before `time`.
+    ///
+    /// See [`rating_at_past_time`][Self::rating_at_past_time].
+    ///
+    /// # Returns
+    ///
+    /// The rating, or `None` if no rating was recorded at or before `time`,
+    /// e.g. because the rating history is disabled (see [`with_rating_history`][Self::with_rating_history])
+    /// or the entries have been forgotten already.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if `player` doesn't belong to this [`RatingEngine`].
+    pub fn try_rating_at_past_time<Scale: RatingScale>(
+        &self,
+        player: PlayerHandle,
+        time: SystemTime,
+    ) -> Result<Option<Rating<Scale>>, GlickoError>
+    where
+        Public: ConvertToScale<Scale>,
+    {
+        Ok(self
+            .player(player)?
+            .rating_history
+            .rating_at(time)
+            .map(|rating| rating.into_with_settings(self.settings)))
+    }
+
+    /// Calculates a player's rating at this point in time without changing this engine.
+    ///
+    /// Unlike [`player_rating`][Self::player_rating], this function doesn't close old rating periods.
+    /// Instead, rating periods that have elapsed are applied virtually,
+    /// so the result is the same as that of [`player_rating`][Self::player_rating].
+    /// Because it takes `self` immutably, this function can be used by multiple readers at the same time,
+    /// e.g. behind an [`RwLock`][std::sync::RwLock] read guard.
+    ///
+    /// The virtually closed rating periods are calculated again on every call until they are actually closed,
+    /// so if many rating periods have elapsed, [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]
+    /// should be called occasionally.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `player` doesn't belong to this [`RatingEngine`].
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_peek_player_rating`][Self::try_peek_player_rating] for a non-panicking version.
+    #[must_use]
+    pub fn peek_player_rating<Scale: RatingScale>(&self, player: PlayerHandle) -> Rating<Scale>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.peek_player_rating_at(player, self.clock.now())
+    }
+
+    /// Calculates a player's rating at a given point in time without changing this engine.
+    ///
+    /// See [`peek_player_rating`][Self::peek_player_rating].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `player` doesn't belong to this [`RatingEngine`].
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_peek_player_rating_at`][Self::try_peek_player_rating_at] for a non-panicking version.
+    #[must_use]
+    pub fn peek_player_rating_at<Scale: RatingScale>(
+        &self,
+        player: PlayerHandle,
+        time: SystemTime,
+    ) -> Rating<Scale>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.try_peek_player_rating_at(player, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Calculates a player's rating at this point in time without changing this engine.
+    ///
+    /// See [`peek_player_rating`][Self::peek_player_rating].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if `player` doesn't belong to this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if the rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_peek_player_rating<Scale: RatingScale>(
+        &self,
+        player: PlayerHandle,
+    ) -> Result<Rating<Scale>, GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.try_peek_player_rating_at(player, self.clock.now())
+    }
+
+    /// Calculates a player's rating at a given point in time without changing this engine.
+    ///
+    /// See [`peek_player_rating`][Self::peek_player_rating].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if `player` doesn't belong to this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if the rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_peek_player_rating_at<Scale: RatingScale>(
+        &self,
+        player: PlayerHandle,
+        time: SystemTime,
+    ) -> Result<Rating<Scale>, GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        let player = self.player(player)?;
+
+        Ok(self
+            .peek_rating(player, self.elapsed_periods_at(time))?
+            .into_with_settings(self.settings))
+    }
+
+    /// The score `player_1` is expected to achieve against `player_2` right now on average,
+    /// between `0.0` (certain loss) and `1.0` (certain win), without changing this engine.
+    ///
+    /// The current ratings of both players are used, see [`peek_player_rating`][Self::peek_player_rating].
+    /// See [`algorithm::expected_score`] for how the deviations and the draw parameter are taken into account.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if one of the players doesn't belong to this [`RatingEngine`].
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_expected_score`][Self::try_expected_score] for a non-panicking version.
+    #[must_use]
+    pub fn expected_score(&self, player_1: PlayerHandle, player_2: PlayerHandle) -> f64 {
+        self.expected_score_at(player_1, player_2, self.clock.now())
+    }
+
+    /// The score `player_1` is expected to achieve against `player_2` at the given time on average,
+    /// between `0.0` (certain loss) and `1.0` (certain win), without changing this engine.
+    ///
+    /// See [`expected_score`][Self::expected_score].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if one of the players doesn't belong to this [`RatingEngine`].
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_expected_score_at`][Self::try_expected_score_at] for a non-panicking version.
+    #[must_use]
+    pub fn expected_score_at(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        time: SystemTime,
+    ) -> f64 {
+        self.try_expected_score_at(player_1, player_2, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// The score `player_1` is expected to achieve against `player_2` right now on average,
+    /// between `0.0` (certain loss) and `1.0` (certain win), without changing this engine.
+    ///
+    /// See [`expected_score`][Self::expected_score].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_expected_score(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+    ) -> Result<f64, GlickoError> {
+        self.try_expected_score_at(player_1, player_2, self.clock.now())
+    }
+
+    /// The score `player_1` is expected to achieve against `player_2` at the given time on average,
+    /// between `0.0` (certain loss) and `1.0` (certain win), without changing this engine.
+    ///
+    /// See [`expected_score`][Self::expected_score].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_expected_score_at(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        time: SystemTime,
+    ) -> Result<f64, GlickoError> {
+        let elapsed_periods = self.elapsed_periods_at(time);
+
+        Ok(algorithm::expected_score(
+            self.peek_rating(self.player(player_1)?, elapsed_periods)?,
+            self.peek_rating(self.player(player_2)?, elapsed_periods)?,
+            self.settings,
+        ))
+    }
+
+    /// The probabilities that `player_1` wins, draws or loses against `player_2` right now,
+    /// without changing this engine.
+    ///
+    /// The current ratings of both players are used, see [`peek_player_rating`][Self::peek_player_rating].
+    /// Draws only have a positive probability if the settings have a positive [draw parameter][GlickoSettings::draw_parameter].
+    /// See [`algorithm::outcome_probabilities`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if one of the players doesn't belong to this [`RatingEngine`].
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_outcome_probabilities`][Self::try_outcome_probabilities] for a non-panicking version.
+    #[must_use]
+    pub fn outcome_probabilities(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+    ) -> OutcomeProbabilities {
+        self.outcome_probabilities_at(player_1, player_2, self.clock.now())
+    }
+
+    /// The probabilities that `player_1` wins, draws or loses against `player_2` at the given time,
+    /// without changing this engine.
+    ///
+    /// See [`outcome_probabilities`][Self::outcome_probabilities].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if one of the players doesn't belong to this [`RatingEngine`].
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_outcome_probabilities_at`][Self::try_outcome_probabilities_at] for a non-panicking version.
+    #[must_use]
+    pub fn outcome_probabilities_at(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        time: SystemTime,
+    ) -> OutcomeProbabilities {
+        self.try_outcome_probabilities_at(player_1, player_2, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// The probabilities that `player_1` wins, draws or loses against `player_2` right now,
+    /// without changing this engine.
+    ///
+    /// See [`outcome_probabilities`][Self::outcome_probabilities].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_outcome_probabilities(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+    ) -> Result<OutcomeProbabilities, GlickoError> {
+        self.try_outcome_probabilities_at(player_1, player_2, self.clock.now())
+    }
+
+    /// The probabilities that `player_1` wins, draws or loses against `player_2` at the given time,
+    /// without changing this engine.
+    ///
+    /// See [`outcome_probabilities`][Self::outcome_probabilities].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_outcome_probabilities_at(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        time: SystemTime,
+    ) -> Result<OutcomeProbabilities, GlickoError> {
+        let elapsed_periods = self.elapsed_periods_at(time);
+
+        Ok(algorithm::outcome_probabilities(
+            self.peek_rating(self.player(player_1)?, elapsed_periods)?,
+            self.peek_rating(self.player(player_2)?, elapsed_periods)?,
+            self.settings,
+        ))
+    }
+
+    /// Calculates the ratings two players would have if they played a result right now, for every [`MatchResult`],
+    /// without changing this engine.
+    ///
+    /// Elapsed rating periods are applied virtually, see [`peek_player_rating`][Self::peek_player_rating].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if one of the players doesn't belong to this [`RatingEngine`].
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_preview_result`][Self::try_preview_result] for a non-panicking version.
+    #[must_use]
+    pub fn preview_result<Scale: RatingScale>(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+    ) -> ResultPreview<Scale>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.preview_result_at(player_1, player_2, self.clock.now())
+    }
+
+    /// Calculates the ratings two players would have if they played a result at the given time, for every [`MatchResult`],
+    /// without changing this engine.
+    ///
+    /// See [`preview_result`][Self::preview_result].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if one of the players doesn't belong to this [`RatingEngine`].
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_preview_result_at`][Self::try_preview_result_at] for a non-panicking version.
+    #[must_use]
+    pub fn preview_result_at<Scale: RatingScale>(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        time: SystemTime,
+    ) -> ResultPreview<Scale>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.try_preview_result_at(player_1, player_2, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Calculates the ratings two players would have if they played a result right now, for every [`MatchResult`],
+    /// without changing this engine.
+    ///
+    /// See [`preview_result`][Self::preview_result].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_preview_result<Scale: RatingScale>(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+    ) -> Result<ResultPreview<Scale>, GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.try_preview_result_at(player_1, player_2, self.clock.now())
+    }
+
+    /// Calculates the ratings two players would have if they played a result at the given time, for every [`MatchResult`],
+    /// without changing this engine.
+    ///
+    /// See [`preview_result`][Self::preview_result].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_preview_result_at<Scale: RatingScale>(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        time: SystemTime,
+    ) -> Result<ResultPreview<Scale>, GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        let elapsed_periods = self.elapsed_periods_at(time);
+        let current = (
+            self.peek_rating(self.player(player_1)?, elapsed_periods)?,
+            self.peek_rating(self.player(player_2)?, elapsed_periods)?,
+        );
+
+        let outcome = |result: MatchResult| {
+            self.preview_scores_at(
+                player_1,
+                player_2,
+                result.player_score(),
+                result.opponent_score(),
+                time,
+            )
+        };
+        let win = outcome(MatchResult::Win)?;
+        let draw = outcome(MatchResult::Draw)?;
+        let loss = outcome(MatchResult::Loss)?;
+
+        let convert = |(player_1_rating, player_2_rating): (InternalRating, InternalRating)| {
+            (
+                player_1_rating.into_with_settings(self.settings),
+                player_2_rating.into_with_settings(self.settings),
+            )
+        };
+
+        Ok(ResultPreview {
+            current: convert(current),
+            win: convert(win),
+            draw: convert(draw),
+            loss: convert(loss),
+        })
+    }
+
+    /// Calculates the ratings two players would have if they played a result with the given score right now,
+    /// without changing this engine.
+    ///
+    /// Elapsed rating periods are applied virtually, see [`peek_player_rating`][Self::peek_player_rating].
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the ratings of `player_1` and `player_2`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if one of the players doesn't belong to this [`RatingEngine`],
+    /// or if `score` is not between `0.0` and `1.0`.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_preview_score`][Self::try_preview_score] for a non-panicking version.
+    #[must_use]
+    pub fn preview_score<Scale: RatingScale, S: Score>(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        score: &S,
+    ) -> (Rating<Scale>, Rating<Scale>)
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.preview_score_at(player_1, player_2, score, self.clock.now())
+    }
+
+    /// Calculates the ratings two players would have if they played a result with the given score at the given time,
+    /// without changing this engine.
+    ///
+    /// See [`preview_score`][Self::preview_score].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the ratings of `player_1` and `player_2`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if one of the players doesn't belong to this [`RatingEngine`],
+    /// or if `score` is not between `0.0` and `1.0`.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_preview_score_at`][Self::try_preview_score_at] for a non-panicking version.
+    #[must_use]
+    pub fn preview_score_at<Scale: RatingScale, S: Score>(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        score: &S,
+        time: SystemTime,
+    ) -> (Rating<Scale>, Rating<Scale>)
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.try_preview_score_at(player_1, player_2, score, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Calculates the ratings two players would have if they played a result with the given score right now,
+    /// without changing this engine.
+    ///
+    /// See [`preview_score`][Self::preview_score].
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the ratings of `player_1` and `player_2`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this [`RatingEngine`],
+    /// [`GlickoError::InvalidScore`] if `score` is not between `0.0` and `1.0`,
+    /// and [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_preview_score<Scale: RatingScale, S: Score>(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        score: &S,
+    ) -> Result<(Rating<Scale>, Rating<Scale>), GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.try_preview_score_at(player_1, player_2, score, self.clock.now())
+    }
+
+    /// Calculates the ratings two players would have if they played a result with the given score at the given time,
+    /// without changing this engine.
+    ///
+    /// See [`preview_score`][Self::preview_score].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the ratings of `player_1` and `player_2`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this [`RatingEngine`],
+    /// [`GlickoError::InvalidScore`] if `score` is not between `0.0` and `1.0`,
+    /// and [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_preview_score_at<Scale: RatingScale, S: Score>(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        score: &S,
+        time: SystemTime,
+    ) -> Result<(Rating<Scale>, Rating<Scale>), GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        let (player_1_rating, player_2_rating) = self.preview_scores_at(
+            player_1,
+            player_2,
+            score.player_score(),
+            score.opponent_score(),
+            time,
+        )?;
+
+        Ok((
+            player_1_rating.into_with_settings(self.settings),
+            player_2_rating.into_with_settings(self.settings),
+        ))
+    }
+
+    /// Calculates the ratings of all players at this point in time without changing this engine.
+    ///
+    /// The only difference to calling [`peek_player_rating`][Self::peek_player_rating] for every player
+    /// is that the elapsed rating periods are determined once instead of once per player.
+    /// The rating of each player is calculated the same way.
+    /// Elapsed rating periods are applied virtually, see [`peek_player_rating`][Self::peek_player_rating].
+    ///
+    /// # Returns
+    ///
+    /// The handles and ratings of all players, sorted by handle.
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_all_ratings`][Self::try_all_ratings] for a non-panicking version.
+    #[must_use]
+    pub fn all_ratings<Scale: RatingScale>(&self) -> Vec<(PlayerHandle, Rating<Scale>)>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.all_ratings_at(self.clock.now())
+    }
+
+    /// Calculates the ratings of all players at a given point in time without changing this engine.
+    ///
+    /// See [`all_ratings`][Self::all_ratings].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// The handles and ratings of all players, sorted by handle.
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_all_ratings_at`][Self::try_all_ratings_at] for a non-panicking version.
+    #[must_use]
+    pub fn all_ratings_at<Scale: RatingScale>(
+        &self,
+        time: SystemTime,
+    ) -> Vec<(PlayerHandle, Rating<Scale>)>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.try_all_ratings_at(time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Calculates the ratings of all players at this point in time without changing this engine.
+    ///
+    /// See [`all_ratings`][Self::all_ratings].
+    ///
+    /// # Returns
+    ///
+    /// The handles and ratings of all players, sorted by handle.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_all_ratings<Scale: RatingScale>(
+        &self,
+    ) -> Result<Vec<(PlayerHandle, Rating<Scale>)>, GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        self.try_all_ratings_at(self.clock.now())
+    }
+
+    /// Calculates the ratings of all players at a given point in time without changing this engine.
+    ///
+    /// See [`all_ratings`][Self::all_ratings].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// The handles and ratings of all players, sorted by handle.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_all_ratings_at<Scale: RatingScale>(
+        &self,
+        time: SystemTime,
+    ) -> Result<Vec<(PlayerHandle, Rating<Scale>)>, GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+    {
+        let elapsed_periods = self.elapsed_periods_at(time);
+
+        self.managed_players
+            .iter()
+            .map(|(handle, player)| {
+                let rating = self.peek_rating(player, elapsed_periods)?;
+
+                Ok((
+                    PlayerHandle(handle),
+                    rating.into_with_settings(self.settings),
+                ))
+            })
+            .collect()
+    }
+
+    /// Calculates the ratings of all players at this point in time in parallel without changing this engine.
+    ///
+    /// See [`all_ratings`][Self::all_ratings].
+    ///
+    /// # Returns
+    ///
+    /// The handles and ratings of all players, sorted by handle.
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_par_all_ratings`][Self::try_par_all_ratings] for a non-panicking version.
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    pub fn par_all_ratings<Scale: RatingScale + Send>(&self) -> Vec<(PlayerHandle, Rating<Scale>)>
+    where
+        Internal: ConvertToScale<Scale>,
+        C: Sync,
+    {
+        self.par_all_ratings_at(self.clock.now())
+    }
+
+    /// Calculates the ratings of all players at a given point in time in parallel without changing this engine.
+    ///
+    /// See [`all_ratings`][Self::all_ratings].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// The handles and ratings of all players, sorted by handle.
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_par_all_ratings_at`][Self::try_par_all_ratings_at] for a non-panicking version.
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    pub fn par_all_ratings_at<Scale: RatingScale + Send>(
+        &self,
+        time: SystemTime,
+    ) -> Vec<(PlayerHandle, Rating<Scale>)>
+    where
+        Internal: ConvertToScale<Scale>,
+        C: Sync,
+    {
+        self.try_par_all_ratings_at(time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Calculates the ratings of all players at this point in time in parallel without changing this engine.
+    ///
+    /// See [`all_ratings`][Self::all_ratings].
+    ///
+    /// # Returns
+    ///
+    /// The handles and ratings of all players, sorted by handle.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    #[cfg(feature = "rayon")]
+    pub fn try_par_all_ratings<Scale: RatingScale + Send>(
+        &self,
+    ) -> Result<Vec<(PlayerHandle, Rating<Scale>)>, GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+        C: Sync,
+    {
+        self.try_par_all_ratings_at(self.clock.now())
+    }
+
+    /// Calculates the ratings of all players at a given point in time in parallel without changing this engine.
+    ///
+    /// See [`all_ratings`][Self::all_ratings].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// The handles and ratings of all players, sorted by handle.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    #[cfg(feature = "rayon")]
+    pub fn try_par_all_ratings_at<Scale: RatingScale + Send>(
+        &self,
+        time: SystemTime,
+    ) -> Result<Vec<(PlayerHandle, Rating<Scale>)>, GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+        C: Sync,
+    {
+        let elapsed_periods = self.elapsed_periods_at(time);
+
+        self.managed_players
+            .par_iter()
+            .map(|(handle, player)| {
+                let rating = self.peek_rating(player, elapsed_periods)?;
+
+                Ok((
+                    PlayerHandle(handle),
+                    rating.into_with_settings(self.settings),
+                ))
+            })
+            .collect()
+    }
+
+    /// Ranks all eligible players by their current rating.
+    ///
+    /// Elapsed rating periods are applied virtually, see [`peek_player_rating`][Self::peek_player_rating].
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_leaderboard`][Self::try_leaderboard] for a non-panicking version.
+    #[must_use]
+    pub fn leaderboard(&self, leaderboard_settings: LeaderboardSettings) -> Leaderboard {
+        self.leaderboard_at(leaderboard_settings, self.clock.now())
+    }
+
+    /// Ranks all eligible players by their rating at the given point in time.
+    ///
+    /// Elapsed rating periods are applied virtually, see [`peek_player_rating`][Self::peek_player_rating].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_leaderboard_at`][Self::try_leaderboard_at] for a non-panicking version.
+    #[must_use]
+    pub fn leaderboard_at(
+        &self,
+        leaderboard_settings: LeaderboardSettings,
+        time: SystemTime,
+    ) -> Leaderboard {
+        self.try_leaderboard_at(leaderboard_settings, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Ranks all eligible players by their current rating.
+    ///
+    /// Elapsed rating periods are applied virtually, see [`peek_player_rating`][Self::peek_player_rating].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_leaderboard(
+        &self,
+        leaderboard_settings: LeaderboardSettings,
+    ) -> Result<Leaderboard, GlickoError> {
+        self.try_leaderboard_at(leaderboard_settings, self.clock.now())
+    }
+
+    /// Ranks all eligible players by their rating at the given point in time.
+    ///
+    /// Elapsed rating periods are applied virtually, see [`peek_player_rating`][Self::peek_player_rating].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_leaderboard_at(
+        &self,
+        leaderboard_settings: LeaderboardSettings,
+        time: SystemTime,
+    ) -> Result<Leaderboard, GlickoError> {
+        let elapsed_periods = self.elapsed_periods_at(time);
+
+        let mut eligible_players = Vec::new();
+
+        for (handle, player) in self.managed_players.iter() {
+            let rating: PublicRating = self
+                .peek_rating(player, elapsed_periods)?
+                .into_with_settings(self.settings);
+
+            if leaderboard_settings.is_eligible(
+                rating,
+                player.games_played,
+                player.last_played,
+                time,
+            ) {
+                eligible_players.push((PlayerHandle(handle), rating));
+            }
+        }
+
+        Ok(Leaderboard::new(
+            time,
+            eligible_players,
+            leaderboard_settings.ranking_key(),
+        ))
+    }
+
+    /// Ranks the possible matches between `player` and each of the `candidates` by their current ratings,
+    /// from the best match to the worst. See [`matchmaking::match_quality`] for how matches are rated.
+    ///
+    /// `player` itself is skipped if it is one of the `candidates`, and duplicate candidates are only ranked once.
+    /// Elapsed rating periods are applied virtually, see [`peek_player_rating`][Self::peek_player_rating].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `player` or one of the `candidates` doesn't belong to this [`RatingEngine`].
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_rank_opponents`][Self::try_rank_opponents] for a non-panicking version.
+    #[must_use]
+    pub fn rank_opponents(
+        &self,
+        player: PlayerHandle,
+        candidates: &[PlayerHandle],
+    ) -> Vec<ProposedMatch> {
+        self.rank_opponents_at(player, candidates, self.clock.now())
+    }
+
+    /// Ranks the possible matches between `player` and each of the `candidates` by their ratings at the given point in time,
+    /// from the best match to the worst.
+    ///
+    /// See [`rank_opponents`][Self::rank_opponents].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `player` or one of the `candidates` doesn't belong to this [`RatingEngine`].
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_rank_opponents_at`][Self::try_rank_opponents_at] for a non-panicking version.
+    #[must_use]
+    pub fn rank_opponents_at(
+        &self,
+        player: PlayerHandle,
+        candidates: &[PlayerHandle],
+        time: SystemTime,
+    ) -> Vec<ProposedMatch> {
+        self.try_rank_opponents_at(player, candidates, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Ranks the possible matches between `player` and each of the `candidates` by their current ratings,
+    /// from the best match to the worst.
+    ///
+    /// See [`rank_opponents`][Self::rank_opponents].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if `player` or one of the `candidates` doesn't belong to this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_rank_opponents(
+        &self,
+        player: PlayerHandle,
+        candidates: &[PlayerHandle],
+    ) -> Result<Vec<ProposedMatch>, GlickoError> {
+        self.try_rank_opponents_at(player, candidates, self.clock.now())
+    }
+
+    /// Ranks the possible matches between `player` and each of the `candidates` by their ratings at the given point in time,
+    /// from the best match to the worst.
+    ///
+    /// See [`rank_opponents`][Self::rank_opponents].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if `player` or one of the `candidates` doesn't belong to this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_rank_opponents_at(
+        &self,
+        player: PlayerHandle,
+        candidates: &[PlayerHandle],
+        time: SystemTime,
+    ) -> Result<Vec<ProposedMatch>, GlickoError> {
+        let elapsed_periods = self.elapsed_periods_at(time);
+
+        let player_rating = self.peek_rating(self.player(player)?, elapsed_periods)?;
+        let candidates = self.peek_ratings(candidates, elapsed_periods)?;
+
+        Ok(matchmaking::rank_opponents(
+            (player, player_rating),
+            &candidates,
+            self.settings,
+        ))
+    }
+
+    /// Greedily pairs the players of `queue` by their current ratings:
+    /// The best possible match is picked first, then the best possible match between the remaining players, and so on.
+    /// See [`matchmaking::match_quality`] for how matches are rated.
+    ///
+    /// Duplicate players are only paired once.
+    /// Elapsed rating periods are applied virtually, see [`peek_player_rating`][Self::peek_player_rating].
+    ///
+    /// Every possible pair of players is rated and sorted,
+    /// so pairing a queue of `n` players takes `O(n² log n)` time and `O(n²)` memory.
+    /// Large queues should be split into smaller ones first, e.g. by rating range.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if one of the players doesn't belong to this [`RatingEngine`].
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_pair_queue`][Self::try_pair_queue] for a non-panicking version.
+    #[must_use]
+    pub fn pair_queue(&self, queue: &[PlayerHandle]) -> QueuePairing {
+        self.pair_queue_at(queue, self.clock.now())
+    }
+
+    /// Greedily pairs the players of `queue` by their ratings at the given point in time.
+    ///
+    /// See [`pair_queue`][Self::pair_queue].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if one of the players doesn't belong to this [`RatingEngine`].
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_pair_queue_at`][Self::try_pair_queue_at] for a non-panicking version.
+    #[must_use]
+    pub fn pair_queue_at(&self, queue: &[PlayerHandle], time: SystemTime) -> QueuePairing {
+        self.try_pair_queue_at(queue, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Greedily pairs the players of `queue` by their current ratings.
+    ///
+    /// See [`pair_queue`][Self::pair_queue].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_pair_queue(&self, queue: &[PlayerHandle]) -> Result<QueuePairing, GlickoError> {
+        self.try_pair_queue_at(queue, self.clock.now())
+    }
+
+    /// Greedily pairs the players of `queue` by their ratings at the given point in time.
+    ///
+    /// See [`pair_queue`][Self::pair_queue].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if one of the players doesn't belong to this [`RatingEngine`],
+    /// and [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn try_pair_queue_at(
+        &self,
+        queue: &[PlayerHandle],
+        time: SystemTime,
+    ) -> Result<QueuePairing, GlickoError> {
+        let queue = self.peek_ratings(queue, self.elapsed_periods_at(time))?;
+
+        Ok(QueuePairing::greedy(&queue, self.settings))
+    }
+
+    /// Closes all open rating periods that have elapsed by now.
+    /// This doesn't need to be called manually.
+    ///
+    /// When a rating period is closed, the stored results are cleared and the players' ratings
+    /// at the end of the period are stored as their ratings at the beginning of the next one.
+    /// With the `rayon` feature, the new ratings are calculated in parallel.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the elapsed periods in the current rating period *after* all previous periods have been closed as a fraction
+    /// as well as the amount of rating periods that have been closed.
+    /// The elapsed periods will always be smaller than 1.
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn maybe_close_rating_periods(&mut self) -> (f64, u32) {
+        self.maybe_close_rating_periods_at(self.clock.now())
+    }
+
+    /// Closes all open rating periods that have elapsed by a given point in time.
+    /// This doesn't need to be called manually.
+    ///
+    /// When a rating period is closed, the stored results are cleared and the players' ratings
+    /// at the end of the period are stored as their ratings at the beginning of the next one.
+    /// See [`maybe_close_rating_periods`][Self::maybe_close_rating_periods] for the effect of the `rayon` feature.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the elapsed periods in the current rating period *after* all previous periods have been closed as a fraction
+    /// as well as the amount of rating periods that have been closed.
+    /// The elapsed periods will always be smaller than `1.0`.
+    ///
+    /// If `time` is earlier than the start of the last rating period,
+    /// no rating periods will be closed and the returned value will be `(0.0, 0)`.
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    /// See [`try_maybe_close_rating_periods_at`][Self::try_maybe_close_rating_periods_at] for a non-panicking version.
+    pub fn maybe_close_rating_periods_at(&mut self, time: SystemTime) -> (f64, u32) {
+        self.try_maybe_close_rating_periods_at(time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Closes all open rating periods that have elapsed by now.
+    /// This doesn't need to be called manually.
+    ///
+    /// When a rating period is closed, the stored results are cleared and the players' ratings
+    /// at the end of the period are stored as their ratings at the beginning of the next one.
+    /// See [`maybe_close_rating_periods`][Self::maybe_close_rating_periods] for the effect of the `rayon` feature.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the elapsed periods in the current rating period *after* all previous periods have been closed as a fraction
+    /// as well as the amount of rating periods that have been closed.
+    /// The elapsed periods will always be smaller than 1.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::NoConvergence`] if the rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    /// In that case, the rating period in which the error occurred stays open.
+    pub fn try_maybe_close_rating_periods(&mut self) -> Result<(f64, u32), GlickoError> {
+        self.try_maybe_close_rating_periods_at(self.clock.now())
+    }
+
+    /// Closes all open rating periods that have elapsed by a given point in time.
+    /// This doesn't need to be called manually.
+    ///
+    /// When a rating period is closed, the stored results are cleared and the players' ratings
+    /// at the end of the period are stored as their ratings at the beginning of the next one.
+    /// See [`maybe_close_rating_periods`][Self::maybe_close_rating_periods] for the effect of the `rayon` feature.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the elapsed periods in the current rating period *after* all previous periods have been closed as a fraction
+    /// as well as the amount of rating periods that have been closed.
+    /// The elapsed periods will always be smaller than `1.0`.
+    ///
+    /// If `time` is earlier than the start of the last rating period,
+    /// no rating periods will be closed and the returned value will be `(0.0, 0)`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::NoConvergence`] if the rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    /// In that case, the rating period in which the error occurred stays open.
+    pub fn try_maybe_close_rating_periods_at(
+        &mut self,
+        time: SystemTime,
+    ) -> Result<(f64, u32), GlickoError> {
+        let elapsed_periods = self.elapsed_periods_at(time);
+
+        // We won't have negative elapsed_periods. Truncation this is the wanted result.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let periods_to_close = elapsed_periods as u32;
+
+        // Every result is in the first rating period that needs to be closed.
+        // This is guaranteed because we call this method before every time a new result gets added.
+        for _ in 0..periods_to_close {
+            self.close_rating_period(false)?;
+        }
+
+        Ok((elapsed_periods.fract(), periods_to_close))
+    }
+
+    /// Closes all open rating periods that have elapsed by now, like [`maybe_close_rating_periods`][Self::maybe_close_rating_periods],
+    /// and reports how the ratings of all players changed.
+    ///
+    /// Rating periods are also closed implicitly by most other operations.
+    /// To get reports for those as well, use an observer (see [`RatingObserver::period_closed`]).
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the elapsed periods in the current rating period *after* all previous periods have been closed as a fraction
+    /// as well as a report for every rating period that has been closed, in order.
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn maybe_close_rating_periods_with_reports(&mut self) -> (f64, Vec<PeriodCloseReport>) {
+        self.maybe_close_rating_periods_with_reports_at(self.clock.now())
+    }
+
+    /// Closes all open rating periods that have elapsed by a given point in time,
+    /// like [`maybe_close_rating_periods_at`][Self::maybe_close_rating_periods_at],
+    /// and reports how the ratings of all players changed.
+    ///
+    /// See [`maybe_close_rating_periods_with_reports`][Self::maybe_close_rating_periods_with_reports].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the elapsed periods in the current rating period *after* all previous periods have been closed as a fraction
+    /// as well as a report for every rating period that has been closed, in order.
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn maybe_close_rating_periods_with_reports_at(
+        &mut self,
+        time: SystemTime,
+    ) -> (f64, Vec<PeriodCloseReport>) {
+        match self.try_maybe_close_rating_periods_with_reports_at(time) {
+            (_, _, Some(err)) => panic!("{err}"),
+            (elapsed_periods, reports, None) => (elapsed_periods, reports),
+        }
+    }
+
+    /// Closes all open rating periods that have elapsed by now, like [`maybe_close_rating_periods`][Self::maybe_close_rating_periods],
+    /// and reports how the ratings of all players changed.
+    ///
+    /// See [`maybe_close_rating_periods_with_reports`][Self::maybe_close_rating_periods_with_reports].
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the elapsed periods in the current rating period *after* all previous periods have been closed as a fraction,
+    /// a report for every rating period that has been closed, in order, and the error that stopped closing rating periods, if any.
+    ///
+    /// If [`GlickoError::NoConvergence`] is returned because the set settings' convergence tolerance is unreasonably low,
+    /// the rating period in which the error occurred stays open, the rating periods before it stay closed and are reported,
+    /// and the elapsed periods are counted from the start of the rating period that stays open.
+    #[must_use]
+    pub fn try_maybe_close_rating_periods_with_reports(
+        &mut self,
+    ) -> (f64, Vec<PeriodCloseReport>, Option<GlickoError>) {
+        self.try_maybe_close_rating_periods_with_reports_at(self.clock.now())
+    }
+
+    /// Closes all open rating periods that have elapsed by a given point in time,
+    /// like [`maybe_close_rating_periods_at`][Self::maybe_close_rating_periods_at],
+    /// and reports how the ratings of all players changed.
+    ///
+    /// See [`maybe_close_rating_periods_with_reports`][Self::maybe_close_rating_periods_with_reports].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the elapsed periods in the current rating period *after* all previous periods have been closed as a fraction,
+    /// a report for every rating period that has been closed, in order, and the error that stopped closing rating periods, if any.
+    ///
+    /// If [`GlickoError::NoConvergence`] is returned because the set settings' convergence tolerance is unreasonably low,
+    /// the rating period in which the error occurred stays open, the rating periods before it stay closed and are reported,
+    /// and the elapsed periods are counted from the start of the rating period that stays open.
+    #[must_use]
+    pub fn try_maybe_close_rating_periods_with_reports_at(
+        &mut self,
+        time: SystemTime,
+    ) -> (f64, Vec<PeriodCloseReport>, Option<GlickoError>) {
+        let elapsed_periods = self.elapsed_periods_at(time);
+
+        // We won't have negative elapsed_periods. Truncation this is the wanted result.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let periods_to_close = elapsed_periods as u32;
+
+        let mut reports = Vec::new();
+
+        for _ in 0..periods_to_close {
+            match self.close_rating_period(true) {
+                Ok(report) => reports.extend(report),
+                Err(err) => return (self.elapsed_periods_at(time), reports, Some(err)),
+            }
+        }
+
+        (elapsed_periods.fract(), reports, None)
+    }
+
+    /// Closes the last opened rating period, regardless of whether it has elapsed.
+    ///
+    /// All new ratings are calculated before any player is modified,
+    /// so if an error is returned, the rating period stays open and nothing is changed.
+    ///
+    /// # Returns
+    ///
+    /// A report of the closed rating period if `with_report` is `true` or if there are observers.
+    fn close_rating_period(
+        &mut self,
+        with_report: bool,
+    ) -> Result<Option<PeriodCloseReport>, GlickoError> {
+        let archived_period =
+            (self.max_archived_periods > 0).then(|| self.archive_current_period());
+
+        let new_ratings = self.period_end_ratings()?;
+
+        let period_start = self.last_rating_period_start;
+        let period_end = period_start + self.settings.rating_period_duration;
+
+        let with_report = with_report || !self.observers.is_empty();
+        let mut player_changes = Vec::new();
+
+        for ((handle, player), new_rating) in self.managed_players.iter_mut().zip(new_ratings) {
+            if with_report {
+                player_changes.push(PlayerPeriodChange::new(
+                    PlayerHandle(handle),
+                    player.rating.into_with_settings(self.settings),
+                    new_rating.into_with_settings(self.settings),
+                    player.current_rating_period_result_count(),
+                ));
+            }
+
+            // Only ratings backed by games of this rating period count as reaching a milestone
+            if !player.current_rating_period_results.is_empty() {
+                player.rating_floor = self
+                    .settings
+                    .raise_rating_floor(player.rating_floor, new_rating);
+            }
+            player.rating = new_rating;
+
+            if let Some(history_settings) = self.rating_history_settings {
+                player.record_rating(
+                    period_end,
+                    new_rating,
+                    RatingHistoryEntryKind::RatingPeriodClosed,
+                    self.settings,
+                    history_settings,
+                );
+            }
+
+            // We have now submitted the results to the players rating
+            player.current_rating_period_results.clear();
+            player.current_rating_period_result_ids.clear();
+        }
+
+        self.open_results.clear();
+
+        if let Some(archived_period) = archived_period {
+            self.push_archived_period(archived_period);
+        }
+
+        self.last_rating_period_start = period_end;
+
+        self.record_event(EngineEvent::RatingPeriodClosed);
+
+        let report =
+            with_report.then(|| PeriodCloseReport::new(period_start, period_end, player_changes));
+
+        if let Some(report) = &report {
+            for observer in self.observers.iter() {
+                for change in report.players() {
+                    observer.rating_period_closed(
+                        change.player(),
+                        change.old_rating(),
+                        change.new_rating(),
+                        period_end,
+                    );
+                }
+
+                observer.period_closed(report);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Calculates the ratings of all players at the end of the last opened rating period, in slot order.
+    #[cfg(not(feature = "rayon"))]
+    fn period_end_ratings(&self) -> Result<Vec<InternalRating>, GlickoError> {
+        self.managed_players
+            .iter()
+            .map(|(_, player)| {
+                rate_player_games(
+                    self.settings,
+                    player.rating,
+                    &player.current_rating_period_results,
+                    1.0,
+                    player.rating_floor,
+                )
+            })
+            .collect()
+    }
+
+    /// Calculates the ratings of all players at the end of the last opened rating period, in slot order.
+    ///
+    /// Every player's new rating only depends on the ratings at the start of the period,
+    /// so they are calculated in parallel.
+    #[cfg(feature = "rayon")]
+    fn period_end_ratings(&self) -> Result<Vec<InternalRating>, GlickoError> {
+        // Only the settings are shared with the other threads, so the clock doesn't need to be `Sync`.
+        let settings = self.settings;
+
+        self.managed_players
+            .par_iter()
+            .map(|(_, player)| {
+                rate_player_games(
+                    settings,
+                    player.rating,
+                    &player.current_rating_period_results,
+                    1.0,
+                    player.rating_floor,
+                )
+            })
+            .collect()
+    }
+
+    /// The amount of rating periods that have elapsed since the last one was closed as a fraction.
+    #[must_use]
+    pub fn elapsed_periods(&self) -> f64 {
+        self.elapsed_periods_at(self.clock.now())
+    }
+
+    /// The amount of rating periods that have elapsed at the given point in time since the last one was closed as a fraction.
+    ///
+    /// If `time` is earlier than the start of the last rating period, this function returns `0.0`.
+    ///
+    /// This function is meant mostly for testability.
+    #[must_use]
+    pub fn elapsed_periods_at(&self, time: SystemTime) -> f64 {
+        if let Ok(elapsed_duration) = time.duration_since(self.last_rating_period_start) {
+            elapsed_duration.as_secs_f64() / self.settings.rating_period_duration.as_secs_f64()
+        } else {
+            0.0
+        }
+    }
+
+    /// Rebuilds the current rating period results of all players from the open results,
+    /// using the current ratings of the opponents.
+    fn rebuild_current_rating_period_results(&mut self) -> Result<(), GlickoError> {
+        for (_, player) in self.managed_players.iter_mut() {
+            player.current_rating_period_results.clear();
+            player.current_rating_period_result_ids.clear();
+        }
+
+        for (&result, open_results) in &mut self.open_results {
+            for open_result in open_results {
+                for (player, rating) in open_result.ratings_mut() {
+                    if let Some(player) = self.managed_players.get(player.0) {
+                        *rating = player.rating;
+                    }
+                }
+
+                for (player, game) in open_result.games()? {
+                    if let Some(player) = self.managed_players.get_mut(player.0) {
+                        player.current_rating_period_results.push(game);
+                        player.current_rating_period_result_ids.push(result);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Calculates the rating of a player after `elapsed_periods` (see [`elapsed_periods_at`][Self::elapsed_periods_at]),
+    /// applying the rating periods that have elapsed by then exactly like [`close_rating_period`][Self::close_rating_period] would.
+    fn peek_rating(
+        &self,
+        player: &InternalEnginePlayer,
+        elapsed_periods: f64,
+    ) -> Result<InternalRating, GlickoError> {
+        let (rating, rating_floor, results) = self.peek_period_start(player, elapsed_periods)?;
+
+        rate_player_games(
+            self.settings,
+            rating,
+            results,
+            elapsed_periods.fract(),
+            rating_floor,
+        )
+    }
+
+    /// Calculates the ratings of the given players after `elapsed_periods` like [`peek_rating`][Self::peek_rating],
+    /// sorted by handle and without duplicates.
+    fn peek_ratings(
+        &self,
+        players: &[PlayerHandle],
+        elapsed_periods: f64,
+    ) -> Result<Vec<(PlayerHandle, InternalRating)>, GlickoError> {
+        let mut players = players.to_vec();
+        players.sort_unstable();
+        players.dedup();
+
+        players
+            .into_iter()
+            .map(|handle| {
+                let rating = self.peek_rating(self.player(handle)?, elapsed_periods)?;
+
+                Ok((handle, rating))
+            })
+            .collect()
+    }
+
+    /// Calculates the rating and individual rating floor of a player at the start of the rating period
+    /// that is current after `elapsed_periods` and returns them together with the player's results in that rating period,
+    /// applying the rating periods that have elapsed by then exactly like [`close_rating_period`][Self::close_rating_period] would.
+    fn peek_period_start<'a>(
+        &self,
+        player: &'a InternalEnginePlayer,
+        elapsed_periods: f64,
+    ) -> Result<(InternalRating, Option<f64>, &'a [InternalGame]), GlickoError> {
+        // We won't have negative elapsed_periods. Truncation this is the wanted result.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let periods_to_close = elapsed_periods as u32;
+
+        if periods_to_close == 0 {
+            return Ok((
+                player.rating,
+                player.rating_floor,
+                &player.current_rating_period_results,
+            ));
+        }
+
+        // The results are all in the first rating period that would be closed,
+        // so the individual rating floor can only be raised by closing that one
+        let mut rating = self.close_player_rating_period(
+            player.rating,
+            &player.current_rating_period_results,
+            player.rating_floor,
+        )?;
+        let rating_floor = if player.current_rating_period_results.is_empty() {
+            player.rating_floor
+        } else {
+            self.settings
+                .raise_rating_floor(player.rating_floor, rating)
+        };
+        for _ in 1..periods_to_close {
+            rating = self.close_player_rating_period(rating, &[], rating_floor)?;
+        }
+
+        Ok((rating, rating_floor, &[]))
+    }
+
+    /// Calculates the ratings of two players at the given time if they played a result with the given scores at that time.
+    fn preview_scores_at(
+        &self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        player_1_score: f64,
+        player_2_score: f64,
+        time: SystemTime,
+    ) -> Result<(InternalRating, InternalRating), GlickoError> {
+        algorithm::validate_score(player_1_score)?;
+        algorithm::validate_score(player_2_score)?;
+
+        let elapsed_periods = self.elapsed_periods_at(time);
+
+        let (player_1_rating, player_1_rating_floor, player_1_results) =
+            self.peek_period_start(self.player(player_1)?, elapsed_periods)?;
+        let (player_2_rating, player_2_rating_floor, player_2_results) =
+            self.peek_period_start(self.player(player_2)?, elapsed_periods)?;
+
+        let player_1_game = InternalGame::new(player_2_rating, player_1_score);
+        let player_2_game = InternalGame::new(player_1_rating, player_2_score);
+
+        // A player playing against themselves gets both games
+        let mut player_1_results = player_1_results.to_vec();
+        let mut player_2_results = player_2_results.to_vec();
+        player_1_results.push(player_1_game);
+        player_2_results.push(player_2_game);
+        if player_1 == player_2 {
+            player_1_results.push(player_2_game);
+            player_2_results.clone_from(&player_1_results);
+        }
+
+        Ok((
+            rate_player_games(
+                self.settings,
+                player_1_rating,
+                &player_1_results,
+                elapsed_periods.fract(),
+                player_1_rating_floor,
+            )?,
+            rate_player_games(
+                self.settings,
+                player_2_rating,
+                &player_2_results,
+                elapsed_periods.fract(),
+                player_2_rating_floor,
+            )?,
+        ))
+    }
+
+    /// Calculates the rating of a player at the end of the last opened rating period.
+    fn close_player_rating_period(
+        &self,
+        rating: InternalRating,
+        results: &[InternalGame],
+        rating_floor: Option<f64>,
+    ) -> Result<InternalRating, GlickoError> {
+        rate_player_games(self.settings, rating, results, 1.0, rating_floor)
+    }
+
+    /// Calculates the rating of a player at the given time if they additionally played `games` in the current rating period.
+    fn rating_with_games_at(
+        &self,
+        player: PlayerHandle,
+        games: &[InternalGame],
+        time: SystemTime,
+    ) -> Result<InternalRating, GlickoError> {
+        let player = self.player(player)?;
+
+        let mut results = player.current_rating_period_results.clone();
+        results.extend_from_slice(games);
+
+        rate_player_games(
+            self.settings,
+            player.rating,
+            &results,
+            self.elapsed_periods_at(time),
+            player.rating_floor,
+        )
+    }
+
+    /// The ratings of the given players at `time`, sorted by handle and without duplicates,
+    /// to notify observers of how they changed. Removed players are skipped.
+    ///
+    /// # Returns
+    ///
+    /// `None` if nobody observes this engine, in which case nothing is calculated.
+    fn observed_ratings_at(
+        &self,
+        players: impl IntoIterator<Item = PlayerHandle>,
+        time: SystemTime,
+    ) -> Result<Option<Vec<(PlayerHandle, InternalRating)>>, GlickoError> {
+        if self.observers.is_empty() {
+            return Ok(None);
+        }
+
+        let elapsed_periods = self.elapsed_periods_at(time);
+
+        players
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter_map(|handle| Some((handle, self.managed_players.get(handle.0)?)))
+            .map(|(handle, player)| Ok((handle, self.peek_rating(player, elapsed_periods)?)))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some)
+    }
+
+    /// Calls `notify` on every observer for every player of `old_ratings` (see [`observed_ratings_at`][Self::observed_ratings_at]),
+    /// with the player's old rating and their rating at `time` after the change.
+    fn notify_rating_changes(
+        &self,
+        old_ratings: Option<Vec<(PlayerHandle, InternalRating)>>,
+        time: SystemTime,
+        notify: impl Fn(&dyn RatingObserver, PlayerHandle, PublicRating, PublicRating),
+    ) -> Result<(), GlickoError> {
+        let elapsed_periods = self.elapsed_periods_at(time);
+
+        for (player, old_rating) in old_ratings.into_iter().flatten() {
+            let new_rating = self.peek_rating(self.player(player)?, elapsed_periods)?;
+
+            let old_rating = old_rating.into_with_settings(self.settings);
+            let new_rating = new_rating.into_with_settings(self.settings);
+
+            for observer in self.observers.iter() {
+                notify(observer, player, old_rating, new_rating);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Notifies observers of every player of `old_ratings` whose rating at `time` changed,
+    /// see [`RatingObserver::ratings_recalculated`].
+    fn notify_ratings_recalculated(
+        &self,
+        old_ratings: Option<Vec<(PlayerHandle, InternalRating)>>,
+        time: SystemTime,
+    ) -> Result<(), GlickoError> {
+        self.notify_rating_changes(
+            old_ratings,
+            time,
+            |observer, player, old_rating, new_rating| {
+                if old_rating != new_rating {
+                    observer.ratings_recalculated(player, old_rating, new_rating, time);
+                }
+            },
+        )
+    }
+
+    fn player(&self, player: PlayerHandle) -> Result<&InternalEnginePlayer, GlickoError> {
+        self.managed_players
+            .get(player.0)
+            .ok_or(GlickoError::UnknownPlayer(player))
+    }
+
+    fn player_mut(
+        &mut self,
+        player: PlayerHandle,
+    ) -> Result<&mut InternalEnginePlayer, GlickoError> {
+        self.managed_players
+            .get_mut(player.0)
+            .ok_or(GlickoError::UnknownPlayer(player))
+    }
+}
+
+/// Calculates the rating of a player like [`algorithm::try_rate_games_untimed`],
+/// additionally limiting it to the player's individual rating floor.
+fn rate_player_games(
+    settings: GlickoSettings,
+    rating: InternalRating,
+    results: &[InternalGame],
+    elapsed_periods: f64,
+    rating_floor: Option<f64>,
+) -> Result<InternalRating, GlickoError> {
+    let rating = algorithm::try_rate_games_untimed(rating, results, elapsed_periods, settings)?;
+
+    Ok(settings.limit_rating(rating, rating_floor))
+}
+
+/// Converts an internal rating from the internal scale of `old_settings` to the internal scale of `new_settings`,
+/// keeping the [`Public`] value the same.
+fn convert_rating_settings(
+    rating: InternalRating,
+    old_settings: GlickoSettings,
+    new_settings: GlickoSettings,
+) -> InternalRating {
+    if old_settings.start_rating() == new_settings.start_rating() {
+        rating
+    } else {
+        Rating::<Public>::from_with_settings(rating, old_settings).into_with_settings(new_settings)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, SystemTime};
+
+    use super::{MatchResult, RatingEngine, ResultId};
+    use crate::history::{RatingHistoryEntry, RatingHistoryEntryKind, RatingHistorySettings};
+    use crate::{GlickoError, GlickoSettings, MilestoneFloors, Public, PublicRating};
+
+    macro_rules! assert_approx_eq {
+        ($a:expr, $b:expr, $tolerance:expr $(,)?) => {{
+            let a_val = $a;
+            let b_val = $b;
+
+            assert!(
+                (a_val - b_val).abs() <= $tolerance,
+                "{} = {a_val} is not approximately equal to {} = {b_val}",
+                stringify!($a),
+                stringify!($b)
+            )
+        }};
     }
 
     /// This tests the example calculation in [Glickman's paper](http://www.glicko.net/glicko/glicko2.pdf).
     #[test]
-    fn test_paper_example() {
-        let settings = GlickoSettings::default()
-            .with_volatility_change(0.5)
-            .with_rating_period_duration(Duration::from_secs(1));
+    fn test_paper_example() {
+        let settings = GlickoSettings::default()
+            .with_volatility_change(0.5)
+            .with_rating_period_duration(Duration::from_secs(1));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings);
+
+        let player = engine
+            .register_player_at(PublicRating::new(1500.0, 200.0, 0.06), start_time)
+            .0;
+
+        let opponent_a = engine
+            .register_player_at(
+                PublicRating::new(1400.0, 30.0, settings.start_rating().volatility()),
+                start_time,
+            )
+            .0;
+        let opponent_b = engine
+            .register_player_at(
+                PublicRating::new(1550.0, 100.0, settings.start_rating().volatility()),
+                start_time,
+            )
+            .0;
+        let opponent_c = engine
+            .register_player_at(
+                PublicRating::new(1700.0, 300.0, settings.start_rating().volatility()),
+                start_time,
+            )
+            .0;
+
+        engine.register_result_at(player, opponent_a, &MatchResult::Win, start_time);
+        engine.register_result_at(player, opponent_b, &MatchResult::Loss, start_time);
+        engine.register_result_at(player, opponent_c, &MatchResult::Loss, start_time);
+
+        let rating_period_end_time = start_time + Duration::from_secs(1);
+
+        let new_rating: PublicRating = engine.player_rating_at(player, rating_period_end_time).0;
+
+        assert_approx_eq!(new_rating.rating(), 1464.06, 0.01);
+        assert_approx_eq!(new_rating.deviation(), 151.52, 0.01);
+        assert_approx_eq!(new_rating.volatility(), 0.05999, 0.0001);
+    }
+
+    #[test]
+    fn test_rating_period_close() {
+        // Setup similar to paper setup
+        let settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(1));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings);
+
+        let player = engine
+            .register_player_at(PublicRating::new(1500.0, 200.0, 0.06), start_time)
+            .0;
+
+        let opponent = engine
+            .register_player_at(
+                PublicRating::new(1400.0, 30.0, settings.start_rating().volatility()),
+                start_time,
+            )
+            .0;
+
+        engine.register_result_at(player, opponent, &MatchResult::Win, start_time);
+
+        assert_approx_eq!(engine.elapsed_periods_at(start_time), 0.0, f64::EPSILON);
+        let (elapsed_periods, closed_periods) = engine.maybe_close_rating_periods_at(start_time);
+        assert_approx_eq!(elapsed_periods, 0.0, f64::EPSILON);
+        assert_eq!(closed_periods, 0);
+
+        // Test that rating doesn't radically change across rating periods
+        let right_before = start_time + (Duration::from_secs(1) - Duration::from_nanos(1));
+        let (rating_right_before, closed_periods) =
+            engine.player_rating_at::<Public>(player, right_before);
+        assert_eq!(closed_periods, 0);
+
+        let right_after = start_time + (Duration::from_secs(1) + Duration::from_nanos(1));
+        let (rating_right_after, closed_periods) =
+            engine.player_rating_at::<Public>(player, right_after);
+        assert_eq!(closed_periods, 1);
+
+        assert_approx_eq!(
+            rating_right_before.rating(),
+            rating_right_after.rating(),
+            0.000_000_001,
+        );
+        // Rating deviation is supposed to change over time.
+        // 2 nanoseconds won't change deviation much,
+        // but they theoretically can change it a little and it's fine
+        assert_approx_eq!(
+            rating_right_before.deviation(),
+            rating_right_after.deviation(),
+            0.000_000_001,
+        );
+        assert_approx_eq!(
+            rating_right_before.volatility(),
+            rating_right_after.volatility(),
+            0.000_000_001,
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_rating_period_close() {
+        let settings = GlickoSettings::default().with_rating_floor(1300.0);
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings);
+
+        let players = (0..500_u32)
+            .map(|index| {
+                let rating = PublicRating::new(
+                    1300.0 + f64::from(index % 50) * 10.0,
+                    50.0 + f64::from(index % 7) * 40.0,
+                    0.06,
+                );
+
+                engine.register_player_at(rating, start_time).0
+            })
+            .collect::<Vec<_>>();
+
+        for &player in players.iter().step_by(9) {
+            engine.set_player_rating_floor(player, Some(1450.0));
+        }
+
+        let results = [MatchResult::Win, MatchResult::Draw, MatchResult::Loss];
+        for (index, window) in players.windows(3).enumerate() {
+            engine.register_result_at(window[0], window[2], &results[index % 3], start_time);
+            engine.register_result_at(window[1], window[0], &results[index % 2], start_time);
+        }
+
+        // The new ratings calculated in parallel are the same as calculating them one by one
+        let expected_ratings = engine
+            .managed_players
+            .iter()
+            .map(|(handle, player)| {
+                let rating = engine
+                    .close_player_rating_period(
+                        player.rating,
+                        &player.current_rating_period_results,
+                        player.rating_floor,
+                    )
+                    .unwrap();
+
+                (super::PlayerHandle(handle), rating)
+            })
+            .collect::<Vec<_>>();
+
+        engine.maybe_close_rating_periods_at(start_time + settings.rating_period_duration());
+
+        for (player, expected_rating) in expected_ratings {
+            assert_eq!(engine.player(player).unwrap().rating, expected_rating);
+        }
+    }
+
+    #[test]
+    fn test_time_change() {
+        // Setup similar to paper setup
+        let settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(60 * 60));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings);
+
+        let player = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+
+        let rating_at_start: PublicRating = engine.player_rating_at(player, start_time).0;
+        let rating_after_year: PublicRating = engine
+            .player_rating_at(player, start_time + Duration::from_secs(60 * 60 * 24 * 365))
+            .0;
+
+        // Deviation grows over time, rest should stay the same
+        assert_approx_eq!(
+            rating_at_start.rating(),
+            rating_after_year.rating(),
+            0.000_000_001,
+        );
+        // Adding one to make sure it grows somewhat significantly
+        // TODO: Make actual calculation on what deviation is expected
+        assert!(rating_at_start.deviation() + 1.0 < rating_after_year.deviation());
+        assert_approx_eq!(
+            rating_at_start.volatility(),
+            rating_after_year.volatility(),
+            0.000_000_001,
+        );
+    }
+
+    #[test]
+    fn test_remove_player() {
+        let settings = GlickoSettings::default();
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings);
+
+        let player = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        let opponent = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+
+        engine.register_result_at(player, opponent, &MatchResult::Win, start_time);
+
+        let removed = engine.remove_player::<Public>(player).unwrap();
+        assert_eq!(removed.current_rating_period_results().len(), 1);
+        assert!(!engine.contains_player(player));
+        assert!(engine.remove_player::<Public>(player).is_none());
+        assert_eq!(engine.player_count(), 1);
+
+        // The freed slot gets reused, but the stale handle must not point to the new player
+        let new_player = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        assert_ne!(player, new_player);
+        assert!(!engine.contains_player(player));
+        assert!(engine.contains_player(new_player));
+        assert_eq!(
+            engine.player_handles().collect::<Vec<_>>(),
+            vec![new_player, opponent],
+        );
+
+        // The opponent keeps the result against the removed player
+        let opponent_rating: PublicRating = engine.player_rating_at(opponent, start_time).0;
+        assert!(opponent_rating.rating() < settings.start_rating().rating());
+    }
+
+    #[test]
+    fn test_try_errors() {
+        let settings = GlickoSettings::default();
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings);
+
+        let player = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        let removed = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        engine.remove_player::<Public>(removed);
+
+        assert_eq!(
+            engine.try_register_result_at(player, removed, &MatchResult::Win, start_time),
+            Err(GlickoError::UnknownPlayer(removed)),
+        );
+        assert_eq!(
+            engine.try_player_rating_at::<Public>(removed, start_time),
+            Err(GlickoError::UnknownPlayer(removed)),
+        );
+        assert_eq!(
+            engine.try_last_rating_period_rating::<Public>(removed),
+            Err(GlickoError::UnknownPlayer(removed)),
+        );
+
+        // A failed registration must not close rating periods
+        let later = start_time + settings.rating_period_duration() * 2;
+        assert_eq!(
+            engine.try_register_result_at(player, removed, &MatchResult::Win, later),
+            Err(GlickoError::UnknownPlayer(removed)),
+        );
+        assert_eq!(engine.last_rating_period_start(), start_time);
+
+        // The player has no results, so the failed registration didn't add anything
+        let rating: PublicRating = engine.player_rating_at(player, start_time).0;
+        assert_eq!(rating, settings.start_rating());
+
+        assert_eq!(
+            PublicRating::try_new(1500.0, 0.0, 0.06),
+            Err(GlickoError::InvalidDeviation(0.0)),
+        );
+        assert_eq!(
+            GlickoSettings::try_new(settings.start_rating(), 0.5, 0.000_001, Duration::ZERO),
+            Err(GlickoError::ZeroRatingPeriodDuration),
+        );
+    }
+
+    #[test]
+    fn test_replay() {
+        let settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(10));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings).with_event_log();
+
+        let player_1 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        let player_2 = engine
+            .register_player_at(PublicRating::new(1700.0, 100.0, 0.06), start_time)
+            .0;
+        let player_3 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+
+        engine.register_result_at(player_1, player_2, &MatchResult::Win, start_time);
+        engine.register_result_at(
+            player_2,
+            player_3,
+            &MatchResult::Draw,
+            start_time + Duration::from_secs(15),
+        );
+        let (result, _) = engine.register_result_at(
+            player_1,
+            player_3,
+            &MatchResult::Win,
+            start_time + Duration::from_secs(21),
+        );
+        engine.amend_result_at(
+            result,
+            &MatchResult::Loss,
+            start_time + Duration::from_secs(22),
+        );
+        let (result, _) = engine.register_result_at(
+            player_1,
+            player_2,
+            &MatchResult::Win,
+            start_time + Duration::from_secs(23),
+        );
+        engine.retract_result_at(result, start_time + Duration::from_secs(24));
+        let _ = engine.player_rating_at::<Public>(player_1, start_time + Duration::from_secs(25));
+        engine.remove_player::<Public>(player_3);
+        engine.change_settings_at(
+            settings.with_start_rating(PublicRating::new(1200.0, 350.0, 0.06)),
+            start_time + Duration::from_secs(27),
+        );
+        let player_4 = engine
+            .register_player_at(
+                settings.start_rating(),
+                start_time + Duration::from_secs(28),
+            )
+            .0;
+        engine.register_result_at(
+            player_4,
+            player_1,
+            &MatchResult::Loss,
+            start_time + Duration::from_secs(35),
+        );
+
+        let events = engine.event_log().unwrap().to_vec();
+        let replayed = RatingEngine::replay(settings, &events).unwrap();
+        assert_eq!(replayed, engine);
+
+        // Draining the event log in pieces results in the same log
+        let mut drained = engine.drain_event_log();
+        drained.extend(engine.drain_event_log());
+        assert_eq!(drained, events);
+
+        // Replaying with different settings results in different ratings
+        let mut replayed =
+            RatingEngine::replay(settings.with_volatility_change(0.3), &events).unwrap();
+        let new_rating: PublicRating = replayed.player_rating_at(player_1, start_time).0;
+        let old_rating: PublicRating = engine.player_rating_at(player_1, start_time).0;
+        assert_ne!(new_rating, old_rating);
+
+        assert_eq!(
+            RatingEngine::replay(settings, &events[1..]),
+            Err(GlickoError::InvalidEventLog),
+        );
+    }
+
+    #[test]
+    fn test_replay_registered_rating() {
+        let settings = GlickoSettings::default();
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings).with_event_log();
+
+        let rating = PublicRating::new(1700.0, 100.0, 0.06);
+        let player = engine.register_player_at(rating, start_time).0;
+
+        // The registered rating is kept on the public scale, even if the start rating differs
+        let replay_settings = settings.with_start_rating(PublicRating::new(1200.0, 350.0, 0.06));
+        let mut replayed =
+            RatingEngine::replay(replay_settings, engine.event_log().unwrap()).unwrap();
+        let replayed_rating: PublicRating = replayed.player_rating_at(player, start_time).0;
+        assert_approx_eq!(replayed_rating.rating(), rating.rating(), 1e-9);
+        assert_approx_eq!(replayed_rating.deviation(), rating.deviation(), 1e-9);
+    }
+
+    #[test]
+    fn test_retract_and_amend_result() {
+        let settings = GlickoSettings::default();
 
         let start_time = SystemTime::UNIX_EPOCH;
 
         let mut engine = RatingEngine::start_new_at(start_time, settings);
 
         let player = engine
-            .register_player_at(PublicRating::new(1500.0, 200.0, 0.06), start_time)
+            .register_player_at(settings.start_rating(), start_time)
             .0;
-
-        let opponent_a = engine
-            .register_player_at(
-                PublicRating::new(1400.0, 30.0, settings.start_rating().volatility()),
-                start_time,
-            )
+        let opponent = engine
+            .register_player_at(PublicRating::new(1600.0, 100.0, 0.06), start_time)
             .0;
-        let opponent_b = engine
-            .register_player_at(
-                PublicRating::new(1550.0, 100.0, settings.start_rating().volatility()),
-                start_time,
-            )
+
+        let mut expected_engine = engine.clone();
+        expected_engine.register_result_at(player, opponent, &MatchResult::Draw, start_time);
+
+        let (win, _) = engine.register_result_at(player, opponent, &MatchResult::Win, start_time);
+        let (loss, _) = engine.register_result_at(player, opponent, &MatchResult::Loss, start_time);
+
+        engine.retract_result_at(win, start_time);
+        engine.amend_result_at(loss, &MatchResult::Draw, start_time);
+
+        for handle in [player, opponent] {
+            let rating: PublicRating = engine.player_rating_at(handle, start_time).0;
+            let expected_rating: PublicRating =
+                expected_engine.player_rating_at(handle, start_time).0;
+            assert_eq!(rating, expected_rating);
+        }
+
+        assert_eq!(
+            engine.try_retract_result_at(win, start_time),
+            Err(GlickoError::ClosedResult(win)),
+        );
+        assert_eq!(
+            engine.try_retract_result_at(ResultId(42), start_time),
+            Err(GlickoError::UnknownResult(ResultId(42))),
+        );
+
+        // Once the rating period is closed, the result can't be changed anymore
+        let next_period = start_time + settings.rating_period_duration();
+        assert_eq!(
+            engine.try_amend_result_at(loss, &MatchResult::Win, next_period),
+            Err(GlickoError::ClosedResult(loss)),
+        );
+    }
+
+    #[test]
+    fn test_rating_history() {
+        let settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(10));
+        let history_settings = RatingHistorySettings::new().with_record_games(true);
+
+        let start_time = SystemTime::UNIX_EPOCH;
+        let period = settings.rating_period_duration();
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings)
+            .with_rating_history(history_settings)
+            .with_event_log();
+
+        let player_1 = engine
+            .register_player_at(settings.start_rating(), start_time)
             .0;
-        let opponent_c = engine
-            .register_player_at(
-                PublicRating::new(1700.0, 300.0, settings.start_rating().volatility()),
-                start_time,
-            )
+        let player_2 = engine
+            .register_player_at(settings.start_rating(), start_time + Duration::from_secs(1))
             .0;
 
-        engine.register_result_at(player, opponent_a, &MatchResult::Win, start_time);
-        engine.register_result_at(player, opponent_b, &MatchResult::Loss, start_time);
-        engine.register_result_at(player, opponent_c, &MatchResult::Loss, start_time);
+        let game_time = start_time + Duration::from_secs(2);
+        engine.register_result_at(player_1, player_2, &MatchResult::Win, game_time);
+        let game_rating: PublicRating = engine.clone().player_rating_at(player_1, game_time).0;
 
-        let rating_period_end_time = start_time + Duration::from_secs(1);
+        let (_, closed_periods) = engine.maybe_close_rating_periods_at(start_time + period * 2);
+        assert_eq!(closed_periods, 2);
 
-        let new_rating: PublicRating = engine.player_rating_at(player, rating_period_end_time).0;
+        let history = engine.rating_history(player_1).collect::<Vec<_>>();
+        let kinds = history
+            .iter()
+            .map(RatingHistoryEntry::kind)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [
+                RatingHistoryEntryKind::Registered,
+                RatingHistoryEntryKind::Game,
+                RatingHistoryEntryKind::RatingPeriodClosed,
+                RatingHistoryEntryKind::RatingPeriodClosed,
+            ],
+        );
+        assert_eq!(history[1].rating(), game_rating);
+        assert_eq!(history[3].time(), start_time + period * 2);
+        assert_eq!(
+            history[3].rating(),
+            engine.last_rating_period_rating::<Public>(player_1),
+        );
 
-        assert_approx_eq!(new_rating.rating(), 1464.06, 0.01);
-        assert_approx_eq!(new_rating.deviation(), 151.52, 0.01);
-        assert_approx_eq!(new_rating.volatility(), 0.05999, 0.0001);
+        assert_eq!(
+            engine.rating_at_past_time::<Public>(player_1, start_time + Duration::from_secs(5)),
+            Some(game_rating),
+        );
+        // player_2 was registered after this
+        assert_eq!(
+            engine.rating_at_past_time::<Public>(player_2, start_time),
+            None,
+        );
+
+        let events = engine.event_log().unwrap().to_vec();
+        let replayed = RatingEngine::replay(settings, &events).unwrap();
+        assert_eq!(replayed, engine);
+
+        // Old entries are forgotten when the retention shrinks
+        engine.set_rating_history_settings(Some(history_settings.with_max_entries(2)));
+        assert_eq!(engine.rating_history(player_1).count(), 2);
+        assert_eq!(
+            engine.rating_at_past_time::<Public>(player_1, start_time + Duration::from_secs(5)),
+            None,
+        );
+
+        engine.set_rating_history_settings(None);
+        assert_eq!(engine.rating_history(player_1).count(), 0);
     }
 
     #[test]
-    fn test_rating_period_close() {
-        // Setup similar to paper setup
+    fn test_peek_player_rating() {
         let settings =
-            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(1));
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(10));
 
         let start_time = SystemTime::UNIX_EPOCH;
 
         let mut engine = RatingEngine::start_new_at(start_time, settings);
 
         let player = engine
-            .register_player_at(PublicRating::new(1500.0, 200.0, 0.06), start_time)
+            .register_player_at(settings.start_rating(), start_time)
             .0;
-
         let opponent = engine
-            .register_player_at(
-                PublicRating::new(1400.0, 30.0, settings.start_rating().volatility()),
-                start_time,
-            )
+            .register_player_at(PublicRating::new(1700.0, 100.0, 0.06), start_time)
             .0;
 
         engine.register_result_at(player, opponent, &MatchResult::Win, start_time);
 
-        assert_approx_eq!(engine.elapsed_periods_at(start_time), 0.0, f64::EPSILON);
-        let (elapsed_periods, closed_periods) = engine.maybe_close_rating_periods_at(start_time);
-        assert_approx_eq!(elapsed_periods, 0.0, f64::EPSILON);
-        assert_eq!(closed_periods, 0);
+        let unchanged_engine = engine.clone();
 
-        // Test that rating doesn't radically change across rating periods
-        let right_before = start_time + (Duration::from_secs(1) - Duration::from_nanos(1));
-        let (rating_right_before, closed_periods) =
-            engine.player_rating_at::<Public>(player, right_before);
-        assert_eq!(closed_periods, 0);
+        for secs in [0, 5, 10, 25, 123] {
+            let time = start_time + Duration::from_secs(secs);
 
-        let right_after = start_time + (Duration::from_secs(1) + Duration::from_nanos(1));
-        let (rating_right_after, closed_periods) =
-            engine.player_rating_at::<Public>(player, right_after);
-        assert_eq!(closed_periods, 1);
+            let peeked_rating: PublicRating = engine.peek_player_rating_at(player, time);
+            let rating: PublicRating = engine.clone().player_rating_at(player, time).0;
+            assert_eq!(peeked_rating, rating);
+        }
+
+        assert_eq!(engine, unchanged_engine);
+    }
+
+    #[test]
+    fn test_all_ratings() {
+        let settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(10));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings);
+
+        let players = (0..5)
+            .map(|_| {
+                engine
+                    .register_player_at(settings.start_rating(), start_time)
+                    .0
+            })
+            .collect::<Vec<_>>();
+
+        for pair in players.windows(2) {
+            engine.register_result_at(pair[0], pair[1], &MatchResult::Win, start_time);
+        }
+        engine.remove_player::<Public>(players[2]);
+
+        let time = start_time + Duration::from_secs(15);
+        let all_ratings = engine.all_ratings_at::<Public>(time);
+
+        let expected_ratings = engine
+            .player_handles()
+            .map(|player| (player, engine.peek_player_rating_at(player, time)))
+            .collect::<Vec<_>>();
+        assert_eq!(all_ratings, expected_ratings);
+        assert_eq!(all_ratings.len(), 4);
+
+        #[cfg(feature = "rayon")]
+        assert_eq!(engine.par_all_ratings_at::<Public>(time), all_ratings);
+    }
+
+    #[test]
+    fn test_register_ranked_result() {
+        let settings = GlickoSettings::default();
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings).with_event_log();
+
+        let players = (0..4)
+            .map(|_| {
+                engine
+                    .register_player_at(settings.start_rating(), start_time)
+                    .0
+            })
+            .collect::<Vec<_>>();
+
+        let unchanged_engine = engine.clone();
+
+        let (result, _) = engine.register_ranked_result_at(&players, &[1, 2, 2, 4], start_time);
+
+        let ratings = players
+            .iter()
+            .map(|&player| engine.peek_player_rating_at::<Public>(player, start_time))
+            .collect::<Vec<_>>();
+        assert!(ratings[0].rating() > ratings[1].rating());
+        assert_eq!(ratings[1], ratings[2]);
+        assert!(ratings[2].rating() > ratings[3].rating());
+
+        // The whole ranking counts as one game for every player
+        assert!(players.iter().all(|&player| engine
+            .managed_players
+            .get(player.0)
+            .unwrap()
+            .games_played()
+            == 1));
+
+        // The virtual games add up to one full game,
+        // so the winner gains less than from winning one game against each opponent
+        let mut pairwise_engine = unchanged_engine.clone();
+        for &opponent in &players[1..] {
+            pairwise_engine.register_result_at(players[0], opponent, &MatchResult::Win, start_time);
+        }
+        let pairwise_rating: PublicRating =
+            pairwise_engine.peek_player_rating_at(players[0], start_time);
+        assert!(ratings[0].rating() < pairwise_rating.rating());
+
+        // A ranking of two players is the same as a normal result
+        let mut two_player_engine = unchanged_engine.clone();
+        two_player_engine.register_ranked_result_at(&players[..2], &[0, 1], start_time);
+        let mut result_engine = unchanged_engine.clone();
+        result_engine.register_result_at(players[0], players[1], &MatchResult::Win, start_time);
+        for &player in &players[..2] {
+            assert_eq!(
+                two_player_engine.peek_player_rating_at::<Public>(player, start_time),
+                result_engine.peek_player_rating_at::<Public>(player, start_time),
+            );
+        }
 
+        let events = engine.event_log().unwrap().to_vec();
+        let replayed = RatingEngine::replay(settings, &events).unwrap();
+        assert_eq!(replayed, engine);
+
+        // Rejecting a ranked result doesn't close rating periods
+        assert_eq!(
+            engine.try_amend_result_at(
+                result,
+                &MatchResult::Win,
+                start_time + settings.rating_period_duration() * 2,
+            ),
+            Err(GlickoError::RankedResult(result)),
+        );
+        assert_eq!(engine.last_rating_period_start(), start_time);
+        engine.retract_result_at(result, start_time);
+        for &player in &players {
+            assert_eq!(
+                engine.peek_player_rating_at::<Public>(player, start_time),
+                unchanged_engine.peek_player_rating_at::<Public>(player, start_time),
+            );
+        }
+
+        assert_eq!(
+            engine.try_register_ranked_result_at(&players[..1], &[0], start_time),
+            Err(GlickoError::InvalidRanking),
+        );
+        assert_eq!(
+            engine.try_register_ranked_result_at(
+                &[players[0], players[1], players[0]],
+                &[0, 1, 2],
+                start_time,
+            ),
+            Err(GlickoError::InvalidRanking),
+        );
+        assert_eq!(
+            engine.try_register_ranked_result_at(&players, &[0, 1], start_time),
+            Err(GlickoError::InvalidRanking),
+        );
+    }
+
+    #[test]
+    fn test_preview_result() {
+        let settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(60));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings);
+
+        let player_1 = engine
+            .register_player_at(PublicRating::new(1600.0, 100.0, 0.06), start_time)
+            .0;
+        let player_2 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        engine.register_result_at(player_1, player_2, &MatchResult::Draw, start_time);
+
+        let unchanged_engine = engine.clone();
+
+        // Also preview across a rating period boundary
+        for time in [
+            start_time + Duration::from_secs(30),
+            start_time + Duration::from_secs(90),
+        ] {
+            let preview = engine.preview_result_at::<Public>(player_1, player_2, time);
+
+            for result in [MatchResult::Win, MatchResult::Draw, MatchResult::Loss] {
+                let mut result_engine = engine.clone();
+                result_engine.register_result_at(player_1, player_2, &result, time);
+
+                let expected_ratings = (
+                    result_engine.peek_player_rating_at(player_1, time),
+                    result_engine.peek_player_rating_at(player_2, time),
+                );
+                assert_eq!(preview.outcome(result), expected_ratings);
+                assert_eq!(
+                    engine.preview_score_at::<Public, _>(player_1, player_2, &result, time),
+                    expected_ratings,
+                );
+            }
+
+            assert_eq!(
+                preview.current(),
+                (
+                    engine.peek_player_rating_at(player_1, time),
+                    engine.peek_player_rating_at(player_2, time),
+                ),
+            );
+            assert!(preview.rating_change(MatchResult::Win) > 0.0);
+            assert!(preview.rating_change(MatchResult::Loss) < 0.0);
+        }
+
+        assert_eq!(engine, unchanged_engine);
+
+        engine.remove_player::<Public>(player_2);
+        assert_eq!(
+            engine.try_preview_result_at::<Public>(player_1, player_2, start_time),
+            Err(GlickoError::UnknownPlayer(player_2)),
+        );
+    }
+
+    #[test]
+    fn test_rating_limits() {
+        let settings = GlickoSettings::default()
+            .with_rating_period_duration(Duration::from_secs(60))
+            .with_rating_floor(1300.0)
+            .with_rating_cap(2000.0)
+            .with_milestone_floors(MilestoneFloors::new(100.0, 200.0));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings).with_event_log();
+
+        let player = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        let strong_opponent = engine
+            .register_player_at(PublicRating::new(1950.0, 50.0, 0.06), start_time)
+            .0;
+        let weak_opponent = engine
+            .register_player_at(PublicRating::new(1320.0, 300.0, 0.06), start_time)
+            .0;
+
+        // The global floor and cap limit every rating
+        for _ in 0..10 {
+            engine.register_result_at(
+                weak_opponent,
+                strong_opponent,
+                &MatchResult::Loss,
+                start_time,
+            );
+        }
+        let weak_rating = engine.peek_player_rating_at::<Public>(weak_opponent, start_time);
+        assert_approx_eq!(weak_rating.rating(), 1300.0, 0.000_001);
+        assert!(
+            engine
+                .peek_player_rating_at::<Public>(strong_opponent, start_time)
+                .rating()
+                <= 2000.0
+        );
+        assert_eq!(engine.player_rating_floor(weak_opponent), None);
+
+        for _ in 0..3 {
+            engine.register_result_at(player, strong_opponent, &MatchResult::Win, start_time);
+        }
+        let time = start_time + Duration::from_secs(60);
+        let rating = engine.player_rating_at::<Public>(player, time).0;
+        assert!(rating.rating() > 1800.0);
+
+        // Reaching a milestone raises the individual floor
+        let rating_floor = engine.player_rating_floor(player).unwrap();
         assert_approx_eq!(
-            rating_right_before.rating(),
-            rating_right_after.rating(),
-            0.000_000_001,
+            rating_floor,
+            (rating.rating() / 100.0).floor() * 100.0 - 200.0,
+            0.000_001
         );
-        // Rating deviation is supposed to change over time.
-        // 2 nanoseconds won't change deviation much,
-        // but they theoretically can change it a little and it's fine
+
+        for _ in 0..10 {
+            engine.register_result_at(player, weak_opponent, &MatchResult::Loss, time);
+        }
         assert_approx_eq!(
-            rating_right_before.deviation(),
-            rating_right_after.deviation(),
-            0.000_000_001,
+            engine
+                .peek_player_rating_at::<Public>(player, time)
+                .rating(),
+            rating_floor,
+            0.000_001
+        );
+
+        engine.set_player_rating_floor(player, Some(rating.rating() - 10.0));
+        assert_approx_eq!(
+            engine
+                .peek_player_rating_at::<Public>(player, time)
+                .rating(),
+            rating.rating() - 10.0,
+            0.000_001
         );
+        let later = time + Duration::from_secs(60);
+        engine.maybe_close_rating_periods_at(later);
         assert_approx_eq!(
-            rating_right_before.volatility(),
-            rating_right_after.volatility(),
-            0.000_000_001,
+            engine
+                .peek_player_rating_at::<Public>(player, later)
+                .rating(),
+            rating.rating() - 10.0,
+            0.000_001
+        );
+
+        assert_eq!(
+            engine.try_set_player_rating_floor(player, Some(f64::INFINITY)),
+            Err(GlickoError::InvalidRatingLimit(f64::INFINITY)),
         );
+
+        let events = engine.event_log().unwrap().to_vec();
+        assert_eq!(RatingEngine::replay(settings, &events).unwrap(), engine);
     }
 
     #[test]
-    fn test_time_change() {
-        // Setup similar to paper setup
-        let settings =
-            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(60 * 60));
+    fn test_milestone_floors_need_games() {
+        let settings = GlickoSettings::default()
+            .with_rating_period_duration(Duration::from_secs(60))
+            .with_milestone_floors(MilestoneFloors::new(100.0, 200.0));
 
         let start_time = SystemTime::UNIX_EPOCH;
 
         let mut engine = RatingEngine::start_new_at(start_time, settings);
 
+        let idle_player = engine
+            .register_player_at(PublicRating::new(1850.0, 50.0, 0.06), start_time)
+            .0;
         let player = engine
-            .register_player_at(settings.start_rating(), start_time)
+            .register_player_at(PublicRating::new(1850.0, 50.0, 0.06), start_time)
+            .0;
+        let opponent = engine
+            .register_player_at(PublicRating::new(1850.0, 50.0, 0.06), start_time)
             .0;
+        engine.register_result_at(player, opponent, &MatchResult::Draw, start_time);
 
-        let rating_at_start: PublicRating = engine.player_rating_at(player, start_time).0;
-        let rating_after_year: PublicRating = engine
-            .player_rating_at(player, start_time + Duration::from_secs(60 * 60 * 24 * 365))
+        let time = start_time + Duration::from_secs(150);
+        let peeked_engine = engine.clone();
+        engine.maybe_close_rating_periods_at(time);
+
+        // Only the players who played reached their milestone
+        assert_eq!(engine.player_rating_floor(idle_player), None);
+        assert_eq!(engine.player_rating_floor(player), Some(1600.0));
+        assert_eq!(engine.player_rating_floor(opponent), Some(1600.0));
+
+        // Peeking applies the elapsed rating periods the same way
+        assert_eq!(
+            peeked_engine.peek_player_rating_at::<Public>(player, time),
+            engine.peek_player_rating_at::<Public>(player, time),
+        );
+        assert_eq!(
+            peeked_engine.peek_player_rating_at::<Public>(idle_player, time),
+            engine.peek_player_rating_at::<Public>(idle_player, time),
+        );
+    }
+
+    #[test]
+    fn test_expected_score() {
+        let settings = GlickoSettings::default();
+
+        let start_time = SystemTime::UNIX_EPOCH;
+        let mut engine = RatingEngine::start_new_at(start_time, settings);
+        let player_1 = engine
+            .register_player_at(PublicRating::new(1700.0, 50.0, 0.06), start_time)
+            .0;
+        let player_2 = engine
+            .register_player_at(PublicRating::new(1500.0, 50.0, 0.06), start_time)
             .0;
 
-        // Deviation grows over time, rest should stay the same
+        let time = start_time + Duration::from_secs(60 * 60 * 24 * 3);
+        let peeked_score = engine
+            .peek_player_rating_at::<Public>(player_1, time)
+            .expected_score(engine.peek_player_rating_at(player_2, time), settings);
         assert_approx_eq!(
-            rating_at_start.rating(),
-            rating_after_year.rating(),
-            0.000_000_001,
+            engine.expected_score_at(player_1, player_2, time),
+            peeked_score,
+            0.000_000_001
         );
-        // Adding one to make sure it grows somewhat significantly
-        // TODO: Make actual calculation on what deviation is expected
-        assert!(rating_at_start.deviation() + 1.0 < rating_after_year.deviation());
+    }
+
+    #[test]
+    fn test_max_deviation() {
+        let settings = GlickoSettings::default().with_max_deviation(350.0);
+
+        let start_time = SystemTime::UNIX_EPOCH;
+        let mut engine = RatingEngine::start_new_at(start_time, settings);
+        let player = engine
+            .register_player_at(PublicRating::new(1500.0, 300.0, 0.06), start_time)
+            .0;
+
+        // The deviation of an inactive player doesn't exceed the limit
+        let time = start_time + Duration::from_secs(60 * 60 * 24 * 365 * 2);
         assert_approx_eq!(
-            rating_at_start.volatility(),
-            rating_after_year.volatility(),
-            0.000_000_001,
+            engine
+                .player_rating_at::<Public>(player, time)
+                .0
+                .deviation(),
+            350.0,
+            0.000_001
+        );
+    }
+
+    #[test]
+    fn test_min_deviation() {
+        let settings = GlickoSettings::default().with_min_deviation(60.0);
+
+        let start_time = SystemTime::UNIX_EPOCH;
+        let mut engine = RatingEngine::start_new_at(start_time, settings);
+        let player = engine
+            .register_player_at(PublicRating::new(1500.0, 50.0, 0.06), start_time)
+            .0;
+        let opponent = engine
+            .register_player_at(PublicRating::new(1500.0, 50.0, 0.06), start_time)
+            .0;
+
+        // The deviation of a very active player doesn't fall below the limit
+        for _ in 0..20 {
+            engine.register_result_at(player, opponent, &MatchResult::Win, start_time);
+        }
+        let time = start_time + settings.rating_period_duration();
+        assert_approx_eq!(
+            engine
+                .player_rating_at::<Public>(player, time)
+                .0
+                .deviation(),
+            60.0,
+            0.000_001
         );
     }
 }
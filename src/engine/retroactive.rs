@@ -0,0 +1,657 @@
+//! Retroactive registration of results into already closed rating periods.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::SystemTime;
+
+use crate::algorithm;
+use crate::clock::Clock;
+use crate::events::EngineEvent;
+use crate::{GlickoError, GlickoSettings, InternalRating, IntoWithSettings, PublicRating};
+
+use super::{
+    convert_rating_settings, rate_player_games, PlayerHandle, RatingEngine, RecordedResult,
+    ResultId, Score,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A closed rating period as remembered by [`RatingEngine`] so results can be inserted retroactively.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub(super) struct ArchivedPeriod {
+    start: SystemTime,
+    /// The settings the rating period was closed with.
+    settings: GlickoSettings,
+    /// The ratings of all players at the start of the rating period
+    /// and their individual rating floors when it was closed, sorted by handle.
+    start_ratings: Vec<(PlayerHandle, InternalRating, Option<f64>)>,
+    /// The results of the rating period in the order they were registered.
+    results: Vec<RecordedResult>,
+}
+
+impl ArchivedPeriod {
+    fn contains_time(&self, time: SystemTime) -> bool {
+        time >= self.start && time < self.start + self.settings.rating_period_duration()
+    }
+
+    fn start_rating(&self, player: PlayerHandle) -> Option<InternalRating> {
+        self.start_ratings
+            .binary_search_by_key(&player, |&(handle, _, _)| handle)
+            .ok()
+            .map(|index| self.start_ratings[index].1)
+    }
+
+    /// The individual rating floors of all players when this rating period was closed,
+    /// together with whether they played in it.
+    fn closing_rating_floors(&self) -> BTreeMap<PlayerHandle, (Option<f64>, bool)> {
+        let players: BTreeSet<_> = self
+            .results
+            .iter()
+            .flat_map(RecordedResult::players)
+            .collect();
+
+        self.start_ratings
+            .iter()
+            .map(|&(handle, _, rating_floor)| (handle, (rating_floor, players.contains(&handle))))
+            .collect()
+    }
+
+    /// Calculates the ratings and individual rating floors of all players at the end of this rating period,
+    /// exactly like [`RatingEngine::close_rating_period`] does.
+    fn calculate_end_ratings(&self) -> Result<RecalculatedPlayers, GlickoError> {
+        // If a player played against themselves, both games count
+        let mut player_games: BTreeMap<PlayerHandle, Vec<_>> = BTreeMap::new();
+        for result in &self.results {
+            for (player, game) in result.games()? {
+                player_games.entry(player).or_default().push(game);
+            }
+        }
+
+        self.start_ratings
+            .iter()
+            .map(|&(handle, rating, rating_floor)| {
+                let games = player_games.get(&handle);
+                let new_rating = rate_player_games(
+                    self.settings,
+                    rating,
+                    games.map_or(&[][..], Vec::as_slice),
+                    1.0,
+                    rating_floor,
+                )?;
+                let new_rating_floor =
+                    end_rating_floor(rating_floor, games.is_some(), new_rating, self.settings);
+
+                Ok((handle, (new_rating, new_rating_floor)))
+            })
+            .collect()
+    }
+}
+
+/// The recalculated ratings and individual rating floors of players, by handle.
+type RecalculatedPlayers = BTreeMap<PlayerHandle, (InternalRating, Option<f64>)>;
+
+/// Corrected ratings at the ends of rating periods for the rating history.
+type CorrectedHistory = Vec<(SystemTime, PlayerHandle, PublicRating)>;
+
+/// The individual rating floor of a player with the floor `rating_floor` after closing a rating period
+/// in which they reached `rating`, see [`RatingEngine::close_rating_period`].
+fn end_rating_floor(
+    rating_floor: Option<f64>,
+    played: bool,
+    rating: InternalRating,
+    settings: GlickoSettings,
+) -> Option<f64> {
+    if played {
+        settings.raise_rating_floor(rating_floor, rating)
+    } else {
+        rating_floor
+    }
+}
+
+/// The recalculated individual rating floor of a player at the start of a rating period.
+///
+/// `rating_floor` and `rating` (in the internal scale of `settings`) are what the player originally had
+/// at the start of the rating period, and `closing_rating_floor` is what they originally had when the previous rating period was closed
+/// with `settings` (see [`ArchivedPeriod::closing_rating_floors`]).
+/// If `rating_floor` is the floor that closing the previous rating period resulted in,
+/// it is replaced with `recalculated_rating_floor`.
+/// Otherwise, it was set by hand in the meantime and stays as it was set.
+fn next_rating_floor(
+    rating_floor: Option<f64>,
+    rating: InternalRating,
+    (closing_rating_floor, played): (Option<f64>, bool),
+    recalculated_rating_floor: Option<f64>,
+    settings: GlickoSettings,
+) -> Option<f64> {
+    if rating_floor == end_rating_floor(closing_rating_floor, played, rating, settings) {
+        recalculated_rating_floor
+    } else {
+        rating_floor
+    }
+}
+
+impl<C: Clock> RatingEngine<C> {
+    /// Makes the engine remember the last `max_archived_periods` closed rating periods,
+    /// so results can be registered into them retroactively
+    /// (see [`register_result_retroactive`][Self::register_result_retroactive]).
+    ///
+    /// Only rating periods closed after this call are archived.
+    #[must_use]
+    pub fn with_archived_periods(mut self, max_archived_periods: usize) -> Self {
+        self.set_max_archived_periods(max_archived_periods);
+
+        self
+    }
+
+    /// The maximum number of closed rating periods this engine remembers.
+    ///
+    /// See [`set_max_archived_periods`][Self::set_max_archived_periods].
+    #[must_use]
+    pub fn max_archived_periods(&self) -> usize {
+        self.max_archived_periods
+    }
+
+    /// Changes how many closed rating periods this engine remembers,
+    /// so results can be registered into them retroactively
+    /// (see [`register_result_retroactive`][Self::register_result_retroactive]).
+    ///
+    /// If the engine currently remembers more rating periods, the oldest ones are forgotten.
+    /// Setting this to `0` disables archiving.
+    pub fn set_max_archived_periods(&mut self, max_archived_periods: usize) {
+        self.max_archived_periods = max_archived_periods;
+
+        while self.archived_periods.len() > max_archived_periods {
+            self.archived_periods.pop_front();
+        }
+
+        self.record_event(EngineEvent::MaxArchivedPeriodsChanged {
+            max_archived_periods,
+        });
+    }
+
+    /// Registers a result that was played at `game_time`, which might be in an already closed rating period.
+    ///
+    /// If `game_time` is in the current rating period, this behaves like [`try_register_result`][Self::try_register_result].
+    /// Otherwise, the result is inserted into the archived rating period containing `game_time`,
+    /// and all following rating periods are recalculated.
+    /// This can change the rating of any player who played in these rating periods,
+    /// and transitively of their opponents.
+    /// Individual rating floors raised by milestones are recalculated as well,
+    /// while floors set with [`set_player_rating_floor`][Self::set_player_rating_floor] stay as they were set.
+    /// If the rating history is enabled (see [`with_rating_history`][Self::with_rating_history]),
+    /// the recorded ratings at the ends of the recalculated rating periods are corrected,
+    /// but the entries recorded after games are not.
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the [`ResultId`] of the result if it was registered in the current rating period,
+    /// the handles of all players whose rating changed because of the recalculation, sorted,
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if either player doesn't belong to this [`RatingEngine`]
+    /// or wasn't registered in the rating period containing `game_time`,
+    /// [`GlickoError::InvalidScore`] if the score is not between `0.0` and `1.0`,
+    /// [`GlickoError::PeriodNotArchived`] if the rating period containing `game_time` is closed and no longer archived,
+    /// and [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn register_result_retroactive<S: Score>(
+        &mut self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        result: &S,
+        game_time: SystemTime,
+    ) -> Result<(Option<ResultId>, Vec<PlayerHandle>, u32), GlickoError> {
+        self.register_result_retroactive_at(player_1, player_2, result, game_time, self.clock.now())
+    }
+
+    /// Registers a result that was played at `game_time`, which might be in a rating period that is closed at `time`.
+    ///
+    /// See [`register_result_retroactive`][Self::register_result_retroactive].
+    ///
+    /// This function can close old rating periods (see [`maybe_close_rating_periods`][Self::maybe_close_rating_periods]).
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the [`ResultId`] of the result if it was registered in the current rating period,
+    /// the handles of all players whose rating changed because of the recalculation, sorted,
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if either player doesn't belong to this [`RatingEngine`]
+    /// or wasn't registered in the rating period containing `game_time`,
+    /// [`GlickoError::InvalidScore`] if the score is not between `0.0` and `1.0`,
+    /// [`GlickoError::PeriodNotArchived`] if the rating period containing `game_time` is closed and no longer archived,
+    /// and [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the set settings' convergence tolerance is unreasonably low.
+    pub fn register_result_retroactive_at<S: Score>(
+        &mut self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        result: &S,
+        game_time: SystemTime,
+        time: SystemTime,
+    ) -> Result<(Option<ResultId>, Vec<PlayerHandle>, u32), GlickoError> {
+        self.register_scores_retroactive_at(
+            player_1,
+            player_2,
+            result.player_score(),
+            result.opponent_score(),
+            game_time,
+            time,
+        )
+    }
+
+    pub(super) fn register_scores_retroactive_at(
+        &mut self,
+        player_1: PlayerHandle,
+        player_2: PlayerHandle,
+        player_1_score: f64,
+        player_2_score: f64,
+        game_time: SystemTime,
+        time: SystemTime,
+    ) -> Result<(Option<ResultId>, Vec<PlayerHandle>, u32), GlickoError> {
+        // Validate everything before closing rating periods so that errors don't have side effects.
+        self.player(player_1)?;
+        self.player(player_2)?;
+
+        algorithm::validate_score(player_1_score)?;
+        algorithm::validate_score(player_2_score)?;
+
+        let (_, closed_periods) = self.try_maybe_close_rating_periods_at(time)?;
+
+        if game_time >= self.last_rating_period_start {
+            let (result, _) =
+                self.register_scores_at(player_1, player_2, player_1_score, player_2_score, time)?;
+
+            return Ok((Some(result), Vec::new(), closed_periods));
+        }
+
+        let period_index = self
+            .archived_periods
+            .iter()
+            .position(|period| period.contains_time(game_time))
+            .ok_or(GlickoError::PeriodNotArchived(game_time))?;
+
+        // Work on a copy of the recalculated periods so that errors don't leave the archive half recalculated
+        let mut recalculated_periods: Vec<_> = self
+            .archived_periods
+            .range(period_index..)
+            .cloned()
+            .collect();
+
+        let period = &mut recalculated_periods[0];
+        let player_1_rating = period
+            .start_rating(player_1)
+            .ok_or(GlickoError::UnknownPlayer(player_1))?;
+        let player_2_rating = period
+            .start_rating(player_2)
+            .ok_or(GlickoError::UnknownPlayer(player_2))?;
+
+        period.results.push(RecordedResult::pair(
+            (player_1, player_1_rating),
+            (player_2, player_2_rating),
+            player_1_score,
+            player_2_score,
+            1.0,
+        ));
+
+        let (ratings, corrected_history) =
+            self.recalculate_periods(period_index, &mut recalculated_periods)?;
+
+        let old_ratings = self.observed_ratings_at(self.player_handles(), time)?;
+
+        // Everything was calculated successfully, so we can commit the results
+        self.archived_periods.truncate(period_index);
+        self.archived_periods.extend(recalculated_periods);
+
+        let changed_players = self.apply_recalculated_ratings(&ratings)?;
+
+        self.player_mut(player_1)?.record_game_played(game_time);
+        // A player playing against themselves played only one game
+        if player_1 != player_2 {
+            self.player_mut(player_2)?.record_game_played(game_time);
+        }
+
+        for (period_end, handle, rating) in corrected_history {
+            if let Some(player) = self.managed_players.get_mut(handle.0) {
+                player
+                    .rating_history
+                    .replace_rating_period_closed(period_end, rating);
+            }
+        }
+
+        self.notify_ratings_recalculated(old_ratings, time)?;
+
+        self.record_event(EngineEvent::RetroactiveResultRegistered {
+            player_1,
+            player_2,
+            player_1_score,
+            player_2_score,
+            game_time,
+            time,
+        });
+
+        Ok((None, changed_players, closed_periods))
+    }
+
+    /// Recalculates `recalculated_periods`, which are copies of the archived rating periods from `period_index` on,
+    /// applying every rating period like [`close_rating_period`][Self::close_rating_period] would.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the recalculated ratings and individual rating floors of all players
+    /// at the start of the current rating period, in the internal scale of the current settings,
+    /// and the corrected ratings at the end of each rating period for the rating history.
+    fn recalculate_periods(
+        &self,
+        period_index: usize,
+        recalculated_periods: &mut [ArchivedPeriod],
+    ) -> Result<(RecalculatedPlayers, CorrectedHistory), GlickoError> {
+        // The ratings are in the internal scale of the settings of the last recalculated period.
+        let mut ratings = RecalculatedPlayers::new();
+        let mut ratings_settings = recalculated_periods[0].settings;
+        // The floors the players originally had when the last recalculated period was closed
+        let mut closing_rating_floors = BTreeMap::new();
+        // The corrected ratings at the end of each rating period for the rating history
+        let mut corrected_history = Vec::new();
+
+        for (period, original_period) in recalculated_periods
+            .iter_mut()
+            .zip(self.archived_periods.range(period_index..))
+        {
+            let convert =
+                |rating| convert_rating_settings(rating, ratings_settings, period.settings);
+
+            for (handle, rating, rating_floor) in &mut period.start_ratings {
+                if let Some(&(new_rating, new_rating_floor)) = ratings.get(handle) {
+                    if let Some(&closing_rating_floor) = closing_rating_floors.get(handle) {
+                        *rating_floor = next_rating_floor(
+                            *rating_floor,
+                            convert_rating_settings(*rating, period.settings, ratings_settings),
+                            closing_rating_floor,
+                            new_rating_floor,
+                            ratings_settings,
+                        );
+                    }
+                    *rating = convert(new_rating);
+                }
+            }
+
+            for (handle, rating) in period
+                .results
+                .iter_mut()
+                .flat_map(RecordedResult::ratings_mut)
+            {
+                if let Some(&(new_rating, _)) = ratings.get(handle) {
+                    *rating = convert(new_rating);
+                }
+            }
+
+            // Players removed before this period keep their last recalculated rating
+            let mut end_ratings = period.calculate_end_ratings()?;
+            for (handle, (rating, rating_floor)) in ratings {
+                end_ratings
+                    .entry(handle)
+                    .or_insert_with(|| (convert(rating), rating_floor));
+            }
+
+            if self.rating_history_settings.is_some() {
+                let period_end = period.start + period.settings.rating_period_duration();
+
+                corrected_history.extend(end_ratings.iter().map(|(&handle, &(rating, _))| {
+                    (
+                        period_end,
+                        handle,
+                        rating.into_with_settings(period.settings),
+                    )
+                }));
+            }
+
+            ratings = end_ratings;
+            ratings_settings = period.settings;
+            closing_rating_floors = original_period.closing_rating_floors();
+        }
+
+        // Convert the ratings to the current settings and carry the floors over into the current period
+        let settings = self.settings;
+        for (handle, (rating, rating_floor)) in &mut ratings {
+            if let (Some(player), Some(&closing_rating_floor)) = (
+                self.managed_players.get(handle.0),
+                closing_rating_floors.get(handle),
+            ) {
+                *rating_floor = next_rating_floor(
+                    player.rating_floor,
+                    convert_rating_settings(player.rating, settings, ratings_settings),
+                    closing_rating_floor,
+                    *rating_floor,
+                    ratings_settings,
+                );
+            }
+            *rating = convert_rating_settings(*rating, ratings_settings, settings);
+        }
+
+        Ok((ratings, corrected_history))
+    }
+
+    /// Replaces the ratings and individual rating floors of all players with the recalculated ones
+    /// (see [`recalculate_periods`][Self::recalculate_periods]),
+    /// and updates the current rating period results accordingly.
+    ///
+    /// # Returns
+    ///
+    /// The handles of the players whose rating changed, sorted.
+    fn apply_recalculated_ratings(
+        &mut self,
+        ratings: &RecalculatedPlayers,
+    ) -> Result<Vec<PlayerHandle>, GlickoError> {
+        let mut changed_players = Vec::new();
+
+        for (handle, player) in self.managed_players.iter_mut() {
+            if let Some(&(rating, rating_floor)) = ratings.get(&PlayerHandle(handle)) {
+                player.rating_floor = rating_floor;
+
+                if rating != player.rating {
+                    player.rating = rating;
+                    changed_players.push(PlayerHandle(handle));
+                }
+            }
+        }
+
+        // Players removed in the current period don't have their rating updated by rebuilding
+        for (handle, rating) in self
+            .open_results
+            .values_mut()
+            .flatten()
+            .flat_map(RecordedResult::ratings_mut)
+        {
+            if let Some(&(new_rating, _)) = ratings.get(handle) {
+                *rating = new_rating;
+            }
+        }
+
+        self.rebuild_current_rating_period_results()?;
+
+        Ok(changed_players)
+    }
+
+    /// Takes a snapshot of the current rating period for archiving it.
+    pub(super) fn archive_current_period(&self) -> ArchivedPeriod {
+        ArchivedPeriod {
+            start: self.last_rating_period_start,
+            settings: self.settings,
+            start_ratings: self
+                .managed_players
+                .iter()
+                .map(|(handle, player)| (PlayerHandle(handle), player.rating, player.rating_floor))
+                .collect(),
+            results: self.open_results.values().flatten().cloned().collect(),
+        }
+    }
+
+    /// Archives a closed rating period, forgetting the oldest one if too many are archived.
+    pub(super) fn push_archived_period(&mut self, archived_period: ArchivedPeriod) {
+        self.archived_periods.push_back(archived_period);
+
+        while self.archived_periods.len() > self.max_archived_periods {
+            self.archived_periods.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, SystemTime};
+
+    use super::super::{MatchResult, RatingEngine};
+    use crate::{GlickoError, GlickoSettings, MilestoneFloors, Public, PublicRating};
+
+    #[test]
+    fn test_register_result_retroactive() {
+        let settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(10));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+        let period = settings.rating_period_duration();
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings)
+            .with_archived_periods(2)
+            .with_event_log();
+
+        let player_1 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        let player_2 = engine
+            .register_player_at(PublicRating::new(1700.0, 100.0, 0.06), start_time)
+            .0;
+        let player_3 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        let bystander = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+
+        let mut expected_engine = engine.clone();
+
+        // The result arrives late in the engine, but in time in the expected engine.
+        // player_2 is affected through the changed rating of player_3 in the second period.
+        engine.register_result_at(player_1, player_2, &MatchResult::Win, start_time);
+        engine.register_result_at(player_2, player_3, &MatchResult::Draw, start_time + period);
+
+        expected_engine.register_result_at(player_1, player_2, &MatchResult::Win, start_time);
+        expected_engine.register_result_at(player_3, player_1, &MatchResult::Loss, start_time);
+        expected_engine.register_result_at(
+            player_2,
+            player_3,
+            &MatchResult::Draw,
+            start_time + period,
+        );
+
+        let now = start_time + period * 2;
+        let (result, changed_players, closed_periods) = engine
+            .register_result_retroactive_at(player_3, player_1, &MatchResult::Loss, start_time, now)
+            .unwrap();
+        assert_eq!(result, None);
+        assert_eq!(changed_players, vec![player_1, player_2, player_3]);
+        assert_eq!(closed_periods, 1);
+
+        for handle in [player_1, player_2, player_3, bystander] {
+            let rating: PublicRating = engine.player_rating_at(handle, now).0;
+            let expected_rating: PublicRating = expected_engine.player_rating_at(handle, now).0;
+            assert_eq!(rating, expected_rating);
+        }
+
+        // A result in the current rating period is registered normally
+        let (result, changed_players, _) = engine
+            .register_result_retroactive_at(player_1, bystander, &MatchResult::Win, now, now)
+            .unwrap();
+        assert!(result.is_some());
+        assert!(changed_players.is_empty());
+
+        let events = engine.event_log().unwrap().to_vec();
+        let replayed = RatingEngine::replay(settings, &events).unwrap();
+        assert_eq!(replayed, engine);
+
+        // Only the last two rating periods are archived
+        assert_eq!(
+            engine.register_result_retroactive_at(
+                player_1,
+                player_2,
+                &MatchResult::Win,
+                start_time,
+                start_time + period * 3,
+            ),
+            Err(GlickoError::PeriodNotArchived(start_time)),
+        );
+    }
+
+    #[test]
+    fn test_register_result_retroactive_rating_floors() {
+        let settings = GlickoSettings::default()
+            .with_rating_period_duration(Duration::from_secs(10))
+            .with_milestone_floors(MilestoneFloors::new(100.0, 200.0));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+        let period = settings.rating_period_duration();
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings).with_archived_periods(2);
+
+        let player = engine
+            .register_player_at(PublicRating::new(1880.0, 150.0, 0.06), start_time)
+            .0;
+        let strong = engine
+            .register_player_at(PublicRating::new(2300.0, 50.0, 0.06), start_time)
+            .0;
+        let weak = engine
+            .register_player_at(PublicRating::new(1300.0, 50.0, 0.06), start_time)
+            .0;
+        let floored = engine
+            .register_player_at(PublicRating::new(1500.0, 100.0, 0.06), start_time)
+            .0;
+
+        let mut expected_engine = engine.clone();
+
+        // The win against the strong player lets the player reach the next milestone,
+        // so the floor binding in the second period is higher
+        for engine in [&mut engine, &mut expected_engine] {
+            engine.register_result_at(player, weak, &MatchResult::Draw, start_time);
+        }
+        expected_engine.register_result_at(player, strong, &MatchResult::Win, start_time);
+        for engine in [&mut engine, &mut expected_engine] {
+            for _ in 0..10 {
+                engine.register_result_at(player, weak, &MatchResult::Loss, start_time + period);
+                engine.register_result_at(floored, weak, &MatchResult::Loss, start_time + period);
+            }
+            // A floor set by hand binds as well
+            engine.set_player_rating_floor(floored, Some(1450.0));
+        }
+        assert_eq!(engine.player_rating_floor(player), Some(1600.0));
+        assert_eq!(expected_engine.player_rating_floor(player), Some(1700.0));
+
+        let now = start_time + period * 2;
+        engine
+            .register_result_retroactive_at(player, strong, &MatchResult::Win, start_time, now)
+            .unwrap();
+
+        for handle in [player, strong, weak, floored] {
+            let rating: PublicRating = engine.player_rating_at(handle, now).0;
+            let expected_rating: PublicRating = expected_engine.player_rating_at(handle, now).0;
+            assert_eq!(rating, expected_rating);
+            assert_eq!(
+                engine.player_rating_floor(handle),
+                expected_engine.player_rating_floor(handle),
+            );
+        }
+        assert_eq!(engine.player_rating_floor(player), Some(1700.0));
+        assert!((engine.player_rating_at::<Public>(player, now).0.rating() - 1700.0).abs() < 1e-9);
+        assert_eq!(engine.player_rating_floor(floored), Some(1450.0));
+        assert!((engine.player_rating_at::<Public>(floored, now).0.rating() - 1450.0).abs() < 1e-9);
+    }
+}
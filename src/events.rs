@@ -0,0 +1,157 @@
+//! This mod defines the [`EngineEvent`] enum which describes every change made to a [`RatingEngine`][crate::engine::RatingEngine].
+//!
+//! A [`RatingEngine`][crate::engine::RatingEngine] with an enabled event log (see [`RatingEngine::with_event_log`][crate::engine::RatingEngine::with_event_log]) records an [`EngineEvent`] for every change.
+//! These events can be persisted and later be passed to [`RatingEngine::replay`][crate::engine::RatingEngine::replay]
+//! to reconstruct the engine, possibly with different settings.
+
+use std::time::SystemTime;
+
+use crate::engine::{PlayerHandle, ResultId};
+use crate::history::RatingHistorySettings;
+use crate::team::TeamRatingAggregation;
+use crate::{GlickoSettings, PublicRating};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A change that was made to a [`RatingEngine`][crate::engine::RatingEngine].
+///
+/// Events are recorded in the order the changes were made.
+/// Replaying the same events with the same settings results in an identical engine.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EngineEvent {
+    /// The event log was started.
+    /// This is always the first event of a log.
+    Started {
+        /// The start of the rating period that was open when the event log was started.
+        time: SystemTime,
+    },
+    /// A player was registered, see [`RatingEngine::register_player_at`][crate::engine::RatingEngine::register_player_at].
+    PlayerRegistered {
+        /// The handle the player was assigned.
+        player: PlayerHandle,
+        /// The rating the player was registered with.
+        ///
+        /// When replaying, this is converted to the [`Internal`][crate::Internal] scale
+        /// with the settings in effect at that point of the replay.
+        rating: PublicRating,
+        /// The time the player was registered at.
+        time: SystemTime,
+    },
+    /// A player was removed, see [`RatingEngine::remove_player`][crate::engine::RatingEngine::remove_player].
+    PlayerRemoved {
+        /// The handle of the removed player.
+        player: PlayerHandle,
+    },
+    /// The individual rating floor of a player was changed,
+    /// see [`RatingEngine::set_player_rating_floor`][crate::engine::RatingEngine::set_player_rating_floor].
+    PlayerRatingFloorChanged {
+        /// The handle of the player.
+        player: PlayerHandle,
+        /// The new [`Public`][crate::Public] rating floor, or `None` if it was removed.
+        rating_floor: Option<f64>,
+    },
+    /// A result was registered, see [`RatingEngine::register_result_at`][crate::engine::RatingEngine::register_result_at].
+    ResultRegistered {
+        /// The id the result was assigned.
+        result: ResultId,
+        /// The first player.
+        player_1: PlayerHandle,
+        /// The second player.
+        player_2: PlayerHandle,
+        /// The score of the first player.
+        player_1_score: f64,
+        /// The score of the second player.
+        player_2_score: f64,
+        /// The time the result was registered at.
+        time: SystemTime,
+    },
+    /// A team result was registered, see [`RatingEngine::register_team_result_at`][crate::engine::RatingEngine::register_team_result_at].
+    TeamResultRegistered {
+        /// The id the result was assigned.
+        result: ResultId,
+        /// The members of the first team.
+        team_1: Vec<PlayerHandle>,
+        /// The members of the second team.
+        team_2: Vec<PlayerHandle>,
+        /// The score of the first team.
+        team_1_score: f64,
+        /// The score of the second team.
+        team_2_score: f64,
+        /// The time the result was registered at.
+        time: SystemTime,
+    },
+    /// A ranked result was registered, see [`RatingEngine::register_ranked_result_at`][crate::engine::RatingEngine::register_ranked_result_at].
+    RankedResultRegistered {
+        /// The id the result was assigned.
+        result: ResultId,
+        /// The players of the match.
+        players: Vec<PlayerHandle>,
+        /// The placements of the players, in the same order.
+        placements: Vec<u32>,
+        /// The time the result was registered at.
+        time: SystemTime,
+    },
+    /// A result was retracted, see [`RatingEngine::retract_result_at`][crate::engine::RatingEngine::retract_result_at].
+    ResultRetracted {
+        /// The id of the retracted result.
+        result: ResultId,
+        /// The time the result was retracted at.
+        time: SystemTime,
+    },
+    /// The score of a result was amended, see [`RatingEngine::amend_result_at`][crate::engine::RatingEngine::amend_result_at].
+    ResultAmended {
+        /// The id of the amended result.
+        result: ResultId,
+        /// The new score of the first player.
+        player_1_score: f64,
+        /// The new score of the second player.
+        player_2_score: f64,
+        /// The time the result was amended at.
+        time: SystemTime,
+    },
+    /// A result was registered retroactively, see [`RatingEngine::register_result_retroactive_at`][crate::engine::RatingEngine::register_result_retroactive_at].
+    RetroactiveResultRegistered {
+        /// The first player.
+        player_1: PlayerHandle,
+        /// The second player.
+        player_2: PlayerHandle,
+        /// The score of the first player.
+        player_1_score: f64,
+        /// The score of the second player.
+        player_2_score: f64,
+        /// The time the game was played at.
+        game_time: SystemTime,
+        /// The time the result was registered at.
+        time: SystemTime,
+    },
+    /// The maximum number of archived rating periods was changed,
+    /// see [`RatingEngine::set_max_archived_periods`][crate::engine::RatingEngine::set_max_archived_periods].
+    MaxArchivedPeriodsChanged {
+        /// The new maximum number of archived rating periods.
+        max_archived_periods: usize,
+    },
+    /// The rating history settings were changed,
+    /// see [`RatingEngine::set_rating_history_settings`][crate::engine::RatingEngine::set_rating_history_settings].
+    RatingHistorySettingsChanged {
+        /// The new rating history settings, or `None` if the rating history was disabled.
+        settings: Option<RatingHistorySettings>,
+    },
+    /// The team rating aggregation was changed,
+    /// see [`RatingEngine::set_team_rating_aggregation`][crate::engine::RatingEngine::set_team_rating_aggregation].
+    TeamRatingAggregationChanged {
+        /// The new team rating aggregation.
+        aggregation: TeamRatingAggregation,
+    },
+    /// The settings were changed, see [`RatingEngine::change_settings_at`][crate::engine::RatingEngine::change_settings_at].
+    SettingsChanged {
+        /// The new settings.
+        settings: GlickoSettings,
+        /// The time the settings were changed at.
+        time: SystemTime,
+    },
+    /// The last opened rating period was closed, see [`RatingEngine::maybe_close_rating_periods_at`][crate::engine::RatingEngine::maybe_close_rating_periods_at].
+    RatingPeriodClosed,
+}
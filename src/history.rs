@@ -0,0 +1,189 @@
+//! This mod defines the types used by the rating history of [`RatingEngine`][crate::engine::RatingEngine].
+//!
+//! See [`RatingEngine::with_rating_history`][crate::engine::RatingEngine::with_rating_history].
+
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+
+use crate::PublicRating;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Settings controlling what the rating history of a [`RatingEngine`][crate::engine::RatingEngine] records
+/// and how long it keeps the records.
+///
+/// By default, only the ratings at rating period boundaries are recorded, and they are kept forever.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[allow(clippy::module_name_repetitions)]
+pub struct RatingHistorySettings {
+    record_games: bool,
+    max_entries: Option<usize>,
+    max_age: Option<Duration>,
+}
+
+impl RatingHistorySettings {
+    /// Creates [`RatingHistorySettings`] that record the ratings at rating period boundaries and keep them forever.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether a player's rating is also recorded after each of their games.
+    #[must_use]
+    pub fn with_record_games(mut self, record_games: bool) -> Self {
+        self.record_games = record_games;
+
+        self
+    }
+
+    /// Sets the maximum number of entries kept per player.
+    /// If there are more entries, the oldest ones are forgotten.
+    #[must_use]
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+
+        self
+    }
+
+    /// Sets the maximum age of the entries kept per player, relative to the newest entry of that player.
+    /// Older entries are forgotten.
+    #[must_use]
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+
+        self
+    }
+
+    /// Whether a player's rating is also recorded after each of their games.
+    #[must_use]
+    pub fn record_games(&self) -> bool {
+        self.record_games
+    }
+
+    /// The maximum number of entries kept per player, or `None` if there is no limit.
+    #[must_use]
+    pub fn max_entries(&self) -> Option<usize> {
+        self.max_entries
+    }
+
+    /// The maximum age of the entries kept per player, or `None` if there is no limit.
+    #[must_use]
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+}
+
+/// What caused a [`RatingHistoryEntry`] to be recorded.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RatingHistoryEntryKind {
+    /// The player was registered.
+    Registered,
+    /// A rating period was closed.
+    RatingPeriodClosed,
+    /// The player played a game.
+    Game,
+}
+
+/// A rating of a player at a point in time, as recorded by the rating history of [`RatingEngine`][crate::engine::RatingEngine].
+///
+/// The rating is stored in [`Public`][crate::Public] scale so that it stays valid when the settings of the engine change.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[allow(clippy::module_name_repetitions)]
+pub struct RatingHistoryEntry {
+    time: SystemTime,
+    rating: PublicRating,
+    kind: RatingHistoryEntryKind,
+}
+
+impl RatingHistoryEntry {
+    pub(crate) fn new(
+        time: SystemTime,
+        rating: PublicRating,
+        kind: RatingHistoryEntryKind,
+    ) -> Self {
+        RatingHistoryEntry { time, rating, kind }
+    }
+
+    /// The point in time the rating was recorded for.
+    #[must_use]
+    pub fn time(&self) -> SystemTime {
+        self.time
+    }
+
+    /// The recorded rating.
+    #[must_use]
+    pub fn rating(&self) -> PublicRating {
+        self.rating
+    }
+
+    /// What caused this entry to be recorded.
+    #[must_use]
+    pub fn kind(&self) -> RatingHistoryEntryKind {
+        self.kind
+    }
+}
+
+/// The recorded ratings of one player, sorted by time.
+#[derive(Clone, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub(crate) struct RatingHistory {
+    entries: VecDeque<RatingHistoryEntry>,
+}
+
+impl RatingHistory {
+    pub(crate) fn entries(&self) -> impl Iterator<Item = RatingHistoryEntry> + '_ {
+        self.entries.iter().copied()
+    }
+
+    /// Inserts an entry after all entries that are not later than it, and forgets old entries according to `settings`.
+    pub(crate) fn insert(&mut self, entry: RatingHistoryEntry, settings: RatingHistorySettings) {
+        let index = self
+            .entries
+            .partition_point(|existing| existing.time <= entry.time);
+        self.entries.insert(index, entry);
+
+        self.prune(settings);
+    }
+
+    /// Replaces the rating of the entry recorded for the rating period closed at `time`, if it is still kept.
+    pub(crate) fn replace_rating_period_closed(&mut self, time: SystemTime, rating: PublicRating) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| {
+            entry.time == time && entry.kind == RatingHistoryEntryKind::RatingPeriodClosed
+        }) {
+            entry.rating = rating;
+        }
+    }
+
+    /// The latest rating recorded at or before `time`.
+    pub(crate) fn rating_at(&self, time: SystemTime) -> Option<PublicRating> {
+        let index = self.entries.partition_point(|entry| entry.time <= time);
+
+        index.checked_sub(1).map(|index| self.entries[index].rating)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Forgets old entries according to `settings`.
+    pub(crate) fn prune(&mut self, settings: RatingHistorySettings) {
+        if let Some(max_entries) = settings.max_entries {
+            while self.entries.len() > max_entries {
+                self.entries.pop_front();
+            }
+        }
+
+        if let (Some(max_age), Some(newest)) = (settings.max_age, self.entries.back()) {
+            // If the subtraction overflows, no entry can be too old
+            if let Some(cutoff) = newest.time.checked_sub(max_age) {
+                while matches!(self.entries.front(), Some(entry) if entry.time < cutoff) {
+                    self.entries.pop_front();
+                }
+            }
+        }
+    }
+}
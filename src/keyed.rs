@@ -0,0 +1,1760 @@
+//! This mod defines the [`KeyedRatingEngine`] struct which manages players by caller-supplied keys instead of [`PlayerHandle`]s.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::clock::{Clock, SystemClock};
+use crate::engine::{EnginePlayer, PlayerHandle, RatingEngine, ResultId, ResultPreview, Score};
+use crate::events::EngineEvent;
+use crate::history::{RatingHistoryEntry, RatingHistorySettings};
+use crate::observer::RatingObserver;
+use crate::report::PeriodCloseReport;
+use crate::team::TeamRatingAggregation;
+use crate::{ConvertToScale, GlickoError, GlickoSettings, Internal, Public, Rating, RatingScale};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A wrapper around [`RatingEngine`] that identifies players by keys of type `K`, e.g. user IDs.
+///
+/// The mapping from keys to [`PlayerHandle`]s is managed by this struct,
+/// so it is always in sync with the wrapped engine.
+/// With the `serde` feature, the mapping is (de)serialized together with the engine.
+///
+/// The event log of the wrapped engine (see [`with_event_log`][Self::with_event_log]) only records [`PlayerHandle`]s, not keys.
+/// To reconstruct a [`KeyedRatingEngine`] from its events, the mapping from keys to handles needs to be persisted separately,
+/// e.g. from [`players`][Self::players], and passed to [`try_from_parts`][Self::try_from_parts]
+/// together with the engine returned by [`RatingEngine::replay`].
+///
+/// # Example
+///
+/// ```
+/// use instant_glicko_2::{GlickoSettings, PublicRating};
+/// use instant_glicko_2::engine::MatchResult;
+/// use instant_glicko_2::keyed::KeyedRatingEngine;
+///
+/// let settings = GlickoSettings::default();
+///
+/// let mut engine = KeyedRatingEngine::start_new(settings);
+///
+/// engine.register_player("alice", settings.start_rating());
+/// engine.register_player("bob", settings.start_rating());
+///
+/// // Alice wins against Bob
+/// engine.register_result("alice", "bob", &MatchResult::Win);
+///
+/// let alice_rating: PublicRating = engine.player_rating("alice").0;
+/// let bob_rating: PublicRating = engine.player_rating("bob").0;
+///
+/// assert!(alice_rating.rating() > bob_rating.rating());
+/// ```
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound(
+            serialize = "K: Serialize",
+            deserialize = "K: Deserialize<'de> + Clone, C: Clock + Default"
+        ),
+        try_from = "KeyedRatingEngineParts<K, C>"
+    )
+)]
+pub struct KeyedRatingEngine<K: Hash + Eq, C = SystemClock> {
+    engine: RatingEngine<C>,
+    players: HashMap<K, PlayerHandle>,
+    // The reverse of `players`, which is rebuilt from it when deserializing
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    keys: HashMap<PlayerHandle, K>,
+}
+
+// What is actually deserialized, so the mapping can be checked and the reverse mapping be rebuilt
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(
+    rename = "KeyedRatingEngine",
+    bound(deserialize = "K: Deserialize<'de>, C: Default")
+)]
+struct KeyedRatingEngineParts<K: Hash + Eq, C> {
+    engine: RatingEngine<C>,
+    players: HashMap<K, PlayerHandle>,
+}
+
+#[cfg(feature = "serde")]
+impl<K: Hash + Eq + Clone, C: Clock> TryFrom<KeyedRatingEngineParts<K, C>>
+    for KeyedRatingEngine<K, C>
+{
+    type Error = GlickoError;
+
+    fn try_from(parts: KeyedRatingEngineParts<K, C>) -> Result<Self, GlickoError> {
+        KeyedRatingEngine::try_from_parts(parts.engine, parts.players)
+    }
+}
+
+impl<K: Hash + Eq> KeyedRatingEngine<K> {
+    /// Creates a new [`KeyedRatingEngine`], starting the first rating period immediately.
+    #[must_use]
+    pub fn start_new(settings: GlickoSettings) -> Self {
+        Self::start_new_with_clock(SystemClock, settings)
+    }
+
+    /// Creates a new [`KeyedRatingEngine`], starting the first rating period at the specified point in time.
+    ///
+    /// This function is meant mostly for testability.
+    #[must_use]
+    pub fn start_new_at(start_time: SystemTime, settings: GlickoSettings) -> Self {
+        KeyedRatingEngine {
+            engine: RatingEngine::start_new_at(start_time, settings),
+            players: HashMap::new(),
+            keys: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, C: Clock> KeyedRatingEngine<K, C> {
+    /// Creates a new [`KeyedRatingEngine`] that gets the current time from `clock`, starting the first rating period immediately.
+    ///
+    /// See [`RatingEngine::start_new_with_clock`].
+    #[must_use]
+    pub fn start_new_with_clock(clock: C, settings: GlickoSettings) -> Self {
+        KeyedRatingEngine {
+            engine: RatingEngine::start_new_with_clock(clock, settings),
+            players: HashMap::new(),
+            keys: HashMap::new(),
+        }
+    }
+
+    /// Makes the wrapped engine remember the last `max_archived_periods` closed rating periods,
+    /// so results can be registered into them retroactively.
+    ///
+    /// See [`RatingEngine::with_archived_periods`].
+    #[must_use]
+    pub fn with_archived_periods(mut self, max_archived_periods: usize) -> Self {
+        self.engine.set_max_archived_periods(max_archived_periods);
+
+        self
+    }
+
+    /// Enables the rating history of the wrapped engine with the given settings.
+    ///
+    /// See [`RatingEngine::with_rating_history`].
+    #[must_use]
+    pub fn with_rating_history(mut self, history_settings: RatingHistorySettings) -> Self {
+        self.engine
+            .set_rating_history_settings(Some(history_settings));
+
+        self
+    }
+
+    /// Sets the strategy the wrapped engine uses for combining the ratings of a team's members in team results.
+    ///
+    /// See [`RatingEngine::with_team_rating_aggregation`].
+    #[must_use]
+    pub fn with_team_rating_aggregation(mut self, aggregation: TeamRatingAggregation) -> Self {
+        self.engine.set_team_rating_aggregation(aggregation);
+
+        self
+    }
+
+    /// Enables the event log of the wrapped engine.
+    ///
+    /// The log doesn't record the keys of players, see the [type-level documentation][KeyedRatingEngine] for how to replay it.
+    ///
+    /// See [`RatingEngine::with_event_log`].
+    #[must_use]
+    pub fn with_event_log(mut self) -> Self {
+        self.engine = self.engine.with_event_log();
+
+        self
+    }
+
+    /// Adds an observer to the wrapped engine.
+    ///
+    /// See [`RatingEngine::with_observer`].
+    #[must_use]
+    pub fn with_observer(mut self, observer: Arc<dyn RatingObserver>) -> Self {
+        self.engine.add_observer(observer);
+
+        self
+    }
+
+    /// The wrapped [`RatingEngine`].
+    #[must_use]
+    pub fn engine(&self) -> &RatingEngine<C> {
+        &self.engine
+    }
+
+    /// Creates a [`KeyedRatingEngine`] from a [`RatingEngine`] and a mapping from keys to [`PlayerHandle`]s of that engine,
+    /// e.g. one returned by [`into_parts`][Self::into_parts].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if a handle doesn't belong to `engine` or if two keys are mapped to the same handle.
+    /// See [`try_from_parts`][Self::try_from_parts] for a non-panicking version.
+    #[must_use]
+    pub fn from_parts(engine: RatingEngine<C>, players: HashMap<K, PlayerHandle>) -> Self
+    where
+        K: Clone,
+    {
+        Self::try_from_parts(engine, players).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates a [`KeyedRatingEngine`] from a [`RatingEngine`] and a mapping from keys to [`PlayerHandle`]s of that engine,
+    /// e.g. one returned by [`into_parts`][Self::into_parts].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayer`] if a handle doesn't belong to `engine`,
+    /// and [`GlickoError::DuplicatePlayerKey`] if two keys are mapped to the same handle.
+    pub fn try_from_parts(
+        engine: RatingEngine<C>,
+        players: HashMap<K, PlayerHandle>,
+    ) -> Result<Self, GlickoError>
+    where
+        K: Clone,
+    {
+        let mut keys = HashMap::with_capacity(players.len());
+
+        for (key, &handle) in &players {
+            if !engine.contains_player(handle) {
+                return Err(GlickoError::UnknownPlayer(handle));
+            }
+
+            if keys.insert(handle, key.clone()).is_some() {
+                return Err(GlickoError::DuplicatePlayerKey);
+            }
+        }
+
+        Ok(KeyedRatingEngine {
+            engine,
+            players,
+            keys,
+        })
+    }
+
+    /// Consumes this struct, returning the wrapped [`RatingEngine`] and the mapping from keys to [`PlayerHandle`]s.
+    #[must_use]
+    pub fn into_parts(self) -> (RatingEngine<C>, HashMap<K, PlayerHandle>) {
+        (self.engine, self.players)
+    }
+
+    /// The settings.
+    #[must_use]
+    pub fn settings(&self) -> GlickoSettings {
+        self.engine.settings()
+    }
+
+    /// Changes the settings of the wrapped engine.
+    ///
+    /// See [`RatingEngine::change_settings`].
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the old settings' convergence tolerance is unreasonably low.
+    pub fn change_settings(&mut self, settings: GlickoSettings) -> u32 {
+        self.engine.change_settings(settings)
+    }
+
+    /// Changes the settings of the wrapped engine at the given point in time.
+    ///
+    /// See [`RatingEngine::change_settings_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the old settings' convergence tolerance is unreasonably low.
+    pub fn change_settings_at(&mut self, settings: GlickoSettings, time: SystemTime) -> u32 {
+        self.engine.change_settings_at(settings, time)
+    }
+
+    /// Changes the settings of the wrapped engine at the given point in time.
+    ///
+    /// See [`RatingEngine::try_change_settings_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingEngine::try_change_settings_at`].
+    pub fn try_change_settings_at(
+        &mut self,
+        settings: GlickoSettings,
+        time: SystemTime,
+    ) -> Result<u32, GlickoError> {
+        self.engine.try_change_settings_at(settings, time)
+    }
+
+    /// Removes all events recorded by the wrapped engine so far and returns them.
+    ///
+    /// See [`RatingEngine::drain_event_log`].
+    pub fn drain_event_log(&mut self) -> Vec<EngineEvent> {
+        self.engine.drain_event_log()
+    }
+
+    /// The [`PlayerHandle`] of the player with the given key in the wrapped [`RatingEngine`],
+    /// or `None` if no player with that key is registered.
+    #[must_use]
+    pub fn handle<Q>(&self, key: &Q) -> Option<PlayerHandle>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.players.get(key).copied()
+    }
+
+    /// The key of the player with the given [`PlayerHandle`] in the wrapped [`RatingEngine`],
+    /// or `None` if the handle doesn't belong to a player registered with this struct.
+    ///
+    /// This is useful for results of the wrapped engine that identify players by handle,
+    /// e.g. the entries of a [`leaderboard`][RatingEngine::leaderboard].
+    #[must_use]
+    pub fn key(&self, player: PlayerHandle) -> Option<&K> {
+        self.keys.get(&player)
+    }
+
+    /// Returns `true` if a player with the given key is registered.
+    #[must_use]
+    pub fn contains_player<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.players.contains_key(key)
+    }
+
+    /// Returns an [`Iterator`] over the keys of all registered players.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.players.keys()
+    }
+
+    /// Returns an [`Iterator`] over the keys and [`PlayerHandle`]s of all registered players.
+    pub fn players(&self) -> impl Iterator<Item = (&K, PlayerHandle)> {
+        self.players.iter().map(|(key, &handle)| (key, handle))
+    }
+
+    /// The number of registered players.
+    #[must_use]
+    pub fn player_count(&self) -> usize {
+        self.players.len()
+    }
+
+    /// The rating of a player at the **start of** the last opened rating period.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no player with the given key is registered.
+    #[must_use]
+    pub fn last_rating_period_rating<Scale: RatingScale, Q>(&self, key: &Q) -> Rating<Scale>
+    where
+        Internal: ConvertToScale<Scale>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_last_rating_period_rating(key)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// The rating of a player at the **start of** the last opened rating period.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with the given key is registered.
+    pub fn try_last_rating_period_rating<Scale: RatingScale, Q>(
+        &self,
+        key: &Q,
+    ) -> Result<Rating<Scale>, GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.engine
+            .try_last_rating_period_rating(self.try_handle(key)?)
+    }
+
+    /// The recorded rating history of a player, sorted by time.
+    ///
+    /// See [`RatingEngine::rating_history`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no player with the given key is registered.
+    pub fn rating_history<Q>(&self, key: &Q) -> impl Iterator<Item = RatingHistoryEntry> + '_
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_rating_history(key)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// The recorded rating history of a player, sorted by time.
+    ///
+    /// See [`RatingEngine::rating_history`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with the given key is registered.
+    pub fn try_rating_history<Q>(
+        &self,
+        key: &Q,
+    ) -> Result<impl Iterator<Item = RatingHistoryEntry> + '_, GlickoError>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.engine.try_rating_history(self.try_handle(key)?)
+    }
+
+    /// The latest rating recorded for a player at or before `time`.
+    ///
+    /// See [`RatingEngine::rating_at_past_time`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no player with the given key is registered.
+    #[must_use]
+    pub fn rating_at_past_time<Scale: RatingScale, Q>(
+        &self,
+        key: &Q,
+        time: SystemTime,
+    ) -> Option<Rating<Scale>>
+    where
+        Public: ConvertToScale<Scale>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_rating_at_past_time(key, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// The latest rating recorded for a player at or before `time`.
+    ///
+    /// See [`RatingEngine::rating_at_past_time`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with the given key is registered.
+    pub fn try_rating_at_past_time<Scale: RatingScale, Q>(
+        &self,
+        key: &Q,
+        time: SystemTime,
+    ) -> Result<Option<Rating<Scale>>, GlickoError>
+    where
+        Public: ConvertToScale<Scale>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.engine
+            .try_rating_at_past_time(self.try_handle(key)?, time)
+    }
+
+    /// Registers a new player with the given key and rating at the start of the current rating period.
+    ///
+    /// See [`RatingEngine::register_player`].
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if a player with the given key is already registered.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn register_player<Scale>(&mut self, key: K, rating: Rating<Scale>) -> u32
+    where
+        Scale: RatingScale + ConvertToScale<Internal>,
+        K: Clone,
+    {
+        self.register_player_at(key, rating, self.engine.clock().now())
+    }
+
+    /// Registers a new player with the given key and rating at the start of what is the current rating period at the given time.
+    ///
+    /// See [`RatingEngine::register_player_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if a player with the given key is already registered.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn register_player_at<Scale>(
+        &mut self,
+        key: K,
+        rating: Rating<Scale>,
+        time: SystemTime,
+    ) -> u32
+    where
+        Scale: RatingScale + ConvertToScale<Internal>,
+        K: Clone,
+    {
+        self.try_register_player_at(key, rating, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Registers a new player with the given key and rating at the start of the current rating period.
+    ///
+    /// See [`RatingEngine::try_register_player`].
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::DuplicatePlayerKey`] if a player with the given key is already registered,
+    /// and [`GlickoError::NoConvergence`] if closing a rating period failed.
+    pub fn try_register_player<Scale>(
+        &mut self,
+        key: K,
+        rating: Rating<Scale>,
+    ) -> Result<u32, GlickoError>
+    where
+        Scale: RatingScale + ConvertToScale<Internal>,
+        K: Clone,
+    {
+        self.try_register_player_at(key, rating, self.engine.clock().now())
+    }
+
+    /// Registers a new player with the given key and rating at the start of what is the current rating period at the given time.
+    ///
+    /// See [`RatingEngine::try_register_player_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::DuplicatePlayerKey`] if a player with the given key is already registered,
+    /// and [`GlickoError::NoConvergence`] if closing a rating period failed.
+    pub fn try_register_player_at<Scale>(
+        &mut self,
+        key: K,
+        rating: Rating<Scale>,
+        time: SystemTime,
+    ) -> Result<u32, GlickoError>
+    where
+        Scale: RatingScale + ConvertToScale<Internal>,
+        K: Clone,
+    {
+        if self.players.contains_key(&key) {
+            return Err(GlickoError::DuplicatePlayerKey);
+        }
+
+        let (handle, closed_periods) = self.engine.try_register_player_at(rating, time)?;
+        self.keys.insert(handle, key.clone());
+        self.players.insert(key, handle);
+
+        Ok(closed_periods)
+    }
+
+    /// Removes the player with the given key.
+    ///
+    /// See [`RatingEngine::remove_player`].
+    ///
+    /// # Returns
+    ///
+    /// The removed player, or `None` if no player with the given key is registered.
+    pub fn remove_player<Scale: RatingScale, Q>(&mut self, key: &Q) -> Option<EnginePlayer<Scale>>
+    where
+        Internal: ConvertToScale<Scale>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let handle = self.players.remove(key)?;
+        self.keys.remove(&handle);
+
+        self.engine.remove_player(handle)
+    }
+
+    /// Registers a result between the players with the given keys in the current rating period.
+    ///
+    /// See [`RatingEngine::register_result`].
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract or amend the result while the current rating period is open
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no player with one of the given keys is registered,
+    /// or if `score` is not between `0.0` and `1.0`.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn register_result<S: Score, Q>(
+        &mut self,
+        player_1: &Q,
+        player_2: &Q,
+        score: &S,
+    ) -> (ResultId, u32)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.register_result_at(player_1, player_2, score, self.engine.clock().now())
+    }
+
+    /// Registers a result between the players with the given keys at the given time in the current rating period.
+    ///
+    /// See [`RatingEngine::register_result_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract or amend the result while the current rating period is open
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no player with one of the given keys is registered,
+    /// or if `score` is not between `0.0` and `1.0`.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn register_result_at<S: Score, Q>(
+        &mut self,
+        player_1: &Q,
+        player_2: &Q,
+        score: &S,
+        time: SystemTime,
+    ) -> (ResultId, u32)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_register_result_at(player_1, player_2, score, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Registers a result between the players with the given keys in the current rating period.
+    ///
+    /// See [`RatingEngine::try_register_result`].
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract or amend the result while the current rating period is open
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with one of the given keys is registered.
+    /// See [`RatingEngine::try_register_result`] for the other errors.
+    pub fn try_register_result<S: Score, Q>(
+        &mut self,
+        player_1: &Q,
+        player_2: &Q,
+        score: &S,
+    ) -> Result<(ResultId, u32), GlickoError>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_register_result_at(player_1, player_2, score, self.engine.clock().now())
+    }
+
+    /// Registers a result between the players with the given keys at the given time in the current rating period.
+    ///
+    /// See [`RatingEngine::try_register_result_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract or amend the result while the current rating period is open
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with one of the given keys is registered.
+    /// See [`RatingEngine::try_register_result_at`] for the other errors.
+    pub fn try_register_result_at<S: Score, Q>(
+        &mut self,
+        player_1: &Q,
+        player_2: &Q,
+        score: &S,
+        time: SystemTime,
+    ) -> Result<(ResultId, u32), GlickoError>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let player_1 = self.try_handle(player_1)?;
+        let player_2 = self.try_handle(player_2)?;
+
+        self.engine
+            .try_register_result_at(player_1, player_2, score, time)
+    }
+
+    /// Registers a result between two teams of players with the given keys in the current rating period.
+    ///
+    /// See [`RatingEngine::register_team_result`].
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract or amend the result while the current rating period is open
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no player with one of the given keys is registered,
+    /// if a team is empty, if a player appears more than once,
+    /// or if `score` is not between `0.0` and `1.0`.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn register_team_result<S: Score, Q>(
+        &mut self,
+        team_1: &[&Q],
+        team_2: &[&Q],
+        score: &S,
+    ) -> (ResultId, u32)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.register_team_result_at(team_1, team_2, score, self.engine.clock().now())
+    }
+
+    /// Registers a result between two teams of players with the given keys at the given time in the current rating period.
+    ///
+    /// See [`RatingEngine::register_team_result_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract or amend the result while the current rating period is open
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no player with one of the given keys is registered,
+    /// if a team is empty, if a player appears more than once,
+    /// or if `score` is not between `0.0` and `1.0`.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn register_team_result_at<S: Score, Q>(
+        &mut self,
+        team_1: &[&Q],
+        team_2: &[&Q],
+        score: &S,
+        time: SystemTime,
+    ) -> (ResultId, u32)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_register_team_result_at(team_1, team_2, score, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Registers a result between two teams of players with the given keys in the current rating period.
+    ///
+    /// See [`RatingEngine::try_register_team_result`].
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract or amend the result while the current rating period is open
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with one of the given keys is registered.
+    /// See [`RatingEngine::try_register_team_result`] for the other errors.
+    pub fn try_register_team_result<S: Score, Q>(
+        &mut self,
+        team_1: &[&Q],
+        team_2: &[&Q],
+        score: &S,
+    ) -> Result<(ResultId, u32), GlickoError>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_register_team_result_at(team_1, team_2, score, self.engine.clock().now())
+    }
+
+    /// Registers a result between two teams of players with the given keys at the given time in the current rating period.
+    ///
+    /// See [`RatingEngine::try_register_team_result_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract or amend the result while the current rating period is open
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with one of the given keys is registered.
+    /// See [`RatingEngine::try_register_team_result_at`] for the other errors.
+    pub fn try_register_team_result_at<S: Score, Q>(
+        &mut self,
+        team_1: &[&Q],
+        team_2: &[&Q],
+        score: &S,
+        time: SystemTime,
+    ) -> Result<(ResultId, u32), GlickoError>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let team_1 = self.try_handles(team_1)?;
+        let team_2 = self.try_handles(team_2)?;
+
+        self.engine
+            .try_register_team_result_at(&team_1, &team_2, score, time)
+    }
+
+    /// Registers the result of a match between the players with the given keys in the current rating period.
+    ///
+    /// See [`RatingEngine::register_ranked_result`].
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract the result while the current rating period is open
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no player with one of the given keys is registered,
+    /// if there are fewer than two players, if a player appears more than once,
+    /// or if the number of placements doesn't match the number of players.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn register_ranked_result<Q>(
+        &mut self,
+        players: &[&Q],
+        placements: &[u32],
+    ) -> (ResultId, u32)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.register_ranked_result_at(players, placements, self.engine.clock().now())
+    }
+
+    /// Registers the result of a match between the players with the given keys at the given time in the current rating period.
+    ///
+    /// See [`RatingEngine::register_ranked_result_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract the result while the current rating period is open
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no player with one of the given keys is registered,
+    /// if there are fewer than two players, if a player appears more than once,
+    /// or if the number of placements doesn't match the number of players.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn register_ranked_result_at<Q>(
+        &mut self,
+        players: &[&Q],
+        placements: &[u32],
+        time: SystemTime,
+    ) -> (ResultId, u32)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_register_ranked_result_at(players, placements, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Registers the result of a match between the players with the given keys in the current rating period.
+    ///
+    /// See [`RatingEngine::try_register_ranked_result`].
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract the result while the current rating period is open
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with one of the given keys is registered.
+    /// See [`RatingEngine::try_register_ranked_result`] for the other errors.
+    pub fn try_register_ranked_result<Q>(
+        &mut self,
+        players: &[&Q],
+        placements: &[u32],
+    ) -> Result<(ResultId, u32), GlickoError>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_register_ranked_result_at(players, placements, self.engine.clock().now())
+    }
+
+    /// Registers the result of a match between the players with the given keys at the given time in the current rating period.
+    ///
+    /// See [`RatingEngine::try_register_ranked_result_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to retract the result while the current rating period is open
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with one of the given keys is registered.
+    /// See [`RatingEngine::try_register_ranked_result_at`] for the other errors.
+    pub fn try_register_ranked_result_at<Q>(
+        &mut self,
+        players: &[&Q],
+        placements: &[u32],
+        time: SystemTime,
+    ) -> Result<(ResultId, u32), GlickoError>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let players = self.try_handles(players)?;
+
+        self.engine
+            .try_register_ranked_result_at(&players, placements, time)
+    }
+
+    /// Retracts a result that was registered in the current rating period, as if it was never registered.
+    ///
+    /// See [`RatingEngine::retract_result`].
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// See [`RatingEngine::retract_result`].
+    pub fn retract_result(&mut self, result: ResultId) -> u32 {
+        self.engine.retract_result(result)
+    }
+
+    /// Retracts a result that was registered in the current rating period, as if it was never registered.
+    ///
+    /// See [`RatingEngine::try_retract_result`].
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingEngine::try_retract_result`].
+    pub fn try_retract_result(&mut self, result: ResultId) -> Result<u32, GlickoError> {
+        self.engine.try_retract_result(result)
+    }
+
+    /// Retracts a result that was registered in what is the current rating period at the given time, as if it was never registered.
+    ///
+    /// See [`RatingEngine::retract_result_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// See [`RatingEngine::retract_result_at`].
+    pub fn retract_result_at(&mut self, result: ResultId, time: SystemTime) -> u32 {
+        self.engine.retract_result_at(result, time)
+    }
+
+    /// Retracts a result that was registered in what is the current rating period at the given time, as if it was never registered.
+    ///
+    /// See [`RatingEngine::try_retract_result_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingEngine::try_retract_result_at`].
+    pub fn try_retract_result_at(
+        &mut self,
+        result: ResultId,
+        time: SystemTime,
+    ) -> Result<u32, GlickoError> {
+        self.engine.try_retract_result_at(result, time)
+    }
+
+    /// Replaces the score of a result that was registered in the current rating period.
+    ///
+    /// See [`RatingEngine::amend_result`].
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// See [`RatingEngine::amend_result`].
+    pub fn amend_result<S: Score>(&mut self, result: ResultId, new_score: &S) -> u32 {
+        self.engine.amend_result(result, new_score)
+    }
+
+    /// Replaces the score of a result that was registered in the current rating period.
+    ///
+    /// See [`RatingEngine::try_amend_result`].
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingEngine::try_amend_result`].
+    pub fn try_amend_result<S: Score>(
+        &mut self,
+        result: ResultId,
+        new_score: &S,
+    ) -> Result<u32, GlickoError> {
+        self.engine.try_amend_result(result, new_score)
+    }
+
+    /// Replaces the score of a result that was registered in what is the current rating period at the given time.
+    ///
+    /// See [`RatingEngine::amend_result_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// See [`RatingEngine::amend_result_at`].
+    pub fn amend_result_at<S: Score>(
+        &mut self,
+        result: ResultId,
+        new_score: &S,
+        time: SystemTime,
+    ) -> u32 {
+        self.engine.amend_result_at(result, new_score, time)
+    }
+
+    /// Replaces the score of a result that was registered in what is the current rating period at the given time.
+    ///
+    /// See [`RatingEngine::try_amend_result_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// The number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingEngine::try_amend_result_at`].
+    pub fn try_amend_result_at<S: Score>(
+        &mut self,
+        result: ResultId,
+        new_score: &S,
+        time: SystemTime,
+    ) -> Result<u32, GlickoError> {
+        self.engine.try_amend_result_at(result, new_score, time)
+    }
+
+    /// Registers a result that was played at `game_time`, which might be in an already closed rating period.
+    ///
+    /// See [`RatingEngine::register_result_retroactive`].
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the [`ResultId`] of the result if it was registered in the current rating period,
+    /// the handles of all players whose rating changed because of the recalculation,
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with one of the given keys is registered.
+    /// See [`RatingEngine::register_result_retroactive`] for the other errors.
+    pub fn register_result_retroactive<S: Score, Q>(
+        &mut self,
+        player_1: &Q,
+        player_2: &Q,
+        score: &S,
+        game_time: SystemTime,
+    ) -> Result<(Option<ResultId>, Vec<PlayerHandle>, u32), GlickoError>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.register_result_retroactive_at(
+            player_1,
+            player_2,
+            score,
+            game_time,
+            self.engine.clock().now(),
+        )
+    }
+
+    /// Registers a result that was played at `game_time`, which might be in a rating period that is closed at `time`.
+    ///
+    /// See [`RatingEngine::register_result_retroactive_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the [`ResultId`] of the result if it was registered in the current rating period,
+    /// the handles of all players whose rating changed because of the recalculation,
+    /// and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with one of the given keys is registered.
+    /// See [`RatingEngine::register_result_retroactive_at`] for the other errors.
+    pub fn register_result_retroactive_at<S: Score, Q>(
+        &mut self,
+        player_1: &Q,
+        player_2: &Q,
+        score: &S,
+        game_time: SystemTime,
+        time: SystemTime,
+    ) -> Result<(Option<ResultId>, Vec<PlayerHandle>, u32), GlickoError>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let player_1 = self.try_handle(player_1)?;
+        let player_2 = self.try_handle(player_2)?;
+
+        self.engine
+            .register_result_retroactive_at(player_1, player_2, score, game_time, time)
+    }
+
+    /// Calculates the rating of the player with the given key at this point in time.
+    ///
+    /// See [`RatingEngine::player_rating`].
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the player's current rating and the number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no player with the given key is registered.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    #[must_use]
+    pub fn player_rating<Scale: RatingScale, Q>(&mut self, key: &Q) -> (Rating<Scale>, u32)
+    where
+        Internal: ConvertToScale<Scale>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.player_rating_at(key, self.engine.clock().now())
+    }
+
+    /// Calculates the rating of the player with the given key at a given point in time.
+    ///
+    /// See [`RatingEngine::player_rating_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the player's current rating and the number of rating periods that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no player with the given key is registered.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    #[must_use]
+    pub fn player_rating_at<Scale: RatingScale, Q>(
+        &mut self,
+        key: &Q,
+        time: SystemTime,
+    ) -> (Rating<Scale>, u32)
+    where
+        Internal: ConvertToScale<Scale>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_player_rating_at(key, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Calculates the rating of the player with the given key at this point in time.
+    ///
+    /// See [`RatingEngine::try_player_rating`].
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the player's current rating and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with the given key is registered.
+    /// See [`RatingEngine::try_player_rating`] for the other errors.
+    pub fn try_player_rating<Scale: RatingScale, Q>(
+        &mut self,
+        key: &Q,
+    ) -> Result<(Rating<Scale>, u32), GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_player_rating_at(key, self.engine.clock().now())
+    }
+
+    /// Calculates the rating of the player with the given key at a given point in time.
+    ///
+    /// See [`RatingEngine::try_player_rating_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the player's current rating and the number of rating periods that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with the given key is registered.
+    /// See [`RatingEngine::try_player_rating_at`] for the other errors.
+    pub fn try_player_rating_at<Scale: RatingScale, Q>(
+        &mut self,
+        key: &Q,
+        time: SystemTime,
+    ) -> Result<(Rating<Scale>, u32), GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let handle = self.try_handle(key)?;
+
+        self.engine.try_player_rating_at(handle, time)
+    }
+
+    /// Calculates the rating of the player with the given key at this point in time without changing this engine.
+    ///
+    /// See [`RatingEngine::peek_player_rating`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no player with the given key is registered.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    #[must_use]
+    pub fn peek_player_rating<Scale: RatingScale, Q>(&self, key: &Q) -> Rating<Scale>
+    where
+        Internal: ConvertToScale<Scale>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.peek_player_rating_at(key, self.engine.clock().now())
+    }
+
+    /// Calculates the rating of the player with the given key at a given point in time without changing this engine.
+    ///
+    /// See [`RatingEngine::peek_player_rating_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no player with the given key is registered.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    #[must_use]
+    pub fn peek_player_rating_at<Scale: RatingScale, Q>(
+        &self,
+        key: &Q,
+        time: SystemTime,
+    ) -> Rating<Scale>
+    where
+        Internal: ConvertToScale<Scale>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_peek_player_rating_at(key, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Calculates the rating of the player with the given key at this point in time without changing this engine.
+    ///
+    /// See [`RatingEngine::try_peek_player_rating`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with the given key is registered.
+    /// See [`RatingEngine::try_peek_player_rating`] for the other errors.
+    pub fn try_peek_player_rating<Scale: RatingScale, Q>(
+        &self,
+        key: &Q,
+    ) -> Result<Rating<Scale>, GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_peek_player_rating_at(key, self.engine.clock().now())
+    }
+
+    /// Calculates the rating of the player with the given key at a given point in time without changing this engine.
+    ///
+    /// See [`RatingEngine::try_peek_player_rating_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with the given key is registered.
+    /// See [`RatingEngine::try_peek_player_rating_at`] for the other errors.
+    pub fn try_peek_player_rating_at<Scale: RatingScale, Q>(
+        &self,
+        key: &Q,
+        time: SystemTime,
+    ) -> Result<Rating<Scale>, GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.engine
+            .try_peek_player_rating_at(self.try_handle(key)?, time)
+    }
+
+    /// Calculates the ratings the players with the given keys would have if they played a result right now,
+    /// for every [`MatchResult`][crate::engine::MatchResult], without changing this engine.
+    ///
+    /// See [`RatingEngine::preview_result`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no player with one of the given keys is registered.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    #[must_use]
+    pub fn preview_result<Scale: RatingScale, Q>(
+        &self,
+        player_1: &Q,
+        player_2: &Q,
+    ) -> ResultPreview<Scale>
+    where
+        Internal: ConvertToScale<Scale>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.preview_result_at(player_1, player_2, self.engine.clock().now())
+    }
+
+    /// Calculates the ratings the players with the given keys would have if they played a result at the given time,
+    /// for every [`MatchResult`][crate::engine::MatchResult], without changing this engine.
+    ///
+    /// See [`RatingEngine::preview_result_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no player with one of the given keys is registered.
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    #[must_use]
+    pub fn preview_result_at<Scale: RatingScale, Q>(
+        &self,
+        player_1: &Q,
+        player_2: &Q,
+        time: SystemTime,
+    ) -> ResultPreview<Scale>
+    where
+        Internal: ConvertToScale<Scale>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_preview_result_at(player_1, player_2, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Calculates the ratings the players with the given keys would have if they played a result right now,
+    /// for every [`MatchResult`][crate::engine::MatchResult], without changing this engine.
+    ///
+    /// See [`RatingEngine::try_preview_result`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with one of the given keys is registered.
+    /// See [`RatingEngine::try_preview_result`] for the other errors.
+    pub fn try_preview_result<Scale: RatingScale, Q>(
+        &self,
+        player_1: &Q,
+        player_2: &Q,
+    ) -> Result<ResultPreview<Scale>, GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_preview_result_at(player_1, player_2, self.engine.clock().now())
+    }
+
+    /// Calculates the ratings the players with the given keys would have if they played a result at the given time,
+    /// for every [`MatchResult`][crate::engine::MatchResult], without changing this engine.
+    ///
+    /// See [`RatingEngine::try_preview_result_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with one of the given keys is registered.
+    /// See [`RatingEngine::try_preview_result_at`] for the other errors.
+    pub fn try_preview_result_at<Scale: RatingScale, Q>(
+        &self,
+        player_1: &Q,
+        player_2: &Q,
+        time: SystemTime,
+    ) -> Result<ResultPreview<Scale>, GlickoError>
+    where
+        Internal: ConvertToScale<Scale>,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.engine.try_preview_result_at(
+            self.try_handle(player_1)?,
+            self.try_handle(player_2)?,
+            time,
+        )
+    }
+
+    /// The individual [`Public`] rating value the player with the given key can't fall below, or `None` if they don't have one.
+    ///
+    /// See [`RatingEngine::player_rating_floor`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no player with the given key is registered.
+    #[must_use]
+    pub fn player_rating_floor<Q>(&self, key: &Q) -> Option<f64>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_player_rating_floor(key)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// The individual [`Public`] rating value the player with the given key can't fall below, or `None` if they don't have one.
+    ///
+    /// See [`RatingEngine::try_player_rating_floor`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with the given key is registered.
+    pub fn try_player_rating_floor<Q>(&self, key: &Q) -> Result<Option<f64>, GlickoError>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.engine.try_player_rating_floor(self.try_handle(key)?)
+    }
+
+    /// Sets the individual [`Public`] rating value the player with the given key can't fall below,
+    /// or removes it if `rating_floor` is `None`.
+    ///
+    /// See [`RatingEngine::set_player_rating_floor`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no player with the given key is registered or if `rating_floor` is not finite.
+    pub fn set_player_rating_floor<Q>(&mut self, key: &Q, rating_floor: Option<f64>)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_set_player_rating_floor(key, rating_floor)
+            .unwrap_or_else(|err| panic!("{err}"));
+    }
+
+    /// Sets the individual [`Public`] rating value the player with the given key can't fall below,
+    /// or removes it if `rating_floor` is `None`.
+    ///
+    /// See [`RatingEngine::try_set_player_rating_floor`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPlayerKey`] if no player with the given key is registered,
+    /// and [`GlickoError::InvalidRatingLimit`] if `rating_floor` is not finite.
+    pub fn try_set_player_rating_floor<Q>(
+        &mut self,
+        key: &Q,
+        rating_floor: Option<f64>,
+    ) -> Result<(), GlickoError>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.engine
+            .try_set_player_rating_floor(self.try_handle(key)?, rating_floor)
+    }
+
+    /// Closes all open rating periods that have elapsed by now.
+    /// This doesn't need to be called manually.
+    ///
+    /// See [`RatingEngine::maybe_close_rating_periods`].
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn maybe_close_rating_periods(&mut self) -> (f64, u32) {
+        self.engine.maybe_close_rating_periods()
+    }
+
+    /// Closes all open rating periods that have elapsed by a given point in time.
+    /// This doesn't need to be called manually.
+    ///
+    /// See [`RatingEngine::maybe_close_rating_periods_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn maybe_close_rating_periods_at(&mut self, time: SystemTime) -> (f64, u32) {
+        self.engine.maybe_close_rating_periods_at(time)
+    }
+
+    /// Closes all open rating periods that have elapsed by now,
+    /// and reports how the ratings of all players changed.
+    ///
+    /// See [`RatingEngine::maybe_close_rating_periods_with_reports`].
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn maybe_close_rating_periods_with_reports(&mut self) -> (f64, Vec<PeriodCloseReport>) {
+        self.engine.maybe_close_rating_periods_with_reports()
+    }
+
+    /// Closes all open rating periods that have elapsed by a given point in time,
+    /// and reports how the ratings of all players changed.
+    ///
+    /// See [`RatingEngine::maybe_close_rating_periods_with_reports_at`].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Panics
+    ///
+    /// This function might panic if the set settings' convergence tolerance is unreasonably low.
+    pub fn maybe_close_rating_periods_with_reports_at(
+        &mut self,
+        time: SystemTime,
+    ) -> (f64, Vec<PeriodCloseReport>) {
+        self.engine.maybe_close_rating_periods_with_reports_at(time)
+    }
+
+    fn try_handle<Q>(&self, key: &Q) -> Result<PlayerHandle, GlickoError>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.handle(key).ok_or(GlickoError::UnknownPlayerKey)
+    }
+
+    fn try_handles<Q>(&self, keys: &[&Q]) -> Result<Vec<PlayerHandle>, GlickoError>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        keys.iter().map(|key| self.try_handle(key)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::time::{Duration, SystemTime};
+
+    use super::KeyedRatingEngine;
+    use crate::engine::{MatchResult, RatingEngine};
+    use crate::{GlickoError, GlickoSettings, Public, PublicRating};
+
+    #[test]
+    fn test_keyed_engine() {
+        let settings = GlickoSettings::default();
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = KeyedRatingEngine::start_new_at(start_time, settings);
+
+        engine.register_player_at(String::from("alice"), settings.start_rating(), start_time);
+        engine.register_player_at(String::from("bob"), settings.start_rating(), start_time);
+
+        assert_eq!(
+            engine.try_register_player_at(
+                String::from("alice"),
+                settings.start_rating(),
+                start_time
+            ),
+            Err(GlickoError::DuplicatePlayerKey),
+        );
+        assert_eq!(engine.player_count(), 2);
+
+        engine.register_result_at("alice", "bob", &MatchResult::Win, start_time);
+
+        let alice_rating: PublicRating = engine.player_rating_at("alice", start_time).0;
+        let bob_rating: PublicRating = engine.player_rating_at("bob", start_time).0;
+        assert!(alice_rating.rating() > bob_rating.rating());
+
+        // The keyed engine produces the same ratings as the wrapped engine
+        let alice = engine.handle("alice").unwrap();
+        assert_eq!(engine.key(alice).map(String::as_str), Some("alice"));
+        let mut inner = engine.clone().into_parts().0;
+        let inner_rating: PublicRating = inner.player_rating_at(alice, start_time).0;
+        assert_eq!(alice_rating, inner_rating);
+
+        let bob = engine.handle("bob").unwrap();
+        assert!(engine.remove_player::<Public, _>("bob").is_some());
+        assert_eq!(engine.key(bob), None);
+        assert!(!engine.contains_player("bob"));
+        assert_eq!(
+            engine.try_register_result_at("alice", "bob", &MatchResult::Win, start_time),
+            Err(GlickoError::UnknownPlayerKey),
+        );
+    }
+
+    #[test]
+    fn test_keyed_engine_replay() {
+        let settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(10));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = KeyedRatingEngine::start_new_at(start_time, settings).with_event_log();
+
+        engine.register_player_at("alice", settings.start_rating(), start_time);
+        engine.register_player_at("bob", settings.start_rating(), start_time);
+        engine.register_result_at("alice", "bob", &MatchResult::Win, start_time);
+
+        let new_settings = settings.with_volatility_change(0.5);
+        assert_eq!(
+            engine.change_settings_at(new_settings, start_time + Duration::from_secs(15)),
+            1,
+        );
+        assert_eq!(engine.settings(), new_settings);
+
+        engine.register_result_at(
+            "bob",
+            "alice",
+            &MatchResult::Draw,
+            start_time + Duration::from_secs(16),
+        );
+
+        // The log only knows handles, so the keys are persisted separately
+        let events = engine.drain_event_log();
+        let keys: HashMap<_, _> = engine.players().map(|(&key, handle)| (key, handle)).collect();
+
+        let replayed = RatingEngine::replay(settings, &events).unwrap();
+        let mut replayed = KeyedRatingEngine::from_parts(replayed, keys);
+
+        let time = start_time + Duration::from_secs(25);
+        for key in ["alice", "bob"] {
+            let rating: PublicRating = engine.player_rating_at(key, time).0;
+            let replayed_rating: PublicRating = replayed.player_rating_at(key, time).0;
+            assert_eq!(rating, replayed_rating);
+        }
+
+        let (mut inner, keys) = engine.into_parts();
+        let alice = keys["alice"];
+        let bob = keys["bob"];
+        assert_eq!(
+            KeyedRatingEngine::try_from_parts(
+                inner.clone(),
+                HashMap::from([("alice", alice), ("carol", alice)]),
+            ),
+            Err(GlickoError::DuplicatePlayerKey),
+        );
+
+        assert!(inner.remove_player::<Public>(bob).is_some());
+        assert_eq!(
+            KeyedRatingEngine::try_from_parts(inner, keys),
+            Err(GlickoError::UnknownPlayer(bob)),
+        );
+    }
+
+    #[test]
+    fn test_keyed_engine_rating_floor() {
+        let settings = GlickoSettings::default();
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = KeyedRatingEngine::start_new_at(start_time, settings);
+
+        engine.register_player_at("alice", settings.start_rating(), start_time);
+        engine.register_player_at("bob", settings.start_rating(), start_time);
+
+        engine.set_player_rating_floor("alice", Some(1490.0));
+        assert_eq!(engine.player_rating_floor("alice"), Some(1490.0));
+        assert_eq!(engine.player_rating_floor("bob"), None);
+
+        engine.register_result_at("alice", "bob", &MatchResult::Loss, start_time);
+
+        let alice_rating: PublicRating = engine.peek_player_rating_at("alice", start_time);
+        let bob_rating: PublicRating = engine.peek_player_rating_at("bob", start_time);
+        assert!((alice_rating.rating() - 1490.0).abs() < 0.000_001);
+        assert!(bob_rating.rating() > settings.start_rating().rating());
+
+        assert_eq!(
+            engine.try_set_player_rating_floor("carol", Some(1490.0)),
+            Err(GlickoError::UnknownPlayerKey),
+        );
+        assert_eq!(
+            engine.try_player_rating_floor("carol"),
+            Err(GlickoError::UnknownPlayerKey),
+        );
+        assert_eq!(
+            engine.try_set_player_rating_floor("bob", Some(f64::INFINITY)),
+            Err(GlickoError::InvalidRatingLimit(f64::INFINITY)),
+        );
+        assert_eq!(engine.player_rating_floor("bob"), None);
+
+        engine.set_player_rating_floor("alice", None);
+        assert_eq!(engine.player_rating_floor("alice"), None);
+    }
+}
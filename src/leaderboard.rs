@@ -0,0 +1,356 @@
+//! This mod defines the [`Leaderboard`] struct which ranks the players of a [`RatingEngine`][crate::engine::RatingEngine].
+//!
+//! See [`RatingEngine::leaderboard`][crate::engine::RatingEngine::leaderboard].
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+use crate::engine::PlayerHandle;
+use crate::PublicRating;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The value players are ranked by in a [`Leaderboard`]. Higher values rank higher.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RankingKey {
+    /// The [`Public`][crate::Public] rating value.
+    Rating,
+    /// The [`Public`][crate::Public] rating value minus the given multiple of the [`Public`][crate::Public] rating deviation.
+    ///
+    /// This ranks players lower the less certain their rating is.
+    /// A common choice is `2.0`, which is a lower bound of the player's skill with a confidence of about 95%.
+    ConservativeRating(f64),
+}
+
+impl RankingKey {
+    /// Calculates the value of this key for the given rating.
+    #[must_use]
+    pub fn value(self, rating: PublicRating) -> f64 {
+        match self {
+            RankingKey::Rating => rating.rating(),
+            RankingKey::ConservativeRating(deviations) => {
+                rating.rating() - deviations * rating.deviation()
+            }
+        }
+    }
+}
+
+impl Default for RankingKey {
+    fn default() -> Self {
+        RankingKey::Rating
+    }
+}
+
+/// Settings controlling how a [`Leaderboard`] ranks players and which players are eligible for it.
+///
+/// By default, all players are ranked by their [`Public`][crate::Public] rating value.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[allow(clippy::module_name_repetitions)]
+pub struct LeaderboardSettings {
+    ranking_key: RankingKey,
+    max_deviation: Option<f64>,
+    min_games: u64,
+    active_within: Option<Duration>,
+}
+
+impl LeaderboardSettings {
+    /// Creates [`LeaderboardSettings`] that rank all players by their [`Public`][crate::Public] rating value.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value players are ranked by.
+    #[must_use]
+    pub fn with_ranking_key(mut self, ranking_key: RankingKey) -> Self {
+        self.ranking_key = ranking_key;
+
+        self
+    }
+
+    /// Makes players whose current [`Public`][crate::Public] rating deviation is higher than `max_deviation` ineligible.
+    #[must_use]
+    pub fn with_max_deviation(mut self, max_deviation: f64) -> Self {
+        self.max_deviation = Some(max_deviation);
+
+        self
+    }
+
+    /// Makes players who played fewer than `min_games` games ineligible.
+    #[must_use]
+    pub fn with_min_games(mut self, min_games: u64) -> Self {
+        self.min_games = min_games;
+
+        self
+    }
+
+    /// Makes players who didn't play a game within `active_within` before the time of the leaderboard ineligible.
+    #[must_use]
+    pub fn with_active_within(mut self, active_within: Duration) -> Self {
+        self.active_within = Some(active_within);
+
+        self
+    }
+
+    /// The value players are ranked by.
+    #[must_use]
+    pub fn ranking_key(&self) -> RankingKey {
+        self.ranking_key
+    }
+
+    /// The maximum [`Public`][crate::Public] rating deviation of eligible players, or `None` if there is no limit.
+    #[must_use]
+    pub fn max_deviation(&self) -> Option<f64> {
+        self.max_deviation
+    }
+
+    /// The minimum number of games eligible players have played.
+    #[must_use]
+    pub fn min_games(&self) -> u64 {
+        self.min_games
+    }
+
+    /// The duration before the time of the leaderboard eligible players must have played a game in,
+    /// or `None` if inactive players are eligible.
+    #[must_use]
+    pub fn active_within(&self) -> Option<Duration> {
+        self.active_within
+    }
+
+    /// Whether a player with the given properties is eligible for a leaderboard at `time`.
+    pub(crate) fn is_eligible(
+        &self,
+        rating: PublicRating,
+        games_played: u64,
+        last_played: Option<SystemTime>,
+        time: SystemTime,
+    ) -> bool {
+        if matches!(self.max_deviation, Some(max_deviation) if rating.deviation() > max_deviation) {
+            return false;
+        }
+
+        if games_played < self.min_games {
+            return false;
+        }
+
+        if let Some(active_within) = self.active_within {
+            let active = last_played.map_or(false, |last_played| {
+                // If last_played is later than time, the player is active
+                time.duration_since(last_played)
+                    .map_or(true, |inactive_duration| inactive_duration <= active_within)
+            });
+
+            if !active {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// An eligible player in a [`Leaderboard`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[allow(clippy::module_name_repetitions)]
+pub struct LeaderboardEntry {
+    player: PlayerHandle,
+    rating: PublicRating,
+    key: f64,
+}
+
+impl LeaderboardEntry {
+    /// The player.
+    #[must_use]
+    pub fn player(&self) -> PlayerHandle {
+        self.player
+    }
+
+    /// The rating of the player at the time of the leaderboard.
+    #[must_use]
+    pub fn rating(&self) -> PublicRating {
+        self.rating
+    }
+
+    /// The value of the [`RankingKey`] the player was ranked by.
+    #[must_use]
+    pub fn key(&self) -> f64 {
+        self.key
+    }
+}
+
+/// A snapshot of the eligible players of a [`RatingEngine`][crate::engine::RatingEngine], ranked by a [`RankingKey`].
+///
+/// The players are sorted once when the leaderboard is created,
+/// so looking up the top players or the rank of a player is cheap.
+/// The leaderboard doesn't change when the engine changes, so it needs to be recreated to reflect new results.
+///
+/// Ranks start at `1`. Players with the same key are ordered by their [`PlayerHandle`], so every rank is unique.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Leaderboard {
+    time: SystemTime,
+    entries: Vec<LeaderboardEntry>,
+    ranks: HashMap<PlayerHandle, usize>,
+}
+
+impl Leaderboard {
+    /// Creates a leaderboard of the given players with their ratings at `time`.
+    /// The players must already be filtered for eligibility.
+    pub(crate) fn new(
+        time: SystemTime,
+        players: impl IntoIterator<Item = (PlayerHandle, PublicRating)>,
+        ranking_key: RankingKey,
+    ) -> Self {
+        let mut entries: Vec<_> = players
+            .into_iter()
+            .map(|(player, rating)| LeaderboardEntry {
+                player,
+                rating,
+                key: ranking_key.value(rating),
+            })
+            .collect();
+
+        entries.sort_by(|a, b| {
+            b.key
+                .partial_cmp(&a.key)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.player.cmp(&b.player))
+        });
+
+        let ranks = entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (entry.player, index + 1))
+            .collect();
+
+        Leaderboard {
+            time,
+            entries,
+            ranks,
+        }
+    }
+
+    /// The point in time the ratings of this leaderboard were calculated for.
+    #[must_use]
+    pub fn time(&self) -> SystemTime {
+        self.time
+    }
+
+    /// All entries, from the highest rank to the lowest.
+    #[must_use]
+    pub fn entries(&self) -> &[LeaderboardEntry] {
+        &self.entries
+    }
+
+    /// The entries of the top `n` players, from the highest rank to the lowest.
+    /// If there are fewer than `n` eligible players, all entries are returned.
+    #[must_use]
+    pub fn top(&self, n: usize) -> &[LeaderboardEntry] {
+        &self.entries[..n.min(self.entries.len())]
+    }
+
+    /// The rank of a player, starting at `1`, or `None` if the player is not eligible for this leaderboard.
+    #[must_use]
+    pub fn rank(&self, player: PlayerHandle) -> Option<usize> {
+        self.ranks.get(&player).copied()
+    }
+
+    /// The entry of a player, or `None` if the player is not eligible for this leaderboard.
+    #[must_use]
+    pub fn entry(&self, player: PlayerHandle) -> Option<&LeaderboardEntry> {
+        self.rank(player).map(|rank| &self.entries[rank - 1])
+    }
+
+    /// The number of eligible players.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no player is eligible for this leaderboard.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, SystemTime};
+
+    use super::{LeaderboardEntry, LeaderboardSettings, RankingKey};
+    use crate::engine::{MatchResult, RatingEngine};
+    use crate::{GlickoSettings, PublicRating};
+
+    #[test]
+    fn test_leaderboard() {
+        let settings = GlickoSettings::default();
+
+        let start_time = SystemTime::UNIX_EPOCH;
+        let time = start_time + Duration::from_secs(60 * 60);
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings);
+
+        let veteran = engine
+            .register_player_at(PublicRating::new(1800.0, 50.0, 0.06), start_time)
+            .0;
+        let newcomer = engine
+            .register_player_at(PublicRating::new(1850.0, 300.0, 0.06), start_time)
+            .0;
+        let regular = engine
+            .register_player_at(PublicRating::new(1500.0, 100.0, 0.06), start_time)
+            .0;
+        let inactive = engine
+            .register_player_at(PublicRating::new(1600.0, 50.0, 0.06), start_time)
+            .0;
+
+        engine.register_result_at(veteran, regular, &MatchResult::Win, time);
+        engine.register_result_at(newcomer, regular, &MatchResult::Win, time);
+        engine.register_result_at(inactive, regular, &MatchResult::Loss, start_time);
+
+        let leaderboard = engine.leaderboard_at(LeaderboardSettings::new(), time);
+        let top = leaderboard
+            .top(2)
+            .iter()
+            .map(LeaderboardEntry::player)
+            .collect::<Vec<_>>();
+        assert_eq!(top, [newcomer, veteran]);
+        assert_eq!(leaderboard.len(), 4);
+        assert_eq!(leaderboard.rank(inactive), Some(3));
+        assert_eq!(leaderboard.rank(regular), Some(4));
+        assert_eq!(leaderboard.top(10).len(), 4);
+
+        // The uncertain newcomer falls behind when ranked conservatively
+        let leaderboard = engine.leaderboard_at(
+            LeaderboardSettings::new().with_ranking_key(RankingKey::ConservativeRating(2.0)),
+            time,
+        );
+        assert_eq!(leaderboard.rank(veteran), Some(1));
+        assert!(leaderboard.rank(newcomer).unwrap() > 1);
+
+        let leaderboard = engine.leaderboard_at(
+            LeaderboardSettings::new()
+                .with_max_deviation(200.0)
+                .with_min_games(1)
+                .with_active_within(Duration::from_secs(60)),
+            time,
+        );
+        assert_eq!(leaderboard.rank(veteran), Some(1));
+        assert_eq!(leaderboard.rank(newcomer), None);
+        assert_eq!(leaderboard.rank(inactive), None);
+        assert!(leaderboard.entry(regular).is_some());
+
+        let leaderboard = engine.leaderboard_at(LeaderboardSettings::new().with_min_games(2), time);
+        let players = leaderboard
+            .entries()
+            .iter()
+            .map(LeaderboardEntry::player)
+            .collect::<Vec<_>>();
+        assert_eq!(players, [regular]);
+    }
+}
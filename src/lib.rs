@@ -91,6 +91,9 @@
 //!
 //! The [`engine`] module provides the [`RatingEngine`][engine::RatingEngine] struct which allows for adding games
 //! and getting the current rating of managed players at any point in time.
+//!
+//! The [`keyed`] module provides the [`KeyedRatingEngine`][keyed::KeyedRatingEngine] struct
+//! which wraps a [`RatingEngine`][engine::RatingEngine] and identifies players by your own keys, e.g. user IDs.
 
 #![warn(clippy::pedantic)]
 #![warn(clippy::cargo)]
@@ -106,12 +109,22 @@
     rustdoc::invalid_rust_codeblocks
 )]
 #![forbid(unsafe_code)]
+// These lints are newer than the code they would flag, which predates them.
+#![allow(
+    clippy::doc_lazy_continuation,
+    clippy::struct_field_names,
+    clippy::iter_without_into_iter
+)]
 
 // TODO: Lots of const fn
 
+use algorithm::OutcomeProbabilities;
 use constants::RATING_SCALING_RATIO;
+use engine::{PlayerHandle, ResultId};
+use pooled::PooledPlayerHandle;
 use std::cmp::Ordering;
-use std::fmt::Debug;
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
 use std::marker::PhantomData;
 use std::time::Duration;
 
@@ -119,8 +132,19 @@ use std::time::Duration;
 use serde::{Deserialize, Serialize};
 
 pub mod algorithm;
+pub mod clock;
+pub mod concurrent;
 pub mod constants;
 pub mod engine;
+pub mod events;
+pub mod history;
+pub mod keyed;
+pub mod leaderboard;
+pub mod matchmaking;
+pub mod observer;
+pub mod pooled;
+pub mod report;
+pub mod team;
 pub mod util;
 
 /// Marker type for the public scale of Glicko-2 ratings. See [`RatingScale`].
@@ -246,17 +270,32 @@ impl<Scale: RatingScale> Rating<Scale> {
     /// # Panics
     ///
     /// This function panics if `deviation <= 0.0` or `volatility <= 0.0`.
+    /// See [`try_new`][Self::try_new] for a non-panicking version.
     #[must_use]
     pub fn new(rating: f64, deviation: f64, volatility: f64) -> Self {
-        assert!(deviation > 0.0, "deviation <= 0: {deviation}");
-        assert!(volatility > 0.0, "volatility <= 0: {volatility}");
+        Self::try_new(rating, deviation, volatility).unwrap_or_else(|err| panic!("{err}"))
+    }
 
-        Rating {
+    /// Creates a new [`Rating`] with the specified values.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidDeviation`] if `deviation <= 0.0`
+    /// and [`GlickoError::InvalidVolatility`] if `volatility <= 0.0`.
+    pub fn try_new(rating: f64, deviation: f64, volatility: f64) -> Result<Self, GlickoError> {
+        if deviation.is_nan() || deviation <= 0.0 {
+            return Err(GlickoError::InvalidDeviation(deviation));
+        }
+        if volatility.is_nan() || volatility <= 0.0 {
+            return Err(GlickoError::InvalidVolatility(volatility));
+        }
+
+        Ok(Rating {
             rating,
             deviation,
             volatility,
             _scale: PhantomData,
-        }
+        })
     }
 
     /// The rating value.
@@ -276,6 +315,41 @@ impl<Scale: RatingScale> Rating<Scale> {
     pub fn volatility(&self) -> f64 {
         self.volatility
     }
+
+    /// The score a player with this rating is expected to achieve against an opponent with `opponent` rating on average,
+    /// between `0.0` (certain loss) and `1.0` (certain win).
+    ///
+    /// See [`algorithm::expected_score`].
+    #[must_use]
+    pub fn expected_score(&self, opponent: Rating<Scale>, settings: GlickoSettings) -> f64
+    where
+        Scale: ConvertToScale<Internal>,
+    {
+        algorithm::expected_score(
+            (*self).into_with_settings(settings),
+            opponent.into_with_settings(settings),
+            settings,
+        )
+    }
+
+    /// The probabilities that a player with this rating wins, draws or loses against an opponent with `opponent` rating.
+    ///
+    /// See [`algorithm::outcome_probabilities`].
+    #[must_use]
+    pub fn outcome_probabilities(
+        &self,
+        opponent: Rating<Scale>,
+        settings: GlickoSettings,
+    ) -> OutcomeProbabilities
+    where
+        Scale: ConvertToScale<Internal>,
+    {
+        algorithm::outcome_probabilities(
+            (*self).into_with_settings(settings),
+            opponent.into_with_settings(settings),
+            settings,
+        )
+    }
 }
 
 /// The settings used by the Glicko-2 algorithm.
@@ -286,6 +360,18 @@ pub struct GlickoSettings {
     volatility_change: f64,
     convergence_tolerance: f64,
     rating_period_duration: Duration,
+    #[cfg_attr(feature = "serde", serde(default))]
+    draw_parameter: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    rating_floor: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    rating_cap: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    milestone_floors: Option<MilestoneFloors>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    max_deviation: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    min_deviation: Option<f64>,
 }
 
 impl GlickoSettings {
@@ -308,6 +394,7 @@ impl GlickoSettings {
     /// # Panics
     ///
     /// This function panics if `convergence_tolerance <= 0.0` or if `rating_period_duration` is zero.
+    /// See [`try_new`][Self::try_new] for a non-panicking version.
     #[must_use]
     pub fn new(
         start_rating: PublicRating,
@@ -315,21 +402,50 @@ impl GlickoSettings {
         convergence_tolerance: f64,
         rating_period_duration: Duration,
     ) -> Self {
-        assert!(
-            convergence_tolerance > 0.0,
-            "convergence_tolerance <= 0: {convergence_tolerance}"
-        );
-        assert!(
-            !rating_period_duration.is_zero(),
-            "rating_period_duration may not be zero"
-        );
-
-        GlickoSettings {
+        Self::try_new(
             start_rating,
             volatility_change,
             convergence_tolerance,
             rating_period_duration,
+        )
+        .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates [`GlickoSettings`] with the given settings.
+    ///
+    /// See [`new`][Self::new] for a description of the arguments.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidConvergenceTolerance`] if `convergence_tolerance <= 0.0`
+    /// and [`GlickoError::ZeroRatingPeriodDuration`] if `rating_period_duration` is zero.
+    pub fn try_new(
+        start_rating: PublicRating,
+        volatility_change: f64,
+        convergence_tolerance: f64,
+        rating_period_duration: Duration,
+    ) -> Result<Self, GlickoError> {
+        if convergence_tolerance.is_nan() || convergence_tolerance <= 0.0 {
+            return Err(GlickoError::InvalidConvergenceTolerance(
+                convergence_tolerance,
+            ));
         }
+        if rating_period_duration.is_zero() {
+            return Err(GlickoError::ZeroRatingPeriodDuration);
+        }
+
+        Ok(GlickoSettings {
+            start_rating,
+            volatility_change,
+            convergence_tolerance,
+            rating_period_duration,
+            draw_parameter: 0.0,
+            rating_floor: None,
+            rating_cap: None,
+            milestone_floors: None,
+            max_deviation: None,
+            min_deviation: None,
+        })
     }
 
     /// Creates [`GlickoSettings`] with the same settings as `self`, only changing the start rating to `start_rating`.
@@ -368,6 +484,189 @@ impl GlickoSettings {
         }
     }
 
+    /// Creates [`GlickoSettings`] with the same settings as `self`, only changing the draw parameter to `draw_parameter`.
+    ///
+    /// See [`draw_parameter`][Self::draw_parameter].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `draw_parameter < 0.0`.
+    /// See [`try_with_draw_parameter`][Self::try_with_draw_parameter] for a non-panicking version.
+    #[must_use]
+    pub fn with_draw_parameter(self, draw_parameter: f64) -> Self {
+        self.try_with_draw_parameter(draw_parameter)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates [`GlickoSettings`] with the same settings as `self`, only changing the draw parameter to `draw_parameter`.
+    ///
+    /// See [`draw_parameter`][Self::draw_parameter].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidDrawParameter`] if `draw_parameter < 0.0`.
+    pub fn try_with_draw_parameter(self, draw_parameter: f64) -> Result<Self, GlickoError> {
+        if draw_parameter.is_nan() || draw_parameter < 0.0 || draw_parameter.is_infinite() {
+            return Err(GlickoError::InvalidDrawParameter(draw_parameter));
+        }
+
+        Ok(GlickoSettings {
+            draw_parameter,
+            ..self
+        })
+    }
+
+    /// Creates [`GlickoSettings`] with the same settings as `self`, only changing the rating floor to `rating_floor`.
+    ///
+    /// See [`rating_floor`][Self::rating_floor].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `rating_floor` is not finite or higher than the rating cap.
+    /// See [`try_with_rating_floor`][Self::try_with_rating_floor] for a non-panicking version.
+    #[must_use]
+    pub fn with_rating_floor(self, rating_floor: f64) -> Self {
+        self.try_with_rating_floor(rating_floor)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates [`GlickoSettings`] with the same settings as `self`, only changing the rating floor to `rating_floor`.
+    ///
+    /// See [`rating_floor`][Self::rating_floor].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidRatingLimit`] if `rating_floor` is not finite or higher than the rating cap.
+    pub fn try_with_rating_floor(self, rating_floor: f64) -> Result<Self, GlickoError> {
+        if !rating_floor.is_finite()
+            || matches!(self.rating_cap, Some(rating_cap) if rating_floor > rating_cap)
+        {
+            return Err(GlickoError::InvalidRatingLimit(rating_floor));
+        }
+
+        Ok(GlickoSettings {
+            rating_floor: Some(rating_floor),
+            ..self
+        })
+    }
+
+    /// Creates [`GlickoSettings`] with the same settings as `self`, only changing the rating cap to `rating_cap`.
+    ///
+    /// See [`rating_cap`][Self::rating_cap].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `rating_cap` is not finite or lower than the rating floor.
+    /// See [`try_with_rating_cap`][Self::try_with_rating_cap] for a non-panicking version.
+    #[must_use]
+    pub fn with_rating_cap(self, rating_cap: f64) -> Self {
+        self.try_with_rating_cap(rating_cap)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates [`GlickoSettings`] with the same settings as `self`, only changing the rating cap to `rating_cap`.
+    ///
+    /// See [`rating_cap`][Self::rating_cap].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidRatingLimit`] if `rating_cap` is not finite or lower than the rating floor.
+    pub fn try_with_rating_cap(self, rating_cap: f64) -> Result<Self, GlickoError> {
+        if !rating_cap.is_finite()
+            || matches!(self.rating_floor, Some(rating_floor) if rating_cap < rating_floor)
+        {
+            return Err(GlickoError::InvalidRatingLimit(rating_cap));
+        }
+
+        Ok(GlickoSettings {
+            rating_cap: Some(rating_cap),
+            ..self
+        })
+    }
+
+    /// Creates [`GlickoSettings`] with the same settings as `self`, only changing the milestone floors to `milestone_floors`.
+    ///
+    /// See [`milestone_floors`][Self::milestone_floors].
+    #[must_use]
+    pub fn with_milestone_floors(self, milestone_floors: MilestoneFloors) -> Self {
+        GlickoSettings {
+            milestone_floors: Some(milestone_floors),
+            ..self
+        }
+    }
+
+    /// Creates [`GlickoSettings`] with the same settings as `self`, only changing the maximum deviation to `max_deviation`.
+    ///
+    /// See [`max_deviation`][Self::max_deviation].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `max_deviation <= 0.0`, if it is not finite, or if it is lower than the minimum deviation.
+    /// See [`try_with_max_deviation`][Self::try_with_max_deviation] for a non-panicking version.
+    #[must_use]
+    pub fn with_max_deviation(self, max_deviation: f64) -> Self {
+        self.try_with_max_deviation(max_deviation)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates [`GlickoSettings`] with the same settings as `self`, only changing the maximum deviation to `max_deviation`.
+    ///
+    /// See [`max_deviation`][Self::max_deviation].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidDeviation`] if `max_deviation <= 0.0`, if it is not finite,
+    /// or if it is lower than the minimum deviation.
+    pub fn try_with_max_deviation(self, max_deviation: f64) -> Result<Self, GlickoError> {
+        if !max_deviation.is_finite()
+            || max_deviation <= 0.0
+            || matches!(self.min_deviation, Some(min_deviation) if max_deviation < min_deviation)
+        {
+            return Err(GlickoError::InvalidDeviation(max_deviation));
+        }
+
+        Ok(GlickoSettings {
+            max_deviation: Some(max_deviation),
+            ..self
+        })
+    }
+
+    /// Creates [`GlickoSettings`] with the same settings as `self`, only changing the minimum deviation to `min_deviation`.
+    ///
+    /// See [`min_deviation`][Self::min_deviation].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `min_deviation <= 0.0`, if it is not finite, or if it is higher than the maximum deviation.
+    /// See [`try_with_min_deviation`][Self::try_with_min_deviation] for a non-panicking version.
+    #[must_use]
+    pub fn with_min_deviation(self, min_deviation: f64) -> Self {
+        self.try_with_min_deviation(min_deviation)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates [`GlickoSettings`] with the same settings as `self`, only changing the minimum deviation to `min_deviation`.
+    ///
+    /// See [`min_deviation`][Self::min_deviation].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidDeviation`] if `min_deviation <= 0.0`, if it is not finite,
+    /// or if it is higher than the maximum deviation.
+    pub fn try_with_min_deviation(self, min_deviation: f64) -> Result<Self, GlickoError> {
+        if !min_deviation.is_finite()
+            || min_deviation <= 0.0
+            || matches!(self.max_deviation, Some(max_deviation) if min_deviation > max_deviation)
+        {
+            return Err(GlickoError::InvalidDeviation(min_deviation));
+        }
+
+        Ok(GlickoSettings {
+            min_deviation: Some(min_deviation),
+            ..self
+        })
+    }
+
     /// The rating value a new player starts out with.
     ///
     /// See also [`constants::DEFAULT_START_RATING`].
@@ -400,6 +699,197 @@ impl GlickoSettings {
     pub fn rating_period_duration(&self) -> Duration {
         self.rating_period_duration
     }
+
+    /// Also called "ν". Controls how likely draws are in [Davidson's model](https://doi.org/10.1080/01621459.1970.10481082)
+    /// for games that can end in a draw.
+    ///
+    /// The probability of a draw between two players with equal ratings is `ν / (2 + ν)`,
+    /// so for a draw rate of `p` between equal players, `ν = 2p / (1 - p)`.
+    /// Draws become less likely the further apart the ratings are.
+    ///
+    /// With the default of `0.0`, draws aren't modelled explicitly and a draw simply counts as a score of `0.5`,
+    /// as described in [Glickman's paper](http://www.glicko.net/glicko/glicko2.pdf).
+    /// With a positive draw parameter, the expected scores and the information a result carries in the rating update
+    /// follow Davidson's model, so e.g. a draw against a much stronger opponent is less surprising.
+    /// See also [`algorithm::outcome_probabilities`].
+    #[must_use]
+    pub fn draw_parameter(&self) -> f64 {
+        self.draw_parameter
+    }
+
+    /// The [`Public`] rating value no rating can fall below, or `None` if there is no floor.
+    ///
+    /// The floor is applied to every rating calculated by [`algorithm::rate_games_untimed`] and the rating engines.
+    /// Only the rating value is limited, the deviation and volatility are calculated as usual.
+    #[must_use]
+    pub fn rating_floor(&self) -> Option<f64> {
+        self.rating_floor
+    }
+
+    /// The [`Public`] rating value no rating can rise above, or `None` if there is no cap.
+    ///
+    /// The cap is applied to every rating calculated by [`algorithm::rate_games_untimed`] and the rating engines.
+    /// Only the rating value is limited, the deviation and volatility are calculated as usual.
+    /// Individual rating floors of players take precedence over the cap.
+    #[must_use]
+    pub fn rating_cap(&self) -> Option<f64> {
+        self.rating_cap
+    }
+
+    /// The [`Public`] rating deviation no rating can grow beyond while time passes, or `None` if there is no limit.
+    ///
+    /// Without a limit, the deviation of an inactive player grows indefinitely (see "Step 6." in [Glickman's paper](http://www.glicko.net/glicko/glicko2.pdf)),
+    /// so a player returning after a long time could look less known than a new player.
+    /// Setting this to the deviation of the [start rating][Self::start_rating] prevents that, as Lichess does.
+    ///
+    /// The limit is applied by [`TimedRating::rating_at`][algorithm::TimedRating::rating_at],
+    /// [`algorithm::rate_games_untimed`] and the rating engines.
+    #[must_use]
+    pub fn max_deviation(&self) -> Option<f64> {
+        self.max_deviation
+    }
+
+    /// The [`Public`] rating deviation no rating can shrink below when games are rated, or `None` if there is no limit.
+    ///
+    /// The deviation of a very active player shrinks with every rating period,
+    /// which makes their rating change less and less after each game.
+    /// A minimum deviation keeps such ratings responsive to changes in skill.
+    ///
+    /// The limit is applied to the new deviation from "Step 7.1." in [Glickman's paper](http://www.glicko.net/glicko/glicko2.pdf),
+    /// so the new rating value in "Step 7.2." moves according to the limited deviation.
+    /// It is applied by [`algorithm::rate_games_untimed`] and thus by all other rating functions and the rating engines.
+    /// Deviations that are already lower aren't raised if no games are rated.
+    #[must_use]
+    pub fn min_deviation(&self) -> Option<f64> {
+        self.min_deviation
+    }
+
+    /// How the individual rating floors of the players of a [`RatingEngine`][engine::RatingEngine] are raised
+    /// when they reach rating milestones, or `None` if they aren't raised automatically.
+    ///
+    /// See [`EnginePlayer::rating_floor`][engine::EnginePlayer::rating_floor].
+    #[must_use]
+    pub fn milestone_floors(&self) -> Option<MilestoneFloors> {
+        self.milestone_floors
+    }
+
+    /// Limits the rating value of `rating` to the rating floor and cap,
+    /// and to the individual rating floor of a player if given.
+    #[must_use]
+    pub(crate) fn limit_rating(
+        &self,
+        rating: InternalRating,
+        player_rating_floor: Option<f64>,
+    ) -> InternalRating {
+        let to_internal = |value: f64| (value - self.start_rating.rating()) / RATING_SCALING_RATIO;
+
+        let mut value = rating.rating();
+
+        if let Some(rating_cap) = self.rating_cap {
+            value = value.min(to_internal(rating_cap));
+        }
+        for rating_floor in self.rating_floor.iter().chain(&player_rating_floor) {
+            value = value.max(to_internal(*rating_floor));
+        }
+
+        InternalRating::new(value, rating.deviation(), rating.volatility())
+    }
+
+    /// The individual rating floor of a player with the rating floor `player_rating_floor` after reaching `rating`,
+    /// see [`milestone_floors`][Self::milestone_floors].
+    #[must_use]
+    pub(crate) fn raise_rating_floor(
+        &self,
+        player_rating_floor: Option<f64>,
+        rating: InternalRating,
+    ) -> Option<f64> {
+        let milestone_floors = match self.milestone_floors {
+            Some(milestone_floors) => milestone_floors,
+            None => return player_rating_floor,
+        };
+
+        let public_rating: PublicRating = rating.into_with_settings(*self);
+        let milestone_floor = milestone_floors.rating_floor(public_rating.rating());
+
+        Some(
+            player_rating_floor.map_or(milestone_floor, |player_rating_floor| {
+                player_rating_floor.max(milestone_floor)
+            }),
+        )
+    }
+}
+
+/// Individual rating floors for players who reached rating milestones, as used by many chess federations.
+///
+/// Once a player's rating reaches a multiple of the milestone interval when a rating period is closed,
+/// their individual rating floor is raised to that milestone minus the floor distance.
+/// For example, with an interval of `100.0` and a distance of `200.0`,
+/// a player reaching a rating of `1850.0` can't fall below `1600.0` anymore.
+///
+/// Only players who played in the closed rating period have their floor raised,
+/// so a player registered with a high rating doesn't get a floor before their first game.
+/// This includes players whose rating is still provisional, i.e. whose deviation is high,
+/// so a few lucky games early on can already raise their floor.
+///
+/// See [`GlickoSettings::with_milestone_floors`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MilestoneFloors {
+    milestone_interval: f64,
+    floor_distance: f64,
+}
+
+impl MilestoneFloors {
+    /// Creates [`MilestoneFloors`] with milestones at every multiple of `milestone_interval`,
+    /// and floors `floor_distance` below the milestones.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `milestone_interval` is not positive or if one of the values is not finite.
+    /// See [`try_new`][Self::try_new] for a non-panicking version.
+    #[must_use]
+    pub fn new(milestone_interval: f64, floor_distance: f64) -> Self {
+        Self::try_new(milestone_interval, floor_distance).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates [`MilestoneFloors`] with milestones at every multiple of `milestone_interval`,
+    /// and floors `floor_distance` below the milestones.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::InvalidRatingLimit`] if `milestone_interval` is not positive
+    /// or if one of the values is not finite.
+    pub fn try_new(milestone_interval: f64, floor_distance: f64) -> Result<Self, GlickoError> {
+        if !milestone_interval.is_finite() || milestone_interval <= 0.0 {
+            return Err(GlickoError::InvalidRatingLimit(milestone_interval));
+        }
+        if !floor_distance.is_finite() {
+            return Err(GlickoError::InvalidRatingLimit(floor_distance));
+        }
+
+        Ok(MilestoneFloors {
+            milestone_interval,
+            floor_distance,
+        })
+    }
+
+    /// The distance between two milestones.
+    #[must_use]
+    pub fn milestone_interval(&self) -> f64 {
+        self.milestone_interval
+    }
+
+    /// How far below a reached milestone the rating floor is.
+    #[must_use]
+    pub fn floor_distance(&self) -> f64 {
+        self.floor_distance
+    }
+
+    /// The rating floor of a player who reached the [`Public`] rating value `rating`.
+    #[must_use]
+    pub fn rating_floor(&self, rating: f64) -> f64 {
+        (rating / self.milestone_interval).floor() * self.milestone_interval - self.floor_distance
+    }
 }
 
 impl Default for GlickoSettings {
@@ -413,3 +903,144 @@ impl Default for GlickoSettings {
         )
     }
 }
+
+/// The error type for fallible operations in this crate.
+///
+/// Most functions that can fail have a panicking counterpart without the `try_` prefix.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[non_exhaustive]
+pub enum GlickoError {
+    /// The player handle doesn't belong to a registered player of the [`RatingEngine`][engine::RatingEngine]
+    /// or [`ConcurrentRatingEngine`][concurrent::ConcurrentRatingEngine], e.g. because the player was removed.
+    UnknownPlayer(PlayerHandle),
+    /// No player with the given key is registered with the [`KeyedRatingEngine`][keyed::KeyedRatingEngine].
+    UnknownPlayerKey,
+    /// A player with the given key is already registered with the [`KeyedRatingEngine`][keyed::KeyedRatingEngine].
+    DuplicatePlayerKey,
+    /// The player handle doesn't belong to a registered player of the [`PooledRatingEngine`][pooled::PooledRatingEngine],
+    /// e.g. because the player was removed.
+    UnknownPooledPlayer(PooledPlayerHandle),
+    /// No pool with the given identifier exists in the [`PooledRatingEngine`][pooled::PooledRatingEngine].
+    UnknownPool,
+    /// A pool with the given identifier already exists in the [`PooledRatingEngine`][pooled::PooledRatingEngine].
+    DuplicatePool,
+    /// An event log passed to [`RatingEngine::replay`][engine::RatingEngine::replay] was malformed or inconsistent.
+    InvalidEventLog,
+    /// The result id was never handed out by the [`RatingEngine`][engine::RatingEngine].
+    UnknownResult(ResultId),
+    /// The result can no longer be changed because its rating period was closed or because it was retracted.
+    ClosedResult(ResultId),
+    /// The result is a ranked result, which can't be amended with a score,
+    /// see [`RatingEngine::register_ranked_result`][engine::RatingEngine::register_ranked_result].
+    RankedResult(ResultId),
+    /// A team of a team result is empty or a player appears more than once,
+    /// see [`RatingEngine::register_team_result`][engine::RatingEngine::register_team_result].
+    InvalidTeams,
+    /// The players and placements of a ranked result don't match,
+    /// e.g. because there are fewer than two players or a player appears more than once.
+    InvalidRanking,
+    /// The rating period at the given time is not archived by the [`RatingEngine`][engine::RatingEngine],
+    /// see [`RatingEngine::set_max_archived_periods`][engine::RatingEngine::set_max_archived_periods].
+    PeriodNotArchived(std::time::SystemTime),
+    /// A game score was not between `0.0` and `1.0`.
+    InvalidScore(f64),
+    /// A game weight was not positive.
+    InvalidWeight(f64),
+    /// A rating deviation was not positive, or a deviation limit was not finite or contradicted the other limit.
+    InvalidDeviation(f64),
+    /// A rating volatility was not positive.
+    InvalidVolatility(f64),
+    /// A convergence tolerance was not positive.
+    InvalidConvergenceTolerance(f64),
+    /// A draw parameter was negative or not finite.
+    InvalidDrawParameter(f64),
+    /// A rating floor, cap or milestone was not finite, a milestone interval was not positive,
+    /// or a rating floor was higher than a rating cap.
+    InvalidRatingLimit(f64),
+    /// A rating period duration was zero.
+    ZeroRatingPeriodDuration,
+    /// An amount of elapsed rating periods was negative.
+    InvalidElapsedPeriods(f64),
+    /// The converging loop algorithm in "Step 5.4." in [Glickman's paper](http://www.glicko.net/glicko/glicko2.pdf)
+    /// did not converge within the maximum number of iterations.
+    NoConvergence {
+        /// The maximum number of iterations that was exceeded. See [`constants::MAX_ITERATIONS`].
+        max_iterations: u32,
+        /// The convergence tolerance that was used.
+        convergence_tolerance: f64,
+    },
+}
+
+impl Display for GlickoError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            GlickoError::UnknownPlayer(player) => {
+                write!(f, "Player {player:?} didn't belong to this RatingEngine")
+            }
+            GlickoError::UnknownPlayerKey => {
+                write!(f, "No player with this key is registered")
+            }
+            GlickoError::DuplicatePlayerKey => {
+                write!(f, "A player with this key is already registered")
+            }
+            GlickoError::UnknownPooledPlayer(player) => {
+                write!(f, "Player {player:?} didn't belong to this PooledRatingEngine")
+            }
+            GlickoError::UnknownPool => write!(f, "No pool with this identifier exists"),
+            GlickoError::DuplicatePool => write!(f, "A pool with this identifier already exists"),
+            GlickoError::InvalidEventLog => write!(f, "The event log is malformed or inconsistent"),
+            GlickoError::UnknownResult(result) => {
+                write!(f, "Result {result:?} didn't belong to this RatingEngine")
+            }
+            GlickoError::ClosedResult(result) => write!(
+                f,
+                "Result {result:?} was retracted or its rating period is already closed"
+            ),
+            GlickoError::RankedResult(result) => write!(
+                f,
+                "Result {result:?} is a ranked result and can't be amended with a score"
+            ),
+            GlickoError::InvalidTeams => write!(
+                f,
+                "Both teams need at least one member and no player may appear twice"
+            ),
+            GlickoError::InvalidRanking => write!(
+                f,
+                "The ranking needs at least two distinct players with one placement each"
+            ),
+            GlickoError::PeriodNotArchived(time) => {
+                write!(f, "The rating period at {time:?} is not archived")
+            }
+            GlickoError::InvalidScore(score) => {
+                write!(f, "score is not between 0 and 1: {score}")
+            }
+            GlickoError::InvalidWeight(weight) => write!(f, "weight <= 0: {weight}"),
+            GlickoError::InvalidDeviation(deviation) => write!(f, "deviation <= 0: {deviation}"),
+            GlickoError::InvalidVolatility(volatility) => {
+                write!(f, "volatility <= 0: {volatility}")
+            }
+            GlickoError::InvalidConvergenceTolerance(convergence_tolerance) => {
+                write!(f, "convergence_tolerance <= 0: {convergence_tolerance}")
+            }
+            GlickoError::InvalidDrawParameter(draw_parameter) => {
+                write!(f, "draw_parameter is negative or not finite: {draw_parameter}")
+            }
+            GlickoError::InvalidRatingLimit(limit) => write!(f, "invalid rating limit: {limit}"),
+            GlickoError::ZeroRatingPeriodDuration => {
+                write!(f, "rating_period_duration may not be zero")
+            }
+            GlickoError::InvalidElapsedPeriods(elapsed_periods) => {
+                write!(f, "elapsed_periods < 0: {elapsed_periods}")
+            }
+            GlickoError::NoConvergence {
+                max_iterations,
+                convergence_tolerance,
+            } => write!(
+                f,
+                "Maximum number of iterations ({max_iterations}) in converging loop algorithm exceeded. Is the convergence tolerance ({convergence_tolerance}) unreasonably low?"
+            ),
+        }
+    }
+}
+
+impl Error for GlickoError {}
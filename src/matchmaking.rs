@@ -0,0 +1,261 @@
+//! This mod defines helpers to find fair matches between the players of a [`RatingEngine`][crate::engine::RatingEngine].
+//!
+//! See [`RatingEngine::rank_opponents`][crate::engine::RatingEngine::rank_opponents]
+//! and [`RatingEngine::pair_queue`][crate::engine::RatingEngine::pair_queue].
+
+use std::cmp::Ordering;
+
+use crate::algorithm::{self, calculate_g};
+use crate::engine::PlayerHandle;
+use crate::{GlickoSettings, InternalRating};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Calculates the quality of a match between two players, between `0.0` (worst) and `1.0` (best).
+///
+/// A match is better the closer the [expected score][algorithm::expected_score] is to `0.5`.
+/// It is also weighted by the deviations of both players,
+/// since an expected score close to `0.5` says little if the ratings are uncertain.
+/// The quality is symmetric, i.e. it doesn't matter which player is passed first.
+#[must_use]
+pub fn match_quality(
+    player_rating: InternalRating,
+    opponent_rating: InternalRating,
+    settings: GlickoSettings,
+) -> f64 {
+    let combined_deviation = f64::sqrt(
+        player_rating.deviation() * player_rating.deviation()
+            + opponent_rating.deviation() * opponent_rating.deviation(),
+    );
+
+    let expected_score = algorithm::expected_score(player_rating, opponent_rating, settings);
+
+    (1.0 - 2.0 * (expected_score - 0.5).abs()) * calculate_g(combined_deviation)
+}
+
+/// A possible match between two players.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProposedMatch {
+    player_1: PlayerHandle,
+    player_2: PlayerHandle,
+    expected_score: f64,
+    quality: f64,
+}
+
+impl ProposedMatch {
+    /// Proposes a match between two players with the given ratings.
+    pub(crate) fn new(
+        (player_1, player_1_rating): (PlayerHandle, InternalRating),
+        (player_2, player_2_rating): (PlayerHandle, InternalRating),
+        settings: GlickoSettings,
+    ) -> Self {
+        ProposedMatch {
+            player_1,
+            player_2,
+            expected_score: algorithm::expected_score(player_1_rating, player_2_rating, settings),
+            quality: match_quality(player_1_rating, player_2_rating, settings),
+        }
+    }
+
+    /// The first player.
+    #[must_use]
+    pub fn player_1(&self) -> PlayerHandle {
+        self.player_1
+    }
+
+    /// The second player.
+    #[must_use]
+    pub fn player_2(&self) -> PlayerHandle {
+        self.player_2
+    }
+
+    /// The score the first player is expected to achieve against the second player.
+    /// See [`algorithm::expected_score`].
+    #[must_use]
+    pub fn expected_score(&self) -> f64 {
+        self.expected_score
+    }
+
+    /// The quality of this match. See [`match_quality`].
+    #[must_use]
+    pub fn quality(&self) -> f64 {
+        self.quality
+    }
+
+    /// Orders matches from the best to the worst, and matches of the same quality by their players.
+    fn cmp_best_first(&self, other: &Self) -> Ordering {
+        other
+            .quality
+            .partial_cmp(&self.quality)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.player_1.cmp(&other.player_1))
+            .then_with(|| self.player_2.cmp(&other.player_2))
+    }
+}
+
+/// Ranks the possible matches between `player` and each of the `candidates`, from the best to the worst.
+/// `player` itself is not considered as an opponent.
+pub(crate) fn rank_opponents(
+    player: (PlayerHandle, InternalRating),
+    candidates: &[(PlayerHandle, InternalRating)],
+    settings: GlickoSettings,
+) -> Vec<ProposedMatch> {
+    let mut matches: Vec<_> = candidates
+        .iter()
+        .filter(|(candidate, _)| *candidate != player.0)
+        .map(|&candidate| ProposedMatch::new(player, candidate, settings))
+        .collect();
+
+    matches.sort_by(ProposedMatch::cmp_best_first);
+
+    matches
+}
+
+/// The result of pairing a queue of players, see [`RatingEngine::pair_queue`][crate::engine::RatingEngine::pair_queue].
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QueuePairing {
+    matches: Vec<ProposedMatch>,
+    unpaired: Option<PlayerHandle>,
+}
+
+impl QueuePairing {
+    /// Greedily pairs the given players: The best possible match is picked first,
+    /// then the best possible match between the remaining players, and so on.
+    ///
+    /// The players must be sorted by handle and free of duplicates.
+    ///
+    /// All `n * (n - 1) / 2` possible matches are built and sorted up front.
+    pub(crate) fn greedy(
+        queue: &[(PlayerHandle, InternalRating)],
+        settings: GlickoSettings,
+    ) -> Self {
+        // The candidates remember the queue positions of their players to look up whether they're paired
+        let mut candidates = Vec::with_capacity(queue.len() * queue.len().saturating_sub(1) / 2);
+
+        for (index_1, &player_1) in queue.iter().enumerate() {
+            for (index_2, &player_2) in queue.iter().enumerate().skip(index_1 + 1) {
+                candidates.push((
+                    index_1,
+                    index_2,
+                    ProposedMatch::new(player_1, player_2, settings),
+                ));
+            }
+        }
+
+        candidates.sort_by(|(_, _, match_1), (_, _, match_2)| match_1.cmp_best_first(match_2));
+
+        let mut paired = vec![false; queue.len()];
+        let mut matches = Vec::with_capacity(queue.len() / 2);
+
+        for (index_1, index_2, candidate) in candidates {
+            if paired[index_1] || paired[index_2] {
+                continue;
+            }
+
+            paired[index_1] = true;
+            paired[index_2] = true;
+            matches.push(candidate);
+        }
+
+        let unpaired = queue
+            .iter()
+            .zip(paired)
+            .find(|(_, paired)| !paired)
+            .map(|(&(player, _), _)| player);
+
+        QueuePairing { matches, unpaired }
+    }
+
+    /// The matches, from the best to the worst.
+    ///
+    /// Since the pairing is greedy, the first match is the best possible match of the whole queue,
+    /// while later matches can be considerably worse.
+    #[must_use]
+    pub fn matches(&self) -> &[ProposedMatch] {
+        &self.matches
+    }
+
+    /// The player that is left over if the queue has an odd number of players.
+    #[must_use]
+    pub fn unpaired(&self) -> Option<PlayerHandle> {
+        self.unpaired
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use super::{match_quality, ProposedMatch};
+    use crate::engine::RatingEngine;
+    use crate::{GlickoError, GlickoSettings, IntoWithSettings, Public, PublicRating};
+
+    #[test]
+    fn test_matchmaking() {
+        let settings = GlickoSettings::default();
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings);
+
+        let strong = engine
+            .register_player_at(PublicRating::new(2000.0, 50.0, 0.06), start_time)
+            .0;
+        let weak = engine
+            .register_player_at(PublicRating::new(1200.0, 50.0, 0.06), start_time)
+            .0;
+        let close_to_strong = engine
+            .register_player_at(PublicRating::new(1950.0, 50.0, 0.06), start_time)
+            .0;
+        let uncertain = engine
+            .register_player_at(PublicRating::new(1950.0, 350.0, 0.06), start_time)
+            .0;
+        let close_to_weak = engine
+            .register_player_at(PublicRating::new(1250.0, 60.0, 0.06), start_time)
+            .0;
+
+        let even = PublicRating::new(1500.0, 50.0, 0.06).into_with_settings(settings);
+        let uncertain_even = PublicRating::new(1500.0, 350.0, 0.06).into_with_settings(settings);
+        assert!(
+            match_quality(even, even, settings) > match_quality(even, uncertain_even, settings)
+        );
+        assert!(match_quality(even, uncertain_even, settings) > 0.0);
+
+        let ranked = engine.rank_opponents_at(
+            strong,
+            &[weak, strong, uncertain, close_to_strong, close_to_strong],
+            start_time,
+        );
+        let opponents = ranked
+            .iter()
+            .map(ProposedMatch::player_2)
+            .collect::<Vec<_>>();
+        assert_eq!(opponents, [close_to_strong, uncertain, weak]);
+        assert!(ranked.iter().all(|proposed| proposed.player_1() == strong));
+        assert!(ranked[0].expected_score() > 0.5);
+        assert!(ranked[2].expected_score() > 0.9);
+
+        let pairing = engine.pair_queue_at(
+            &[weak, strong, close_to_weak, close_to_strong, uncertain],
+            start_time,
+        );
+        let pairs = pairing
+            .matches()
+            .iter()
+            .map(|proposed| (proposed.player_1(), proposed.player_2()))
+            .collect::<Vec<_>>();
+        assert_eq!(pairs, [(strong, close_to_strong), (weak, close_to_weak)]);
+        assert_eq!(pairing.unpaired(), Some(uncertain));
+
+        assert_eq!(engine.pair_queue_at(&[], start_time).matches(), []);
+
+        engine.remove_player::<Public>(weak);
+        assert_eq!(
+            engine.try_pair_queue_at(&[strong, weak], start_time),
+            Err(GlickoError::UnknownPlayer(weak)),
+        );
+    }
+}
@@ -0,0 +1,418 @@
+//! This mod defines the [`RatingObserver`] trait, which is notified by a [`RatingEngine`][crate::engine::RatingEngine] whenever ratings change.
+//!
+//! See [`RatingEngine::with_observer`][crate::engine::RatingEngine::with_observer].
+
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::engine::{PlayerHandle, ResultId};
+use crate::report::PeriodCloseReport;
+use crate::PublicRating;
+
+/// A listener for the changes of a [`RatingEngine`][crate::engine::RatingEngine], e.g. to push rating updates to clients or caches.
+///
+/// All methods do nothing by default, so only the interesting ones need to be implemented.
+/// The methods are called after the change happened.
+/// Observers are shared between clones of an engine, so they only get a shared reference;
+/// use interior mutability or channels to act on the notifications.
+pub trait RatingObserver: Send + Sync {
+    /// Called when a player was registered with `rating`.
+    fn player_registered(&self, player: PlayerHandle, rating: PublicRating, time: SystemTime) {
+        let _ = (player, rating, time);
+    }
+
+    /// Called for every player of a result that was registered at `time`,
+    /// with the player's rating at `time` right before and right after the result was registered.
+    ///
+    /// A result can have more than two players, e.g. a team result or a ranked result.
+    fn result_registered(
+        &self,
+        result: ResultId,
+        player: PlayerHandle,
+        old_rating: PublicRating,
+        new_rating: PublicRating,
+        time: SystemTime,
+    ) {
+        let _ = (result, player, old_rating, new_rating, time);
+    }
+
+    /// Called for every player of a result that was retracted at `time`,
+    /// with the player's rating at `time` right before and right after the result was retracted.
+    fn result_retracted(
+        &self,
+        result: ResultId,
+        player: PlayerHandle,
+        old_rating: PublicRating,
+        new_rating: PublicRating,
+        time: SystemTime,
+    ) {
+        let _ = (result, player, old_rating, new_rating, time);
+    }
+
+    /// Called for every player of a result whose scores were amended at `time`,
+    /// with the player's rating at `time` right before and right after the result was amended.
+    fn result_amended(
+        &self,
+        result: ResultId,
+        player: PlayerHandle,
+        old_rating: PublicRating,
+        new_rating: PublicRating,
+        time: SystemTime,
+    ) {
+        let _ = (result, player, old_rating, new_rating, time);
+    }
+
+    /// Called for every player whose rating at `time` changed without a result of theirs being registered,
+    /// retracted or amended, e.g. because a result was registered retroactively or their rating floor changed,
+    /// with the player's rating at `time` right before and right after the change.
+    fn ratings_recalculated(
+        &self,
+        player: PlayerHandle,
+        old_rating: PublicRating,
+        new_rating: PublicRating,
+        time: SystemTime,
+    ) {
+        let _ = (player, old_rating, new_rating, time);
+    }
+
+    /// Called for every player when a rating period ending at `period_end` was closed,
+    /// with the player's rating at the start of the period and at its end.
+    fn rating_period_closed(
+        &self,
+        player: PlayerHandle,
+        old_rating: PublicRating,
+        new_rating: PublicRating,
+        period_end: SystemTime,
+    ) {
+        let _ = (player, old_rating, new_rating, period_end);
+    }
+
+    /// Called when a rating period was closed, after [`rating_period_closed`][Self::rating_period_closed]
+    /// was called for every player.
+    fn period_closed(&self, report: &PeriodCloseReport) {
+        let _ = report;
+    }
+}
+
+/// The observers of a [`RatingEngine`][crate::engine::RatingEngine].
+///
+/// Observers aren't part of the state of an engine,
+/// so they are ignored when comparing engines and aren't (de)serialized.
+#[derive(Clone, Default)]
+pub(crate) struct Observers(Vec<Arc<dyn RatingObserver>>);
+
+impl Observers {
+    pub(crate) fn push(&mut self, observer: Arc<dyn RatingObserver>) {
+        self.0.push(observer);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &dyn RatingObserver> + '_ {
+        self.0.iter().map(AsRef::as_ref)
+    }
+}
+
+impl PartialEq for Observers {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Debug for Observers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Observers({})", self.0.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, SystemTime};
+
+    use super::RatingObserver;
+    use crate::clock::ManualClock;
+    use crate::engine::{MatchResult, PlayerHandle, RatingEngine, ResultId};
+    use crate::{GlickoSettings, Public, PublicRating};
+
+    #[derive(Default)]
+    struct RecordingObserver {
+        registered: Mutex<Vec<PlayerHandle>>,
+        results: Mutex<Vec<(ResultId, PlayerHandle, PublicRating, PublicRating)>>,
+        closed: Mutex<Vec<(PlayerHandle, PublicRating, PublicRating, SystemTime)>>,
+        retracted: Mutex<Vec<(ResultId, PlayerHandle, PublicRating, PublicRating)>>,
+        amended: Mutex<Vec<(ResultId, PlayerHandle, PublicRating, PublicRating)>>,
+        recalculated: Mutex<Vec<(PlayerHandle, PublicRating, PublicRating)>>,
+    }
+
+    impl RatingObserver for RecordingObserver {
+        fn player_registered(
+            &self,
+            player: PlayerHandle,
+            _rating: PublicRating,
+            _time: SystemTime,
+        ) {
+            self.registered.lock().unwrap().push(player);
+        }
+
+        fn result_registered(
+            &self,
+            result: ResultId,
+            player: PlayerHandle,
+            old_rating: PublicRating,
+            new_rating: PublicRating,
+            _time: SystemTime,
+        ) {
+            self.results
+                .lock()
+                .unwrap()
+                .push((result, player, old_rating, new_rating));
+        }
+
+        fn result_retracted(
+            &self,
+            result: ResultId,
+            player: PlayerHandle,
+            old_rating: PublicRating,
+            new_rating: PublicRating,
+            _time: SystemTime,
+        ) {
+            self.retracted
+                .lock()
+                .unwrap()
+                .push((result, player, old_rating, new_rating));
+        }
+
+        fn result_amended(
+            &self,
+            result: ResultId,
+            player: PlayerHandle,
+            old_rating: PublicRating,
+            new_rating: PublicRating,
+            _time: SystemTime,
+        ) {
+            self.amended
+                .lock()
+                .unwrap()
+                .push((result, player, old_rating, new_rating));
+        }
+
+        fn ratings_recalculated(
+            &self,
+            player: PlayerHandle,
+            old_rating: PublicRating,
+            new_rating: PublicRating,
+            _time: SystemTime,
+        ) {
+            self.recalculated
+                .lock()
+                .unwrap()
+                .push((player, old_rating, new_rating));
+        }
+
+        fn rating_period_closed(
+            &self,
+            player: PlayerHandle,
+            old_rating: PublicRating,
+            new_rating: PublicRating,
+            period_end: SystemTime,
+        ) {
+            self.closed
+                .lock()
+                .unwrap()
+                .push((player, old_rating, new_rating, period_end));
+        }
+    }
+
+    #[test]
+    fn test_observer() {
+        let settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(60));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let observer = Arc::new(RecordingObserver::default());
+        let mut engine = RatingEngine::start_new_at(start_time, settings)
+            .with_event_log()
+            .with_observer(observer.clone());
+
+        let player_1 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        let player_2 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        assert_eq!(*observer.registered.lock().unwrap(), [player_1, player_2]);
+
+        let result_time = start_time + Duration::from_secs(30);
+        let (result, _) =
+            engine.register_result_at(player_1, player_2, &MatchResult::Win, result_time);
+
+        {
+            let results = observer.results.lock().unwrap();
+            assert_eq!(results.len(), 2);
+            let (observed_result, player, old_rating, new_rating) = results[0];
+            assert_eq!((observed_result, player), (result, player_1));
+            assert!(old_rating.rating() < new_rating.rating());
+            assert_eq!(
+                new_rating,
+                engine.peek_player_rating_at::<Public>(player_1, result_time),
+            );
+        }
+
+        let period_end = start_time + Duration::from_secs(60);
+        engine.maybe_close_rating_periods_at(period_end);
+
+        {
+            let closed = observer.closed.lock().unwrap();
+            assert_eq!(closed.len(), 2);
+            let (player, old_rating, new_rating, observed_period_end) = closed[0];
+            assert_eq!((player, observed_period_end), (player_1, period_end));
+            assert_eq!(old_rating, settings.start_rating());
+            assert_eq!(
+                new_rating,
+                engine.peek_player_rating_at::<Public>(player_1, period_end),
+            );
+        }
+
+        // Observers aren't part of the state of the engine
+        let events = engine.event_log().unwrap().to_vec();
+        assert_eq!(RatingEngine::replay(settings, &events).unwrap(), engine);
+    }
+
+    #[test]
+    fn test_observer_result_changes() {
+        let settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(60));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let observer = Arc::new(RecordingObserver::default());
+        let mut engine =
+            RatingEngine::start_new_at(start_time, settings).with_observer(observer.clone());
+
+        let player_1 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        let player_2 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+
+        let result_time = start_time + Duration::from_secs(10);
+        let (result, _) =
+            engine.register_result_at(player_1, player_2, &MatchResult::Win, result_time);
+        let won_rating = engine.peek_player_rating_at::<Public>(player_1, result_time);
+
+        let amend_time = start_time + Duration::from_secs(20);
+        let old_rating = engine.peek_player_rating_at::<Public>(player_1, amend_time);
+        engine.amend_result_at(result, &MatchResult::Loss, amend_time);
+
+        {
+            let amended = observer.amended.lock().unwrap();
+            assert_eq!(amended.len(), 2);
+            assert_eq!(
+                amended[0],
+                (
+                    result,
+                    player_1,
+                    old_rating,
+                    engine.peek_player_rating_at::<Public>(player_1, amend_time),
+                ),
+            );
+            assert!(amended[0].3.rating() < won_rating.rating());
+            assert_eq!((amended[1].0, amended[1].1), (result, player_2));
+        }
+
+        let retract_time = start_time + Duration::from_secs(30);
+        let old_rating = engine.peek_player_rating_at::<Public>(player_1, retract_time);
+        engine.retract_result_at(result, retract_time);
+
+        {
+            let retracted = observer.retracted.lock().unwrap();
+            assert_eq!(retracted.len(), 2);
+            let (observed_result, player, observed_old_rating, new_rating) = retracted[0];
+            assert_eq!((observed_result, player), (result, player_1));
+            assert_eq!(observed_old_rating, old_rating);
+            assert!(new_rating.rating() > old_rating.rating());
+            assert_eq!(
+                new_rating,
+                engine.peek_player_rating_at::<Public>(player_1, retract_time),
+            );
+        }
+    }
+
+    #[test]
+    fn test_observer_recalculated_ratings() {
+        let settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(60));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let observer = Arc::new(RecordingObserver::default());
+        let clock = ManualClock::new(start_time);
+        let mut engine = RatingEngine::start_new_at(start_time, settings)
+            .with_archived_periods(2)
+            .with_clock(clock.clone())
+            .with_observer(observer.clone());
+
+        let player_1 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        let player_2 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        let player_3 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+
+        let time = start_time + Duration::from_secs(90);
+        let old_rating = engine.peek_player_rating_at::<Public>(player_1, time);
+        engine
+            .register_result_retroactive_at(
+                player_1,
+                player_2,
+                &MatchResult::Win,
+                start_time + Duration::from_secs(30),
+                time,
+            )
+            .unwrap();
+
+        {
+            let recalculated = observer.recalculated.lock().unwrap();
+            // The third player's rating didn't change
+            assert_eq!(recalculated.len(), 2);
+            let (player, observed_old_rating, new_rating) = recalculated[0];
+            assert_eq!(player, player_1);
+            assert_eq!(observed_old_rating, old_rating);
+            assert_eq!(
+                new_rating,
+                engine.peek_player_rating_at::<Public>(player_1, time),
+            );
+            assert!(new_rating.rating() > old_rating.rating());
+            assert_eq!(recalculated[1].0, player_2);
+        }
+
+        clock.set(time);
+        let old_rating = engine.peek_player_rating_at::<Public>(player_2, time);
+        engine.set_player_rating_floor(player_2, Some(1500.0));
+        // A floor that doesn't bind doesn't change any rating
+        engine.set_player_rating_floor(player_3, Some(1000.0));
+
+        {
+            let recalculated = observer.recalculated.lock().unwrap();
+            assert_eq!(recalculated.len(), 3);
+            assert_eq!(
+                recalculated[2],
+                (
+                    player_2,
+                    old_rating,
+                    engine.peek_player_rating_at::<Public>(player_2, time),
+                ),
+            );
+            assert!(recalculated[2].2.rating() > old_rating.rating());
+        }
+    }
+}
@@ -0,0 +1,990 @@
+//! This mod defines the [`PooledRatingEngine`] struct which manages separate rating pools, e.g. one per game mode,
+//! for players that are registered only once.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::SystemTime;
+
+use crate::algorithm;
+use crate::clock::{Clock, SystemClock};
+use crate::engine::{PlayerHandle, RatingEngine, ResultId, Score};
+use crate::util::{ArenaIndex, GenerationalArena};
+use crate::{
+    ConvertToScale, GlickoError, GlickoSettings, IntoWithSettings, Public, PublicRating, Rating,
+    RatingScale,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An opaque index pointing to a player of a [`PooledRatingEngine`].
+/// This is handed out by [`PooledRatingEngine`] and identifies the player in all pools.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+// In this case, just pooled::PlayerHandle would be confused with engine::PlayerHandle.
+#[allow(clippy::module_name_repetitions)]
+pub struct PooledPlayerHandle(ArenaIndex);
+
+/// Where the first rating of a player in a pool comes from.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PoolSeed<P> {
+    /// Players start with the start rating of the pool's settings.
+    StartRating,
+    /// Players start with their current rating in the given pool.
+    /// If they don't have a rating there either, that pool's seed is used.
+    ///
+    /// The current rating includes the deviation growth of the given pool's current rating period so far,
+    /// and the player is registered at the start of the current rating period of the new pool,
+    /// so that growth is counted twice for the part of the rating period that has already elapsed.
+    /// With short rating periods this is negligible, and it errs on the side of a too high deviation,
+    /// which only makes the first games in the new pool count a bit more.
+    RelatedPool(P),
+}
+
+/// A rating pool as managed by [`PooledRatingEngine`].
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "P: Serialize",
+        deserialize = "P: Deserialize<'de>, C: Default"
+    ))
+)]
+struct Pool<P, C> {
+    engine: RatingEngine<C>,
+    seed: PoolSeed<P>,
+}
+
+/// A collection of rating pools identified by values of type `P`, e.g. one per game mode.
+///
+/// Every pool is a separate [`RatingEngine`] with its own settings and rating periods.
+/// Players are registered once with this struct,
+/// and get a rating in a pool the first time they play in it, seeded according to the pool's [`PoolSeed`].
+///
+/// The current time is taken from a [`Clock`], which is the [`SystemClock`] unless the engine was created with
+/// [`new_with_clock`][PooledRatingEngine::new_with_clock].
+/// Every pool gets a clone of that clock.
+///
+/// # Example
+///
+/// ```
+/// use instant_glicko_2::{GlickoSettings, PublicRating};
+/// use instant_glicko_2::engine::MatchResult;
+/// use instant_glicko_2::pooled::{PoolSeed, PooledRatingEngine};
+///
+/// let settings = GlickoSettings::default();
+///
+/// let mut engine = PooledRatingEngine::new();
+/// engine.add_pool("blitz", settings, PoolSeed::StartRating).unwrap();
+/// engine.add_pool("rapid", settings, PoolSeed::RelatedPool("blitz")).unwrap();
+///
+/// let alice = engine.register_player();
+/// let bob = engine.register_player();
+///
+/// // Alice wins against Bob in blitz
+/// engine.register_result(&"blitz", alice, bob, &MatchResult::Win);
+///
+/// // Alice hasn't played rapid yet, so her rapid rating is seeded from her blitz rating
+/// let alice_blitz_rating: PublicRating = engine.peek_player_rating(&"blitz", alice);
+/// let alice_rapid_rating: PublicRating = engine.peek_player_rating(&"rapid", alice);
+///
+/// assert!(alice_blitz_rating.rating() > settings.start_rating().rating());
+/// assert!((alice_rapid_rating.rating() - alice_blitz_rating.rating()).abs() < 0.1);
+/// ```
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "P: Serialize",
+        deserialize = "P: Deserialize<'de>, C: Default"
+    ))
+)]
+pub struct PooledRatingEngine<P: Hash + Eq, C = SystemClock> {
+    pools: HashMap<P, Pool<P, C>>,
+    // The handles of every player in the pools they have a rating in
+    players: GenerationalArena<HashMap<P, PlayerHandle>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    clock: C,
+}
+
+impl<P: Hash + Eq + Clone> PooledRatingEngine<P> {
+    /// Creates a new [`PooledRatingEngine`] without any pools or players.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::new_with_clock(SystemClock)
+    }
+}
+
+impl<P: Hash + Eq + Clone, C: Clock + Clone> PooledRatingEngine<P, C> {
+    /// Creates a new [`PooledRatingEngine`] without any pools or players that gets the current time from `clock`.
+    #[must_use]
+    pub fn new_with_clock(clock: C) -> Self {
+        PooledRatingEngine {
+            pools: HashMap::new(),
+            players: GenerationalArena::new(),
+            clock,
+        }
+    }
+
+    /// The clock this engine and all its pools get the current time from.
+    #[must_use]
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Adds a new pool with the given settings, starting its first rating period immediately.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::DuplicatePool`] if a pool with the given identifier already exists,
+    /// and [`GlickoError::UnknownPool`] if `seed` refers to a pool that doesn't exist.
+    pub fn add_pool(
+        &mut self,
+        pool: P,
+        settings: GlickoSettings,
+        seed: PoolSeed<P>,
+    ) -> Result<(), GlickoError> {
+        self.add_pool_at(pool, settings, seed, self.clock.now())
+    }
+
+    /// Adds a new pool with the given settings, starting its first rating period at the specified point in time.
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::DuplicatePool`] if a pool with the given identifier already exists,
+    /// and [`GlickoError::UnknownPool`] if `seed` refers to a pool that doesn't exist.
+    pub fn add_pool_at(
+        &mut self,
+        pool: P,
+        settings: GlickoSettings,
+        seed: PoolSeed<P>,
+        start_time: SystemTime,
+    ) -> Result<(), GlickoError> {
+        if self.pools.contains_key(&pool) {
+            return Err(GlickoError::DuplicatePool);
+        }
+        // Related pools have to exist already, so they can't form cycles
+        if let PoolSeed::RelatedPool(related_pool) = &seed {
+            if !self.pools.contains_key(related_pool) {
+                return Err(GlickoError::UnknownPool);
+            }
+        }
+
+        self.pools.insert(
+            pool,
+            Pool {
+                engine: RatingEngine::start_new_at(start_time, settings)
+                    .with_clock(self.clock.clone()),
+                seed,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// The [`RatingEngine`] of the given pool, or `None` if no pool with that identifier exists.
+    ///
+    /// Players only have a [`PlayerHandle`] in this engine once they have a rating in the pool,
+    /// see [`pool_handle`][Self::pool_handle].
+    #[must_use]
+    pub fn pool<Q>(&self, pool: &Q) -> Option<&RatingEngine<C>>
+    where
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.pools.get(pool).map(|pool| &pool.engine)
+    }
+
+    /// The [`RatingEngine`] of the given pool, mutably, or `None` if no pool with that identifier exists.
+    ///
+    /// This gives access to all operations of the pool's engine, e.g. team results, retracting results or changing settings.
+    /// Use [`pool_handle_or_register`][Self::pool_handle_or_register] to get the [`PlayerHandle`] of a player in the pool.
+    ///
+    /// Players must be registered and removed through this engine and not through the pool's engine,
+    /// otherwise their handles in the pool get out of sync with this engine.
+    #[must_use]
+    pub fn pool_mut<Q>(&mut self, pool: &Q) -> Option<&mut RatingEngine<C>>
+    where
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.pools.get_mut(pool).map(|pool| &mut pool.engine)
+    }
+
+    /// The seed of the given pool, or `None` if no pool with that identifier exists.
+    #[must_use]
+    pub fn pool_seed<Q>(&self, pool: &Q) -> Option<&PoolSeed<P>>
+    where
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.pools.get(pool).map(|pool| &pool.seed)
+    }
+
+    /// Returns an [`Iterator`] over the identifiers of all pools.
+    pub fn pools(&self) -> impl Iterator<Item = &P> {
+        self.pools.keys()
+    }
+
+    /// Registers a new player.
+    ///
+    /// The player gets a rating in a pool the first time they play in it.
+    ///
+    /// # Returns
+    ///
+    /// A value that can be later used to identify this player with this engine.
+    pub fn register_player(&mut self) -> PooledPlayerHandle {
+        PooledPlayerHandle(self.players.insert(HashMap::new()))
+    }
+
+    /// Removes a player from this engine and from all pools.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the player was removed, or `false` if `player` was already removed or doesn't belong to this engine.
+    pub fn remove_player(&mut self, player: PooledPlayerHandle) -> bool {
+        let pool_handles = match self.players.remove(player.0) {
+            Some(pool_handles) => pool_handles,
+            None => return false,
+        };
+
+        for (pool, handle) in pool_handles {
+            if let Some(pool) = self.pools.get_mut(&pool) {
+                pool.engine.remove_player::<Public>(handle);
+            }
+        }
+
+        true
+    }
+
+    /// Returns `true` if `player` is currently registered with this engine.
+    #[must_use]
+    pub fn contains_player(&self, player: PooledPlayerHandle) -> bool {
+        self.players.contains(player.0)
+    }
+
+    /// The number of registered players.
+    #[must_use]
+    pub fn player_count(&self) -> usize {
+        self.players.len()
+    }
+
+    /// The [`PlayerHandle`] of a player in the [`RatingEngine`] of the given pool (see [`pool`][Self::pool]),
+    /// or `None` if the player doesn't have a rating in the pool yet, the pool doesn't exist,
+    /// or the player doesn't belong to this engine.
+    #[must_use]
+    pub fn pool_handle<Q>(&self, pool: &Q, player: PooledPlayerHandle) -> Option<PlayerHandle>
+    where
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.players.get(player.0)?.get(pool).copied()
+    }
+
+    /// The [`PlayerHandle`] of a player in the [`RatingEngine`] of the given pool (see [`pool_mut`][Self::pool_mut]),
+    /// registering them with the pool first if they don't have a rating in it yet, see [`PoolSeed`].
+    ///
+    /// This function can close old rating periods of the pool (see [`RatingEngine::maybe_close_rating_periods`]).
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the player's handle in the pool
+    /// and the number of rating periods of the pool that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the pool doesn't exist or if `player` doesn't belong to this engine.
+    /// See [`try_pool_handle_or_register`][Self::try_pool_handle_or_register] for a non-panicking version.
+    ///
+    /// This function might panic if the pool's settings' convergence tolerance is unreasonably low.
+    pub fn pool_handle_or_register<Q>(
+        &mut self,
+        pool: &Q,
+        player: PooledPlayerHandle,
+    ) -> (PlayerHandle, u32)
+    where
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.pool_handle_or_register_at(pool, player, self.clock.now())
+    }
+
+    /// The [`PlayerHandle`] of a player in the [`RatingEngine`] of the given pool,
+    /// registering them with the pool at the given time if they don't have a rating in it yet.
+    ///
+    /// See [`pool_handle_or_register`][Self::pool_handle_or_register].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the player's handle in the pool
+    /// and the number of rating periods of the pool that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the pool doesn't exist or if `player` doesn't belong to this engine.
+    /// See [`try_pool_handle_or_register_at`][Self::try_pool_handle_or_register_at] for a non-panicking version.
+    ///
+    /// This function might panic if the pool's settings' convergence tolerance is unreasonably low.
+    pub fn pool_handle_or_register_at<Q>(
+        &mut self,
+        pool: &Q,
+        player: PooledPlayerHandle,
+        time: SystemTime,
+    ) -> (PlayerHandle, u32)
+    where
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_pool_handle_or_register_at(pool, player, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// The [`PlayerHandle`] of a player in the [`RatingEngine`] of the given pool,
+    /// registering them with the pool first if they don't have a rating in it yet.
+    ///
+    /// See [`pool_handle_or_register`][Self::pool_handle_or_register].
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the player's handle in the pool
+    /// and the number of rating periods of the pool that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPool`] if the pool doesn't exist,
+    /// [`GlickoError::UnknownPooledPlayer`] if `player` doesn't belong to this engine,
+    /// and [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the pool's settings' convergence tolerance is unreasonably low.
+    pub fn try_pool_handle_or_register<Q>(
+        &mut self,
+        pool: &Q,
+        player: PooledPlayerHandle,
+    ) -> Result<(PlayerHandle, u32), GlickoError>
+    where
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_pool_handle_or_register_at(pool, player, self.clock.now())
+    }
+
+    /// The [`PlayerHandle`] of a player in the [`RatingEngine`] of the given pool,
+    /// registering them with the pool at the given time if they don't have a rating in it yet.
+    ///
+    /// See [`pool_handle_or_register`][Self::pool_handle_or_register].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the player's handle in the pool
+    /// and the number of rating periods of the pool that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPool`] if the pool doesn't exist,
+    /// [`GlickoError::UnknownPooledPlayer`] if `player` doesn't belong to this engine,
+    /// and [`GlickoError::NoConvergence`] if a rating calculation failed
+    /// because the pool's settings' convergence tolerance is unreasonably low.
+    /// In that case, the player is not registered with the pool.
+    pub fn try_pool_handle_or_register_at<Q>(
+        &mut self,
+        pool: &Q,
+        player: PooledPlayerHandle,
+        time: SystemTime,
+    ) -> Result<(PlayerHandle, u32), GlickoError>
+    where
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let seed_rating = self.pool_rating_at(pool, player, time)?;
+
+        self.register_with_pool(pool, player, seed_rating, time)
+    }
+
+    /// Registers a result between two players in the current rating period of the given pool.
+    ///
+    /// Players who don't have a rating in the pool yet are registered with the pool first, see [`PoolSeed`].
+    ///
+    /// See [`RatingEngine::register_result`].
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to identify the result in the [`RatingEngine`] of the pool
+    /// and the number of rating periods of the pool that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the pool doesn't exist, if one of the players doesn't belong to this engine,
+    /// or if `score` is not between `0.0` and `1.0`.
+    ///
+    /// This function might panic if the pool's settings' convergence tolerance is unreasonably low.
+    pub fn register_result<S: Score, Q>(
+        &mut self,
+        pool: &Q,
+        player_1: PooledPlayerHandle,
+        player_2: PooledPlayerHandle,
+        score: &S,
+    ) -> (ResultId, u32)
+    where
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.register_result_at(pool, player_1, player_2, score, self.clock.now())
+    }
+
+    /// Registers a result between two players at the given time in the current rating period of the given pool.
+    ///
+    /// See [`register_result`][Self::register_result].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to identify the result in the [`RatingEngine`] of the pool
+    /// and the number of rating periods of the pool that were closed for this operation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the pool doesn't exist, if one of the players doesn't belong to this engine,
+    /// or if `score` is not between `0.0` and `1.0`.
+    ///
+    /// This function might panic if the pool's settings' convergence tolerance is unreasonably low.
+    pub fn register_result_at<S: Score, Q>(
+        &mut self,
+        pool: &Q,
+        player_1: PooledPlayerHandle,
+        player_2: PooledPlayerHandle,
+        score: &S,
+        time: SystemTime,
+    ) -> (ResultId, u32)
+    where
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_register_result_at(pool, player_1, player_2, score, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Registers a result between two players in the current rating period of the given pool.
+    ///
+    /// See [`register_result`][Self::register_result].
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to identify the result in the [`RatingEngine`] of the pool
+    /// and the number of rating periods of the pool that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPool`] if the pool doesn't exist,
+    /// and [`GlickoError::UnknownPooledPlayer`] if one of the players doesn't belong to this engine.
+    /// See [`RatingEngine::try_register_result`] for the other errors.
+    pub fn try_register_result<S: Score, Q>(
+        &mut self,
+        pool: &Q,
+        player_1: PooledPlayerHandle,
+        player_2: PooledPlayerHandle,
+        score: &S,
+    ) -> Result<(ResultId, u32), GlickoError>
+    where
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_register_result_at(pool, player_1, player_2, score, self.clock.now())
+    }
+
+    /// Registers a result between two players at the given time in the current rating period of the given pool.
+    ///
+    /// See [`register_result`][Self::register_result].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing a value that can be used to identify the result in the [`RatingEngine`] of the pool
+    /// and the number of rating periods of the pool that were closed for this operation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPool`] if the pool doesn't exist,
+    /// and [`GlickoError::UnknownPooledPlayer`] if one of the players doesn't belong to this engine.
+    /// See [`RatingEngine::try_register_result_at`] for the other errors.
+    pub fn try_register_result_at<S: Score, Q>(
+        &mut self,
+        pool: &Q,
+        player_1: PooledPlayerHandle,
+        player_2: PooledPlayerHandle,
+        score: &S,
+        time: SystemTime,
+    ) -> Result<(ResultId, u32), GlickoError>
+    where
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        // Validate everything before changing the pool so that invalid arguments don't have side effects.
+        self.pools.get(pool).ok_or(GlickoError::UnknownPool)?;
+        self.pool_handles(player_1)?;
+        self.pool_handles(player_2)?;
+
+        algorithm::validate_score(score.player_score())?;
+        algorithm::validate_score(score.opponent_score())?;
+
+        let player_1_seed = self.pool_rating_at(pool, player_1, time)?;
+        let player_2_seed = self.pool_rating_at(pool, player_2, time)?;
+
+        // Close the elapsed rating periods first, which is the only way registering a player can fail
+        let (_, closed_periods) = self
+            .pools
+            .get_mut(pool)
+            .ok_or(GlickoError::UnknownPool)?
+            .engine
+            .try_maybe_close_rating_periods_at(time)?;
+
+        let player_1_is_new = self.pool_handle(pool, player_1).is_none();
+        let (pool_player_1, _) = self.register_with_pool(pool, player_1, player_1_seed, time)?;
+        let player_2_is_new = self.pool_handle(pool, player_2).is_none();
+        let (pool_player_2, _) = match self.register_with_pool(pool, player_2, player_2_seed, time)
+        {
+            Ok(registered) => registered,
+            Err(err) => {
+                if player_1_is_new {
+                    self.unregister_from_pool(pool, player_1);
+                }
+                return Err(err);
+            }
+        };
+
+        match self
+            .pools
+            .get_mut(pool)
+            .ok_or(GlickoError::UnknownPool)?
+            .engine
+            .try_register_result_at(pool_player_1, pool_player_2, score, time)
+        {
+            Ok((result, _)) => Ok((result, closed_periods)),
+            Err(err) => {
+                // Players who only got a rating in the pool for this result don't keep it
+                if player_1_is_new {
+                    self.unregister_from_pool(pool, player_1);
+                }
+                if player_2_is_new {
+                    self.unregister_from_pool(pool, player_2);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Calculates a player's rating in the given pool at this point in time without changing this engine.
+    ///
+    /// If the player doesn't have a rating in the pool yet,
+    /// this is the rating they would be seeded with (see [`PoolSeed`]).
+    ///
+    /// See [`RatingEngine::peek_player_rating`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the pool doesn't exist or if `player` doesn't belong to this engine.
+    ///
+    /// This function might panic if the pool's settings' convergence tolerance is unreasonably low.
+    #[must_use]
+    pub fn peek_player_rating<Scale: RatingScale, Q>(
+        &self,
+        pool: &Q,
+        player: PooledPlayerHandle,
+    ) -> Rating<Scale>
+    where
+        Public: ConvertToScale<Scale>,
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.peek_player_rating_at(pool, player, self.clock.now())
+    }
+
+    /// Calculates a player's rating in the given pool at a given point in time without changing this engine.
+    ///
+    /// See [`peek_player_rating`][Self::peek_player_rating].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the pool doesn't exist or if `player` doesn't belong to this engine.
+    ///
+    /// This function might panic if the pool's settings' convergence tolerance is unreasonably low.
+    #[must_use]
+    pub fn peek_player_rating_at<Scale: RatingScale, Q>(
+        &self,
+        pool: &Q,
+        player: PooledPlayerHandle,
+        time: SystemTime,
+    ) -> Rating<Scale>
+    where
+        Public: ConvertToScale<Scale>,
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_peek_player_rating_at(pool, player, time)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Calculates a player's rating in the given pool at this point in time without changing this engine.
+    ///
+    /// See [`peek_player_rating`][Self::peek_player_rating].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPool`] if the pool doesn't exist,
+    /// [`GlickoError::UnknownPooledPlayer`] if `player` doesn't belong to this engine,
+    /// and [`GlickoError::NoConvergence`] if the rating calculation failed
+    /// because the pool's settings' convergence tolerance is unreasonably low.
+    pub fn try_peek_player_rating<Scale: RatingScale, Q>(
+        &self,
+        pool: &Q,
+        player: PooledPlayerHandle,
+    ) -> Result<Rating<Scale>, GlickoError>
+    where
+        Public: ConvertToScale<Scale>,
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_peek_player_rating_at(pool, player, self.clock.now())
+    }
+
+    /// Calculates a player's rating in the given pool at a given point in time without changing this engine.
+    ///
+    /// See [`peek_player_rating`][Self::peek_player_rating].
+    ///
+    /// This function is meant mostly for testability.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GlickoError::UnknownPool`] if the pool doesn't exist,
+    /// [`GlickoError::UnknownPooledPlayer`] if `player` doesn't belong to this engine,
+    /// and [`GlickoError::NoConvergence`] if the rating calculation failed
+    /// because the pool's settings' convergence tolerance is unreasonably low.
+    pub fn try_peek_player_rating_at<Scale: RatingScale, Q>(
+        &self,
+        pool: &Q,
+        player: PooledPlayerHandle,
+        time: SystemTime,
+    ) -> Result<Rating<Scale>, GlickoError>
+    where
+        Public: ConvertToScale<Scale>,
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let settings = self
+            .pools
+            .get(pool)
+            .ok_or(GlickoError::UnknownPool)?
+            .engine
+            .settings();
+
+        Ok(self
+            .pool_rating_at(pool, player, time)?
+            .into_with_settings(settings))
+    }
+
+    /// The current rating of a player in the given pool, or the rating they would be seeded with if they don't have one yet.
+    fn pool_rating_at<Q>(
+        &self,
+        pool: &Q,
+        player: PooledPlayerHandle,
+        time: SystemTime,
+    ) -> Result<PublicRating, GlickoError>
+    where
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let pool_data = self.pools.get(pool).ok_or(GlickoError::UnknownPool)?;
+
+        if let Some(&handle) = self.pool_handles(player)?.get(pool) {
+            return pool_data.engine.try_peek_player_rating_at(handle, time);
+        }
+
+        match &pool_data.seed {
+            PoolSeed::StartRating => Ok(pool_data.engine.settings().start_rating()),
+            PoolSeed::RelatedPool(related_pool) => {
+                self.pool_rating_at::<P>(related_pool, player, time)
+            }
+        }
+    }
+
+    /// The [`PlayerHandle`] of a player in the given pool, registering them with `seed_rating` if they don't have one yet.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the handle and the number of rating periods of the pool that were closed for this operation.
+    fn register_with_pool<Q>(
+        &mut self,
+        pool: &Q,
+        player: PooledPlayerHandle,
+        seed_rating: PublicRating,
+        time: SystemTime,
+    ) -> Result<(PlayerHandle, u32), GlickoError>
+    where
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if let Some(&handle) = self.pool_handles(player)?.get(pool) {
+            return Ok((handle, 0));
+        }
+
+        let (pool_key, _) = self
+            .pools
+            .get_key_value(pool)
+            .ok_or(GlickoError::UnknownPool)?;
+        let pool_key = pool_key.clone();
+
+        let (handle, closed_periods) = self
+            .pools
+            .get_mut(pool)
+            .ok_or(GlickoError::UnknownPool)?
+            .engine
+            .try_register_player_at(seed_rating, time)?;
+
+        // Registering succeeded, so the player can be associated with the pool
+        self.players
+            .get_mut(player.0)
+            .ok_or(GlickoError::UnknownPooledPlayer(player))?
+            .insert(pool_key, handle);
+
+        Ok((handle, closed_periods))
+    }
+
+    /// Removes a player from the given pool again, undoing [`register_with_pool`][Self::register_with_pool].
+    fn unregister_from_pool<Q>(&mut self, pool: &Q, player: PooledPlayerHandle)
+    where
+        P: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let handle = self
+            .players
+            .get_mut(player.0)
+            .and_then(|pool_handles| pool_handles.remove(pool));
+
+        if let (Some(handle), Some(pool)) = (handle, self.pools.get_mut(pool)) {
+            pool.engine.remove_player::<Public>(handle);
+        }
+    }
+
+    fn pool_handles(
+        &self,
+        player: PooledPlayerHandle,
+    ) -> Result<&HashMap<P, PlayerHandle>, GlickoError> {
+        self.players
+            .get(player.0)
+            .ok_or(GlickoError::UnknownPooledPlayer(player))
+    }
+}
+
+impl<P: Hash + Eq + Clone> Default for PooledRatingEngine<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, SystemTime};
+
+    use super::{PoolSeed, PooledRatingEngine};
+    use crate::clock::{Clock, ManualClock};
+    use crate::engine::MatchResult;
+    use crate::{GlickoError, GlickoSettings, Public, PublicRating};
+
+    #[test]
+    fn test_pooled_engine() {
+        let blitz_settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(60));
+        let classical_settings = GlickoSettings::default()
+            .with_start_rating(PublicRating::new(1200.0, 350.0, 0.06))
+            .with_rating_period_duration(Duration::from_secs(60 * 60));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = PooledRatingEngine::new();
+        engine
+            .add_pool_at("blitz", blitz_settings, PoolSeed::StartRating, start_time)
+            .unwrap();
+        engine
+            .add_pool_at(
+                "rapid",
+                blitz_settings,
+                PoolSeed::RelatedPool("blitz"),
+                start_time,
+            )
+            .unwrap();
+        engine
+            .add_pool_at(
+                "classical",
+                classical_settings,
+                PoolSeed::StartRating,
+                start_time,
+            )
+            .unwrap();
+
+        assert_eq!(
+            engine.add_pool_at("blitz", blitz_settings, PoolSeed::StartRating, start_time),
+            Err(GlickoError::DuplicatePool),
+        );
+        assert_eq!(
+            engine.add_pool_at(
+                "bullet",
+                blitz_settings,
+                PoolSeed::RelatedPool("unknown"),
+                start_time,
+            ),
+            Err(GlickoError::UnknownPool),
+        );
+
+        let alice = engine.register_player();
+        let bob = engine.register_player();
+
+        let time = start_time + Duration::from_secs(90);
+        engine.register_result_at(&"blitz", alice, bob, &MatchResult::Win, time);
+        assert!(engine.pool_handle(&"blitz", alice).is_some());
+        assert!(engine.pool_handle(&"rapid", alice).is_none());
+
+        // The blitz pool has its own clock
+        assert_eq!(
+            engine.pool(&"blitz").unwrap().last_rating_period_start(),
+            start_time + Duration::from_secs(60),
+        );
+        assert_eq!(
+            engine
+                .pool(&"classical")
+                .unwrap()
+                .last_rating_period_start(),
+            start_time,
+        );
+
+        // Ratings in other pools are seeded lazily
+        let blitz_rating: PublicRating = engine.peek_player_rating_at(&"blitz", alice, time);
+        assert_eq!(
+            engine.peek_player_rating_at::<Public, _>(&"rapid", alice, time),
+            blitz_rating,
+        );
+        assert_eq!(
+            engine.peek_player_rating_at::<Public, _>(&"classical", alice, time),
+            classical_settings.start_rating(),
+        );
+
+        engine.register_result_at(&"rapid", alice, bob, &MatchResult::Loss, time);
+        let rapid_rating: PublicRating = engine.peek_player_rating_at(&"rapid", alice, time);
+        assert!(rapid_rating.rating() < blitz_rating.rating());
+        // The blitz rating is unaffected by rapid games
+        assert_eq!(
+            engine.peek_player_rating_at::<Public, _>(&"blitz", alice, time),
+            blitz_rating,
+        );
+
+        assert!(engine.remove_player(bob));
+        assert!(!engine.contains_player(bob));
+        assert_eq!(engine.pool(&"blitz").unwrap().player_count(), 1);
+        assert_eq!(
+            engine.try_register_result_at(&"blitz", alice, bob, &MatchResult::Win, time),
+            Err(GlickoError::UnknownPooledPlayer(bob)),
+        );
+        assert_eq!(
+            engine.try_register_result_at(&"bullet", alice, alice, &MatchResult::Win, time),
+            Err(GlickoError::UnknownPool),
+        );
+    }
+
+    #[test]
+    fn test_pooled_engine_clock() {
+        let blitz_settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(60));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+        let time = start_time + Duration::from_secs(90);
+
+        // The methods without the `_at` suffix get the time from the clock, which every pool shares
+        let clock = ManualClock::new(start_time);
+        let mut clocked_engine = PooledRatingEngine::new_with_clock(clock.clone());
+        clocked_engine
+            .add_pool("blitz", blitz_settings, PoolSeed::StartRating)
+            .unwrap();
+
+        let alice = clocked_engine.register_player();
+        let bob = clocked_engine.register_player();
+        clock.advance(Duration::from_secs(90));
+        clocked_engine.register_result(&"blitz", alice, bob, &MatchResult::Win);
+
+        let blitz_engine = clocked_engine.pool(&"blitz").unwrap();
+        assert_eq!(blitz_engine.clock().now(), time);
+        assert_eq!(
+            blitz_engine.last_rating_period_start(),
+            start_time + Duration::from_secs(60),
+        );
+        assert_eq!(
+            clocked_engine.peek_player_rating::<Public, _>(&"blitz", alice),
+            clocked_engine.peek_player_rating_at::<Public, _>(&"blitz", alice, time),
+        );
+    }
+
+    #[test]
+    fn test_pooled_engine_pool_mut() {
+        let settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(60));
+        let strict_settings = settings.with_convergence_tolerance(f64::MIN_POSITIVE);
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = PooledRatingEngine::new();
+        engine
+            .add_pool_at("blitz", settings, PoolSeed::StartRating, start_time)
+            .unwrap();
+        engine
+            .add_pool_at("strict", strict_settings, PoolSeed::StartRating, start_time)
+            .unwrap();
+
+        let alice = engine.register_player();
+        let bob = engine.register_player();
+
+        // Operations the pooled engine doesn't forward are available on the pool's engine
+        let (alice_blitz, _) = engine.pool_handle_or_register_at(&"blitz", alice, start_time);
+        let (bob_blitz, _) = engine.pool_handle_or_register_at(&"blitz", bob, start_time);
+        assert_eq!(engine.pool_handle(&"blitz", alice), Some(alice_blitz));
+        assert_eq!(
+            engine.pool_handle_or_register_at(&"blitz", alice, start_time),
+            (alice_blitz, 0),
+        );
+
+        let blitz = engine.pool_mut(&"blitz").unwrap();
+        let (result, _) =
+            blitz.register_result_at(alice_blitz, bob_blitz, &MatchResult::Win, start_time);
+        blitz.retract_result_at(result, start_time);
+        blitz.set_player_rating_floor(alice_blitz, Some(1400.0));
+        assert_eq!(
+            engine
+                .pool(&"blitz")
+                .unwrap()
+                .player_rating_floor(alice_blitz),
+            Some(1400.0),
+        );
+        assert!(engine.pool_mut(&"bullet").is_none());
+
+        // A failed registration doesn't leave new players behind in the pool
+        engine.register_result_at(&"strict", alice, bob, &MatchResult::Win, start_time);
+        let carol = engine.register_player();
+        let time = start_time + Duration::from_secs(60);
+        assert!(matches!(
+            engine.try_register_result_at(&"strict", carol, alice, &MatchResult::Win, time),
+            Err(GlickoError::NoConvergence { .. }),
+        ));
+        assert_eq!(engine.pool_handle(&"strict", carol), None);
+        assert_eq!(engine.pool(&"strict").unwrap().player_count(), 2);
+    }
+}
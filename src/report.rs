@@ -0,0 +1,217 @@
+//! This mod defines the [`PeriodCloseReport`] struct which describes how a closed rating period changed the ratings of a [`RatingEngine`][crate::engine::RatingEngine].
+//!
+//! See [`RatingEngine::maybe_close_rating_periods_with_reports`][crate::engine::RatingEngine::maybe_close_rating_periods_with_reports].
+
+use std::time::SystemTime;
+
+use crate::engine::PlayerHandle;
+use crate::PublicRating;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How the rating of one player changed when a rating period was closed.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlayerPeriodChange {
+    player: PlayerHandle,
+    old_rating: PublicRating,
+    new_rating: PublicRating,
+    games_played: u64,
+}
+
+impl PlayerPeriodChange {
+    pub(crate) fn new(
+        player: PlayerHandle,
+        old_rating: PublicRating,
+        new_rating: PublicRating,
+        games_played: u64,
+    ) -> Self {
+        PlayerPeriodChange {
+            player,
+            old_rating,
+            new_rating,
+            games_played,
+        }
+    }
+
+    /// The player.
+    #[must_use]
+    pub fn player(&self) -> PlayerHandle {
+        self.player
+    }
+
+    /// The player's rating at the start of the rating period.
+    #[must_use]
+    pub fn old_rating(&self) -> PublicRating {
+        self.old_rating
+    }
+
+    /// The player's rating at the end of the rating period.
+    #[must_use]
+    pub fn new_rating(&self) -> PublicRating {
+        self.new_rating
+    }
+
+    /// The number of results of the player in the rating period.
+    /// A team result or a ranked result counts as one result.
+    #[must_use]
+    pub fn games_played(&self) -> u64 {
+        self.games_played
+    }
+
+    /// The difference between the new and the old rating value.
+    #[must_use]
+    pub fn rating_change(&self) -> f64 {
+        self.new_rating.rating() - self.old_rating.rating()
+    }
+}
+
+/// How the ratings of all players changed when a rating period was closed.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PeriodCloseReport {
+    period_start: SystemTime,
+    period_end: SystemTime,
+    players: Vec<PlayerPeriodChange>,
+}
+
+impl PeriodCloseReport {
+    pub(crate) fn new(
+        period_start: SystemTime,
+        period_end: SystemTime,
+        players: Vec<PlayerPeriodChange>,
+    ) -> Self {
+        PeriodCloseReport {
+            period_start,
+            period_end,
+            players,
+        }
+    }
+
+    /// The start of the closed rating period.
+    #[must_use]
+    pub fn period_start(&self) -> SystemTime {
+        self.period_start
+    }
+
+    /// The end of the closed rating period.
+    #[must_use]
+    pub fn period_end(&self) -> SystemTime {
+        self.period_end
+    }
+
+    /// The rating changes of all players, sorted by handle.
+    ///
+    /// Players without any results in the rating period are included, since their deviation changes as well.
+    #[must_use]
+    pub fn players(&self) -> &[PlayerPeriodChange] {
+        &self.players
+    }
+
+    /// The rating change of a player, or `None` if the player wasn't registered when the rating period was closed.
+    #[must_use]
+    pub fn player(&self, player: PlayerHandle) -> Option<&PlayerPeriodChange> {
+        self.players
+            .binary_search_by_key(&player, PlayerPeriodChange::player)
+            .ok()
+            .map(|index| &self.players[index])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, SystemTime};
+
+    use crate::engine::{MatchResult, RatingEngine};
+    use crate::{GlickoError, GlickoSettings, Public};
+
+    #[test]
+    fn test_period_close_report() {
+        let settings =
+            GlickoSettings::default().with_rating_period_duration(Duration::from_secs(60));
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings);
+
+        let player_1 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        let player_2 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        let idle_player = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+
+        engine.register_result_at(player_1, player_2, &MatchResult::Win, start_time);
+        engine.register_result_at(player_1, player_2, &MatchResult::Draw, start_time);
+
+        let mut expected_engine = engine.clone();
+
+        let time = start_time + Duration::from_secs(150);
+        let (_, reports) = engine.maybe_close_rating_periods_with_reports_at(time);
+        let (_, closed_periods) = expected_engine.maybe_close_rating_periods_at(time);
+        assert_eq!(reports.len(), closed_periods as usize);
+        assert_eq!(engine, expected_engine);
+
+        let report = &reports[0];
+        assert_eq!(report.period_start(), start_time);
+        assert_eq!(report.period_end(), start_time + Duration::from_secs(60));
+        assert_eq!(report.players().len(), 3);
+
+        let change = report.player(player_1).unwrap();
+        assert_eq!(change.games_played(), 2);
+        assert_eq!(change.old_rating(), settings.start_rating());
+        assert!(change.rating_change() > 0.0);
+
+        let idle_change = report.player(idle_player).unwrap();
+        assert_eq!(idle_change.games_played(), 0);
+        assert!(idle_change.rating_change().abs() < f64::EPSILON);
+        assert!(idle_change.new_rating().deviation() > idle_change.old_rating().deviation());
+
+        // The next report starts where the previous one ended
+        let change = reports[1].player(player_1).unwrap();
+        assert_eq!(change.games_played(), 0);
+        assert_eq!(
+            change.old_rating(),
+            report.player(player_1).unwrap().new_rating()
+        );
+        assert_eq!(
+            change.new_rating(),
+            engine.peek_player_rating_at::<Public>(player_1, start_time + Duration::from_secs(120)),
+        );
+    }
+
+    #[test]
+    fn test_period_close_report_error() {
+        let settings = GlickoSettings::default()
+            .with_rating_period_duration(Duration::from_secs(60))
+            .with_convergence_tolerance(f64::MIN_POSITIVE);
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings);
+
+        let player_1 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        let player_2 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+
+        engine.register_result_at(player_1, player_2, &MatchResult::Win, start_time);
+
+        let expected_engine = engine.clone();
+
+        let time = start_time + Duration::from_secs(150);
+        let (elapsed_periods, reports, err) =
+            engine.try_maybe_close_rating_periods_with_reports_at(time);
+        assert!(matches!(err, Some(GlickoError::NoConvergence { .. })));
+        // The rating period with the result stays open
+        assert!(reports.is_empty());
+        assert!((elapsed_periods - 2.5).abs() < f64::EPSILON);
+        assert_eq!(engine, expected_engine);
+    }
+}
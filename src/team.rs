@@ -0,0 +1,210 @@
+//! This mod defines the [`TeamRatingAggregation`] enum which combines the ratings of a team's members into one team rating.
+//!
+//! See [`RatingEngine::register_team_result`][crate::engine::RatingEngine::register_team_result].
+
+use std::cmp::Ordering;
+
+use crate::{Rating, RatingScale};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A strategy for combining the ratings of a team's members into one rating for the whole team.
+///
+/// In a team result, every member plays a game against the combined rating of the opposing team.
+/// The members' own ratings are updated individually, so each member's own deviation decides how much their rating changes.
+///
+/// All strategies combine a single member's rating into exactly that rating.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TeamRatingAggregation {
+    /// The team's rating value, deviation and volatility are the means of the members' values.
+    Mean,
+    /// The team's rating value and volatility are the means of the members' values,
+    /// and the team's deviation is the square root of the sum of the members' variances.
+    ///
+    /// This makes a team's rating less certain than that of any of its members.
+    SumOfVariances,
+    /// The team's rating is the rating of the member with the lowest rating value.
+    WeakestLink,
+}
+
+impl TeamRatingAggregation {
+    /// Combines the ratings of a team's `members` into one rating for the whole team.
+    ///
+    /// # Returns
+    ///
+    /// The team rating, or `None` if `members` is empty.
+    #[must_use]
+    pub fn team_rating<Scale: RatingScale>(
+        self,
+        members: &[Rating<Scale>],
+    ) -> Option<Rating<Scale>> {
+        match members {
+            [] => return None,
+            [member] => return Some(*member),
+            _ => (),
+        }
+
+        // The number of members won't be anywhere near big enough to lose precision
+        #[allow(clippy::cast_precision_loss)]
+        let member_count = members.len() as f64;
+        let mean = |value: fn(&Rating<Scale>) -> f64| {
+            members.iter().map(value).sum::<f64>() / member_count
+        };
+
+        let team_rating = match self {
+            TeamRatingAggregation::Mean => Rating::new(
+                mean(Rating::rating),
+                mean(Rating::deviation),
+                mean(Rating::volatility),
+            ),
+            TeamRatingAggregation::SumOfVariances => {
+                let variance = members
+                    .iter()
+                    .map(|member| member.deviation() * member.deviation())
+                    .sum::<f64>();
+
+                Rating::new(
+                    mean(Rating::rating),
+                    variance.sqrt(),
+                    mean(Rating::volatility),
+                )
+            }
+            TeamRatingAggregation::WeakestLink => {
+                members.iter().copied().min_by(|member_1, member_2| {
+                    member_1.partial_cmp(member_2).unwrap_or(Ordering::Equal)
+                })?
+            }
+        };
+
+        Some(team_rating)
+    }
+}
+
+impl Default for TeamRatingAggregation {
+    fn default() -> Self {
+        TeamRatingAggregation::Mean
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use super::TeamRatingAggregation;
+    use crate::engine::{MatchResult, RatingEngine};
+    use crate::{GlickoError, GlickoSettings, Public, PublicRating};
+
+    #[test]
+    fn test_team_rating() {
+        let members = [
+            PublicRating::new(1700.0, 30.0, 0.06),
+            PublicRating::new(1500.0, 40.0, 0.06),
+        ];
+
+        assert_eq!(
+            TeamRatingAggregation::Mean.team_rating(&members),
+            Some(PublicRating::new(1600.0, 35.0, 0.06)),
+        );
+        assert_eq!(
+            TeamRatingAggregation::SumOfVariances.team_rating(&members),
+            Some(PublicRating::new(1600.0, 50.0, 0.06)),
+        );
+        assert_eq!(
+            TeamRatingAggregation::WeakestLink.team_rating(&members),
+            Some(members[1]),
+        );
+        assert_eq!(
+            TeamRatingAggregation::Mean.team_rating(&members[..1]),
+            Some(members[0]),
+        );
+        assert_eq!(TeamRatingAggregation::Mean.team_rating::<Public>(&[]), None);
+    }
+
+    #[test]
+    fn test_register_team_result() {
+        let settings = GlickoSettings::default();
+
+        let start_time = SystemTime::UNIX_EPOCH;
+
+        let mut engine = RatingEngine::start_new_at(start_time, settings)
+            .with_team_rating_aggregation(TeamRatingAggregation::WeakestLink)
+            .with_event_log();
+
+        let veteran = engine
+            .register_player_at(PublicRating::new(1600.0, 50.0, 0.06), start_time)
+            .0;
+        let newcomer = engine
+            .register_player_at(PublicRating::new(1600.0, 300.0, 0.06), start_time)
+            .0;
+        let opponent_1 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+        let opponent_2 = engine
+            .register_player_at(settings.start_rating(), start_time)
+            .0;
+
+        let unchanged_engine = engine.clone();
+
+        let (result, _) = engine.register_team_result_at(
+            &[veteran, newcomer],
+            &[opponent_1, opponent_2],
+            &MatchResult::Win,
+            start_time,
+        );
+
+        // Both members win against the same team rating, but the less certain rating changes more
+        let veteran_rating: PublicRating = engine.peek_player_rating_at(veteran, start_time);
+        let newcomer_rating: PublicRating = engine.peek_player_rating_at(newcomer, start_time);
+        assert!(veteran_rating.rating() > 1600.0);
+        assert!(newcomer_rating.rating() > veteran_rating.rating());
+
+        let opponent_rating: PublicRating = engine.peek_player_rating_at(opponent_1, start_time);
+        assert!(opponent_rating.rating() < settings.start_rating().rating());
+        assert_eq!(
+            engine.peek_player_rating_at::<Public>(opponent_2, start_time),
+            opponent_rating,
+        );
+
+        let events = engine.event_log().unwrap().to_vec();
+        let replayed = RatingEngine::replay(settings, &events).unwrap();
+        assert_eq!(replayed, engine);
+
+        // Team results can be amended like normal results
+        engine.amend_result_at(result, &MatchResult::Loss, start_time);
+        let veteran_rating: PublicRating = engine.peek_player_rating_at(veteran, start_time);
+        assert!(veteran_rating.rating() < 1600.0);
+
+        // A result between teams of one player is the same as a normal result
+        let mut team_engine = unchanged_engine.clone();
+        team_engine.register_team_result_at(
+            &[veteran],
+            &[opponent_1],
+            &MatchResult::Draw,
+            start_time,
+        );
+        let mut result_engine = unchanged_engine;
+        result_engine.register_result_at(veteran, opponent_1, &MatchResult::Draw, start_time);
+        for player in [veteran, opponent_1] {
+            assert_eq!(
+                team_engine.peek_player_rating_at::<Public>(player, start_time),
+                result_engine.peek_player_rating_at::<Public>(player, start_time),
+            );
+        }
+
+        assert_eq!(
+            engine.try_register_team_result_at(&[], &[opponent_1], &MatchResult::Win, start_time),
+            Err(GlickoError::InvalidTeams),
+        );
+        assert_eq!(
+            engine.try_register_team_result_at(
+                &[veteran, opponent_1],
+                &[opponent_1],
+                &MatchResult::Win,
+                start_time,
+            ),
+            Err(GlickoError::InvalidTeams),
+        );
+    }
+}
@@ -3,6 +3,8 @@
 
 use std::slice::{IterMut, SliceIndex};
 
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -71,3 +73,218 @@ impl<T> PushOnlyVec<T> {
         self.0.iter_mut()
     }
 }
+
+/// An opaque index into a [`GenerationalArena`].
+///
+/// An index consists of a slot and the generation of that slot at the time the value was inserted.
+/// When a value is removed, the generation of its slot is increased,
+/// so indices to removed values can be detected even if the slot has been reused since.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ArenaIndex {
+    slot: usize,
+    generation: u64,
+}
+
+impl ArenaIndex {
+    /// The slot in the arena this index points to.
+    #[must_use]
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    /// The generation of the slot this index was created for.
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct ArenaSlot<T> {
+    generation: u64,
+    value: Option<T>,
+}
+
+/// A [`Vec`]-backed arena that reuses the slots of removed values.
+///
+/// Every value is identified by an [`ArenaIndex`].
+/// Indices to removed values stay invalid, even if their slot has been reused for a different value.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GenerationalArena<T> {
+    slots: Vec<ArenaSlot<T>>,
+    free_slots: Vec<usize>,
+    len: usize,
+}
+
+impl<T> Default for GenerationalArena<T> {
+    fn default() -> Self {
+        GenerationalArena::new()
+    }
+}
+
+impl<T> GenerationalArena<T> {
+    /// Creates a new, empty `GenerationalArena<T>`.
+    #[must_use]
+    pub fn new() -> Self {
+        GenerationalArena {
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// The number of values in the arena.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the arena contains no values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts a value into the arena, reusing a free slot if there is one.
+    ///
+    /// # Returns
+    ///
+    /// The index that can later be used to access the value.
+    pub fn insert(&mut self, value: T) -> ArenaIndex {
+        self.len += 1;
+
+        if let Some(slot) = self.free_slots.pop() {
+            let arena_slot = &mut self.slots[slot];
+            arena_slot.value = Some(value);
+
+            ArenaIndex {
+                slot,
+                generation: arena_slot.generation,
+            }
+        } else {
+            let slot = self.slots.len();
+            self.slots.push(ArenaSlot {
+                generation: 0,
+                value: Some(value),
+            });
+
+            ArenaIndex {
+                slot,
+                generation: 0,
+            }
+        }
+    }
+
+    /// Removes the value at the given index from the arena.
+    /// The slot of the value will be reused by later insertions.
+    ///
+    /// # Returns
+    ///
+    /// The removed value, or `None` if the index is stale or doesn't belong to this arena.
+    pub fn remove(&mut self, index: ArenaIndex) -> Option<T> {
+        let arena_slot = self.slots.get_mut(index.slot)?;
+
+        if arena_slot.generation != index.generation {
+            return None;
+        }
+
+        let value = arena_slot.value.take()?;
+        arena_slot.generation += 1;
+        self.free_slots.push(index.slot);
+        self.len -= 1;
+
+        Some(value)
+    }
+
+    /// Returns `true` if the arena contains a value for the given index.
+    #[must_use]
+    pub fn contains(&self, index: ArenaIndex) -> bool {
+        self.get(index).is_some()
+    }
+
+    /// Returns a reference to the value at the given index,
+    /// or `None` if the index is stale or doesn't belong to this arena.
+    #[must_use]
+    pub fn get(&self, index: ArenaIndex) -> Option<&T> {
+        self.slots
+            .get(index.slot)
+            .filter(|arena_slot| arena_slot.generation == index.generation)
+            .and_then(|arena_slot| arena_slot.value.as_ref())
+    }
+
+    /// Returns a mutable reference to the value at the given index,
+    /// or `None` if the index is stale or doesn't belong to this arena.
+    #[must_use]
+    pub fn get_mut(&mut self, index: ArenaIndex) -> Option<&mut T> {
+        self.slots
+            .get_mut(index.slot)
+            .filter(|arena_slot| arena_slot.generation == index.generation)
+            .and_then(|arena_slot| arena_slot.value.as_mut())
+    }
+
+    /// Returns an iterator over all indices and values in the arena, in slot order.
+    pub fn iter(&self) -> impl Iterator<Item = (ArenaIndex, &T)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, arena_slot)| {
+                arena_slot.value.as_ref().map(|value| {
+                    (
+                        ArenaIndex {
+                            slot,
+                            generation: arena_slot.generation,
+                        },
+                        value,
+                    )
+                })
+            })
+    }
+
+    /// Returns a parallel iterator over all indices and values in the arena.
+    /// Collecting it preserves slot order.
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    pub fn par_iter(&self) -> impl ParallelIterator<Item = (ArenaIndex, &T)> + '_
+    where
+        T: Sync,
+    {
+        self.slots
+            .par_iter()
+            .enumerate()
+            .filter_map(|(slot, arena_slot)| {
+                arena_slot.value.as_ref().map(|value| {
+                    (
+                        ArenaIndex {
+                            slot,
+                            generation: arena_slot.generation,
+                        },
+                        value,
+                    )
+                })
+            })
+    }
+
+    /// Returns an iterator over all indices and values in the arena, in slot order,
+    /// that allows mutating each value.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (ArenaIndex, &mut T)> {
+        self.slots
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(slot, arena_slot)| {
+                let generation = arena_slot.generation;
+
+                arena_slot
+                    .value
+                    .as_mut()
+                    .map(|value| (ArenaIndex { slot, generation }, value))
+            })
+    }
+
+    /// Returns an iterator over all indices in the arena, in slot order.
+    pub fn indices(&self) -> impl Iterator<Item = ArenaIndex> + '_ {
+        self.iter().map(|(index, _)| index)
+    }
+}